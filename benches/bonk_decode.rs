@@ -0,0 +1,144 @@
+//! Bonk 账户解码基准
+//!
+//! 测量 `*_decode`（只做 borsh 解码）和 `*_parser`（含 discriminator 校验和 `AccountPretty`
+//! 字段复制）的吞吐量，以每秒解码的账户数量报告。账户数据由固定字段值序列化生成，
+//! 每次运行的输入完全相同。
+//!
+//! ```text
+//! cargo bench --bench bonk_decode
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::common::EventMetadata;
+use solana_streamer_sdk::streaming::event_parser::protocols::bonk::{
+    global_config_decode, global_config_parser, platform_config_decode, platform_config_parser,
+    pool_state_decode, pool_state_parser, GlobalConfig, PlatformConfig, PlatformCurveParam,
+    PoolState, BONK_PROGRAM_ID, GLOBAL_CONFIG_DISCRIMINATOR, PLATFORM_CONFIG_DISCRIMINATOR,
+    POOL_STATE_DISCRIMINATOR,
+};
+use solana_streamer_sdk::streaming::grpc::AccountPretty;
+use std::hint::black_box;
+
+/// 固定的 pubkey，避免使用随机地址
+fn fixture_pubkey(seed: u8) -> Pubkey {
+    Pubkey::new_from_array([seed; 32])
+}
+
+/// 带 discriminator 的账户数据
+fn account_data<T: borsh::BorshSerialize>(discriminator: &[u8; 8], value: &T) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend(borsh::to_vec(value).expect("fixture serialization cannot fail"));
+    data
+}
+
+fn pool_state_fixture() -> Vec<u8> {
+    let pool_state = PoolState {
+        epoch: 800,
+        status: 0,
+        base_decimals: 6,
+        quote_decimals: 9,
+        supply: 1_000_000_000_000_000,
+        total_base_sell: 793_100_000_000_000,
+        virtual_base: 1_073_025_605_596_382,
+        virtual_quote: 30_000_852_951,
+        real_base: 120_000_000_000_000,
+        real_quote: 5_000_000_000,
+        total_quote_fund_raising: 85_000_000_000,
+        global_config: fixture_pubkey(1),
+        platform_config: fixture_pubkey(2),
+        base_mint: fixture_pubkey(3),
+        quote_mint: fixture_pubkey(4),
+        base_vault: fixture_pubkey(5),
+        quote_vault: fixture_pubkey(6),
+        creator: fixture_pubkey(7),
+        ..Default::default()
+    };
+    account_data(&POOL_STATE_DISCRIMINATOR, &pool_state)
+}
+
+fn global_config_fixture() -> Vec<u8> {
+    let global_config = GlobalConfig {
+        epoch: 800,
+        trade_fee_rate: 2_500,
+        max_share_fee_rate: 10_000,
+        min_base_supply: 10_000_000,
+        quote_mint: fixture_pubkey(4),
+        protocol_fee_owner: fixture_pubkey(8),
+        migrate_fee_owner: fixture_pubkey(9),
+        migrate_to_amm_wallet: fixture_pubkey(10),
+        migrate_to_cpswap_wallet: fixture_pubkey(11),
+        ..Default::default()
+    };
+    account_data(&GLOBAL_CONFIG_DISCRIMINATOR, &global_config)
+}
+
+fn platform_config_fixture(curve_params: usize) -> Vec<u8> {
+    let platform_config = PlatformConfig {
+        epoch: 800,
+        platform_fee_wallet: fixture_pubkey(12),
+        platform_nft_wallet: fixture_pubkey(13),
+        fee_rate: 1_000,
+        creator_fee_rate: 500,
+        curve_params: vec![
+            PlatformCurveParam { global_config: fixture_pubkey(1), ..Default::default() };
+            curve_params
+        ],
+        ..Default::default()
+    };
+    account_data(&PLATFORM_CONFIG_DISCRIMINATOR, &platform_config)
+}
+
+fn fixture_account(data: Vec<u8>) -> AccountPretty {
+    AccountPretty {
+        slot: 300_000_000,
+        pubkey: fixture_pubkey(14),
+        lamports: 2_039_280,
+        owner: BONK_PROGRAM_ID,
+        data,
+        ..Default::default()
+    }
+}
+
+fn bench_pool_state(c: &mut Criterion) {
+    let data = pool_state_fixture();
+    let account = fixture_account(data.clone());
+    let mut group = c.benchmark_group("pool_state");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("decode", |b| b.iter(|| pool_state_decode(black_box(&data[8..]))));
+    group.bench_function("parser", |b| {
+        b.iter(|| pool_state_parser(black_box(&account), EventMetadata::default()))
+    });
+    group.finish();
+}
+
+fn bench_global_config(c: &mut Criterion) {
+    let data = global_config_fixture();
+    let account = fixture_account(data.clone());
+    let mut group = c.benchmark_group("global_config");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("decode", |b| b.iter(|| global_config_decode(black_box(&data[8..]))));
+    group.bench_function("parser", |b| {
+        b.iter(|| global_config_parser(black_box(&account), EventMetadata::default()))
+    });
+    group.finish();
+}
+
+fn bench_platform_config(c: &mut Criterion) {
+    let mut group = c.benchmark_group("platform_config");
+    group.throughput(Throughput::Elements(1));
+    for curve_params in [0, 1, 8, 32] {
+        let data = platform_config_fixture(curve_params);
+        let account = fixture_account(data.clone());
+        group.bench_with_input(BenchmarkId::new("decode", curve_params), &data, |b, data| {
+            b.iter(|| platform_config_decode(black_box(&data[8..])))
+        });
+        group.bench_with_input(BenchmarkId::new("parser", curve_params), &account, |b, account| {
+            b.iter(|| platform_config_parser(black_box(account), EventMetadata::default()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_pool_state, bench_global_config, bench_platform_config);
+criterion_main!(benches);
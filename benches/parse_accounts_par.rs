@@ -0,0 +1,75 @@
+//! 快照回填解析基准：逐个解析与 `parse_accounts_par` 并行解析的对比
+//!
+//! 输入为 10 万个 Bonk `PoolState` 账户，以每秒解析的账户数量报告。
+//!
+//! ```text
+//! cargo bench --bench parse_accounts_par --features rayon
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use solana_sdk::pubkey::Pubkey;
+use solana_streamer_sdk::streaming::event_parser::core::account_event_parser::AccountEventParser;
+use solana_streamer_sdk::streaming::event_parser::protocols::bonk::{
+    PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+};
+use solana_streamer_sdk::streaming::event_parser::Protocol;
+use solana_streamer_sdk::streaming::grpc::AccountPretty;
+use solana_streamer_sdk::streaming::parse_accounts_par;
+use std::hint::black_box;
+
+const ACCOUNTS: u64 = 100_000;
+
+fn fixture_accounts() -> Vec<AccountPretty> {
+    (0..ACCOUNTS)
+        .map(|i| {
+            let pool_state = PoolState {
+                epoch: 800,
+                base_decimals: 6,
+                quote_decimals: 9,
+                virtual_base: 1_073_025_605_596_382,
+                virtual_quote: 30_000_852_951,
+                real_base: i,
+                real_quote: i * 10,
+                total_quote_fund_raising: 85_000_000_000,
+                ..Default::default()
+            };
+            let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+            data.extend(borsh::to_vec(&pool_state).expect("fixture serialization cannot fail"));
+            let mut pubkey = [0; 32];
+            pubkey[..8].copy_from_slice(&i.to_le_bytes());
+            AccountPretty {
+                slot: 300_000_000 + i,
+                pubkey: Pubkey::new_from_array(pubkey),
+                lamports: 2_039_280,
+                owner: BONK_PROGRAM_ID,
+                data,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn bench_parse_accounts(c: &mut Criterion) {
+    let accounts = fixture_accounts();
+    let protocols = [Protocol::Bonk];
+    let mut group = c.benchmark_group("parse_accounts");
+    group.throughput(Throughput::Elements(ACCOUNTS));
+    group.sample_size(10);
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            black_box(&accounts)
+                .iter()
+                .map(|account| {
+                    AccountEventParser::parse_account_event(&protocols, account.clone(), None)
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| parse_accounts_par(black_box(&accounts), &protocols, None))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_accounts);
+criterion_main!(benches);
@@ -1,2 +1,4 @@
+pub mod string_numbers;
 pub mod types;
+pub use string_numbers::StringNumbers;
 pub use types::*;
@@ -0,0 +1,575 @@
+//! Bonk（Raydium LaunchLab）账户结构体和解码函数
+//!
+//! 只依赖 `core` 和 `alloc`，见 [`crate::decode`]。启用 `std` 时同时从
+//! `streaming::event_parser::protocols::bonk` 导出，那里还为这些类型补充了价格计算、展示视图
+//! 等依赖 std 的方法
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey;
+
+use super::{read_u32_le, DecodeError, DiscriminatorScheme};
+
+/// Bonk 账户数据的前缀格式，账户解析器按此提取数据主体
+pub const BONK_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::Anchor8;
+
+/// padding 字段不参与 serde 序列化（仅用于 borsh 解码对齐），反序列化时填充为 0
+fn zeroed_padding<T: Copy + Default, const N: usize>() -> [T; N] {
+    [T::default(); N]
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub enum PoolStatus {
+    #[default]
+    Fund,
+    Migrate,
+    Trade,
+}
+
+impl PoolStatus {
+    /// 从链上的原始取值转换：0 = Fund，1 = Migrate，2 = Trade，其他取值返回 `None`
+    pub fn from_u8(value: u8) -> Option<PoolStatus> {
+        match value {
+            0 => Some(PoolStatus::Fund),
+            1 => Some(PoolStatus::Migrate),
+            2 => Some(PoolStatus::Trade),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum AmmFeeOn {
+    QuoteToken = 0,
+    BothToken = 1,
+}
+
+impl Default for AmmFeeOn {
+    fn default() -> Self {
+        Self::QuoteToken
+    }
+}
+
+impl AmmFeeOn {
+    /// 从链上的原始取值转换：0 = QuoteToken，1 = BothToken，其他取值返回 `None`
+    pub fn from_u8(value: u8) -> Option<AmmFeeOn> {
+        match value {
+            0 => Some(AmmFeeOn::QuoteToken),
+            1 => Some(AmmFeeOn::BothToken),
+            _ => None,
+        }
+    }
+
+    /// 链上的原始取值
+    pub fn as_u8(&self) -> u8 {
+        self.clone() as u8
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum AmmCreatorFeeOn {
+    QuoteToken = 0,
+    BothToken = 1,
+}
+
+impl Default for AmmCreatorFeeOn {
+    fn default() -> Self {
+        Self::QuoteToken
+    }
+}
+
+impl AmmCreatorFeeOn {
+    /// 从链上的原始取值转换：0 = QuoteToken，1 = BothToken，其他取值返回 `None`
+    pub fn from_u8(value: u8) -> Option<AmmCreatorFeeOn> {
+        match value {
+            0 => Some(AmmCreatorFeeOn::QuoteToken),
+            1 => Some(AmmCreatorFeeOn::BothToken),
+            _ => None,
+        }
+    }
+
+    /// 链上的原始取值
+    pub fn as_u8(&self) -> u8 {
+        self.clone() as u8
+    }
+
+    /// base token 一侧是否收取费用
+    pub fn charges_base(&self) -> bool {
+        matches!(self, Self::BothToken)
+    }
+
+    /// quote token 一侧是否收取费用（两种模式下都会收取）
+    pub fn charges_quote(&self) -> bool {
+        true
+    }
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct ConstantCurve {
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub total_quote_fund_raising: u64,
+    pub migrate_type: u8,
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct FixedCurve {
+    pub supply: u64,
+    pub total_quote_fund_raising: u64,
+    pub migrate_type: u8,
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct LinearCurve {
+    pub supply: u64,
+    pub total_quote_fund_raising: u64,
+    pub migrate_type: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum CurveParams {
+    Constant { data: ConstantCurve },
+    Fixed { data: FixedCurve },
+    Linear { data: LinearCurve },
+}
+
+impl Default for CurveParams {
+    fn default() -> Self {
+        Self::Constant { data: ConstantCurve::default() }
+    }
+}
+
+impl CurveParams {
+    /// 代币总供应量
+    pub fn supply(&self) -> u64 {
+        match self {
+            Self::Constant { data } => data.supply,
+            Self::Fixed { data } => data.supply,
+            Self::Linear { data } => data.supply,
+        }
+    }
+
+    /// 迁移前需要募集的 quote 数量
+    pub fn total_quote_fund_raising(&self) -> u64 {
+        match self {
+            Self::Constant { data } => data.total_quote_fund_raising,
+            Self::Fixed { data } => data.total_quote_fund_raising,
+            Self::Linear { data } => data.total_quote_fund_raising,
+        }
+    }
+
+    /// 迁移类型
+    pub fn migrate_type(&self) -> u8 {
+        match self {
+            Self::Constant { data } => data.migrate_type,
+            Self::Fixed { data } => data.migrate_type,
+            Self::Linear { data } => data.migrate_type,
+        }
+    }
+
+    /// 曲线上可卖出的 base 数量，仅 `Constant` 曲线携带该字段
+    pub fn total_base_sell(&self) -> Option<u64> {
+        match self {
+            Self::Constant { data } => Some(data.total_base_sell),
+            Self::Fixed { .. } | Self::Linear { .. } => None,
+        }
+    }
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct VestingSchedule {
+    pub total_locked_amount: u64,
+    pub cliff_period: u64,
+    pub unlock_period: u64,
+    pub start_time: u64,
+    pub allocated_share_amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct PoolState {
+    /// 账户最近一次更新时所在的 Solana epoch，由程序在写入账户时刷新。
+    /// 它不是结构体布局版本号，不能用于判断账户布局
+    pub epoch: u64,
+    pub auth_bump: u8,
+    pub status: u8,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub migrate_type: u8,
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base: u64,
+    pub real_quote: u64,
+    pub total_quote_fund_raising: u64,
+    pub quote_protocol_fee: u64,
+    pub platform_fee: u64,
+    pub migrate_fee: u64,
+    pub vesting_schedule: VestingSchedule,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub creator: Pubkey,
+    pub token_program_flag: u8,
+    pub amm_creator_fee_on: AmmCreatorFeeOn,
+    pub platform_vesting_share: u64,
+    #[serde(skip, default = "zeroed_padding")]
+    pub padding: [u8; 54],
+}
+
+impl Default for PoolState {
+    fn default() -> Self {
+        Self {
+            epoch: 0,
+            auth_bump: 0,
+            status: 0,
+            base_decimals: 0,
+            quote_decimals: 0,
+            migrate_type: 0,
+            supply: 0,
+            total_base_sell: 0,
+            virtual_base: 0,
+            virtual_quote: 0,
+            real_base: 0,
+            real_quote: 0,
+            total_quote_fund_raising: 0,
+            quote_protocol_fee: 0,
+            platform_fee: 0,
+            migrate_fee: 0,
+            vesting_schedule: VestingSchedule::default(),
+            global_config: Pubkey::default(),
+            platform_config: Pubkey::default(),
+            base_mint: Pubkey::default(),
+            quote_mint: Pubkey::default(),
+            base_vault: Pubkey::default(),
+            quote_vault: Pubkey::default(),
+            creator: Pubkey::default(),
+            token_program_flag: 0,
+            amm_creator_fee_on: AmmCreatorFeeOn::default(),
+            platform_vesting_share: 0,
+            padding: [0u8; 54],
+        }
+    }
+}
+
+/// `PoolState` 账户的 Anchor discriminator
+pub const POOL_STATE_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+
+/// `PoolState` 结构体长度（不含 8 字节 discriminator），与 `pool_state_offsets` 在编译期校验一致
+pub const POOL_STATE_SIZE: usize = 8 + 5 + 8 * 10 + 8 * 5 + 32 * 7 + 1 + 1 + 8 + 54;
+
+/// 解码 `PoolState`（不含 discriminator），失败时返回具体原因
+pub fn pool_state_decode(data: &[u8]) -> Result<PoolState, DecodeError> {
+    Ok(borsh::from_slice::<PoolState>(DecodeError::take(data, POOL_STATE_SIZE)?)?)
+}
+
+/// 严格解码 `PoolState`（不含 discriminator），末尾 padding 出现非零字节时返回 `NonZeroPadding`
+pub fn pool_state_decode_strict(data: &[u8]) -> Result<PoolState, DecodeError> {
+    let pool_state = pool_state_decode(data)?;
+    let padding_start = pool_state_offsets::OFFSET_PADDING - pool_state_offsets::DISCRIMINATOR_LEN;
+    let padding = data.get(padding_start..POOL_STATE_SIZE).unwrap_or_default();
+    DecodeError::check_zero_padding(padding, pool_state_offsets::OFFSET_PADDING)?;
+    Ok(pool_state)
+}
+
+/// 解码 `PoolState`（不含 discriminator）并校验往返一致性
+///
+/// 解码结果重新 borsh 序列化后须与原始数据的前 `POOL_STATE_SIZE` 字节完全一致，否则返回
+/// `RoundTripMismatch` 并指出第一个不同的字节，用于及时发现链上布局与结构体不再匹配。
+/// 额外的序列化和比较使解码开销大约翻倍
+pub fn pool_state_decode_verified(data: &[u8]) -> Result<PoolState, DecodeError> {
+    let pool_state = pool_state_decode(data)?;
+    check_pool_state_round_trip(DecodeError::take(data, POOL_STATE_SIZE)?, &pool_state)?;
+    Ok(pool_state)
+}
+
+/// 比较 `pool_state` 的 borsh 编码与 `original`，返回的偏移量包含 discriminator
+pub(crate) fn check_pool_state_round_trip(
+    original: &[u8],
+    pool_state: &PoolState,
+) -> Result<(), DecodeError> {
+    let encoded = borsh::to_vec(pool_state)?;
+    // `PoolState` 的编码长度固定为 `POOL_STATE_SIZE`，逐字节比较即可
+    match original.iter().zip(&encoded).position(|(a, b)| a != b) {
+        Some(offset) => Err(DecodeError::RoundTripMismatch {
+            first_diff_offset: pool_state_offsets::DISCRIMINATOR_LEN + offset,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// 解码 `PoolState`（不含 discriminator），枚举字段出现未知取值时回退为该枚举的默认值
+///
+/// 程序升级新增枚举变体后，单个字段无法识别不会导致整个账户解码失败。目前只有
+/// `amm_creator_fee_on` 是枚举字段，被替换时返回的警告中记录原始取值；其他原因的解码失败
+/// 与 [`pool_state_decode`] 相同
+pub fn pool_state_decode_tolerant(data: &[u8]) -> Result<(PoolState, Vec<String>), DecodeError> {
+    let error = match pool_state_decode(data) {
+        Ok(pool_state) => return Ok((pool_state, Vec::new())),
+        Err(error) => error,
+    };
+    let offset =
+        pool_state_offsets::OFFSET_AMM_CREATOR_FEE_ON - pool_state_offsets::DISCRIMINATOR_LEN;
+    let value = match data.get(offset) {
+        Some(&value) if AmmCreatorFeeOn::from_u8(value).is_none() => value,
+        _ => return Err(error),
+    };
+    let fallback = AmmCreatorFeeOn::default();
+    let mut patched = DecodeError::take(data, POOL_STATE_SIZE)?.to_vec();
+    patched[offset] = fallback.as_u8();
+    let pool_state = pool_state_decode(&patched)?;
+    let warning = format!("amm_creator_fee_on: unknown discriminant {value}, using {fallback:?}");
+    Ok((pool_state, vec![warning]))
+}
+
+/// `PoolState` 各字段在账户数据中的字节偏移（已计入 8 字节 discriminator）
+///
+/// borsh 不做对齐填充，每个偏移即前一个字段的偏移加上其大小；多字节整数按小端序存放，
+/// 直接读取时使用 `from_le_bytes`
+///
+/// 可直接用于 gRPC 账户订阅的 memcmp 过滤，例如按 `OFFSET_BASE_MINT` 过滤指定 mint 的池子
+pub mod pool_state_offsets {
+    use super::{BONK_DISCRIMINATOR_SCHEME, POOL_STATE_SIZE};
+
+    pub const DISCRIMINATOR_LEN: usize = BONK_DISCRIMINATOR_SCHEME.prefix_len();
+    pub const OFFSET_EPOCH: usize = DISCRIMINATOR_LEN;
+    pub const OFFSET_AUTH_BUMP: usize = OFFSET_EPOCH + 8;
+    pub const OFFSET_STATUS: usize = OFFSET_AUTH_BUMP + 1;
+    pub const OFFSET_BASE_DECIMALS: usize = OFFSET_STATUS + 1;
+    pub const OFFSET_QUOTE_DECIMALS: usize = OFFSET_BASE_DECIMALS + 1;
+    pub const OFFSET_MIGRATE_TYPE: usize = OFFSET_QUOTE_DECIMALS + 1;
+    pub const OFFSET_SUPPLY: usize = OFFSET_MIGRATE_TYPE + 1;
+    pub const OFFSET_TOTAL_BASE_SELL: usize = OFFSET_SUPPLY + 8;
+    pub const OFFSET_VIRTUAL_BASE: usize = OFFSET_TOTAL_BASE_SELL + 8;
+    pub const OFFSET_VIRTUAL_QUOTE: usize = OFFSET_VIRTUAL_BASE + 8;
+    pub const OFFSET_REAL_BASE: usize = OFFSET_VIRTUAL_QUOTE + 8;
+    pub const OFFSET_REAL_QUOTE: usize = OFFSET_REAL_BASE + 8;
+    pub const OFFSET_TOTAL_QUOTE_FUND_RAISING: usize = OFFSET_REAL_QUOTE + 8;
+    pub const OFFSET_QUOTE_PROTOCOL_FEE: usize = OFFSET_TOTAL_QUOTE_FUND_RAISING + 8;
+    pub const OFFSET_PLATFORM_FEE: usize = OFFSET_QUOTE_PROTOCOL_FEE + 8;
+    pub const OFFSET_MIGRATE_FEE: usize = OFFSET_PLATFORM_FEE + 8;
+    /// `VestingSchedule` 由 5 个 u64 组成
+    pub const OFFSET_VESTING_SCHEDULE: usize = OFFSET_MIGRATE_FEE + 8;
+    pub const OFFSET_GLOBAL_CONFIG: usize = OFFSET_VESTING_SCHEDULE + 8 * 5;
+    pub const OFFSET_PLATFORM_CONFIG: usize = OFFSET_GLOBAL_CONFIG + 32;
+    pub const OFFSET_BASE_MINT: usize = OFFSET_PLATFORM_CONFIG + 32;
+    pub const OFFSET_QUOTE_MINT: usize = OFFSET_BASE_MINT + 32;
+    pub const OFFSET_BASE_VAULT: usize = OFFSET_QUOTE_MINT + 32;
+    pub const OFFSET_QUOTE_VAULT: usize = OFFSET_BASE_VAULT + 32;
+    pub const OFFSET_CREATOR: usize = OFFSET_QUOTE_VAULT + 32;
+    pub const OFFSET_TOKEN_PROGRAM_FLAG: usize = OFFSET_CREATOR + 32;
+    pub const OFFSET_AMM_CREATOR_FEE_ON: usize = OFFSET_TOKEN_PROGRAM_FLAG + 1;
+    pub const OFFSET_PLATFORM_VESTING_SHARE: usize = OFFSET_AMM_CREATOR_FEE_ON + 1;
+    pub const OFFSET_PADDING: usize = OFFSET_PLATFORM_VESTING_SHARE + 8;
+    /// 账户数据总长度
+    pub const ACCOUNT_LEN: usize = OFFSET_PADDING + 54;
+
+    // 字段偏移与 POOL_STATE_SIZE 不一致时编译失败
+    const _: () = assert!(ACCOUNT_LEN == DISCRIMINATOR_LEN + POOL_STATE_SIZE);
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct GlobalConfig {
+    /// 账户最近一次更新时所在的 Solana epoch，由程序在写入账户时刷新。
+    /// 它不是结构体布局版本号，不能用于判断账户布局
+    pub epoch: u64,
+    pub curve_type: u8,
+    pub index: u16,
+    pub migrate_fee: u64,
+    pub trade_fee_rate: u64,
+    pub max_share_fee_rate: u64,
+    pub min_base_supply: u64,
+    pub max_lock_rate: u64,
+    pub min_base_sell_rate: u64,
+    pub min_base_migrate_rate: u64,
+    pub min_quote_fund_raising: u64,
+    pub quote_mint: Pubkey,
+    pub protocol_fee_owner: Pubkey,
+    pub migrate_fee_owner: Pubkey,
+    pub migrate_to_amm_wallet: Pubkey,
+    pub migrate_to_cpswap_wallet: Pubkey,
+    #[serde(skip)]
+    pub padding: [u64; 16],
+}
+
+/// `GlobalConfig` 账户的 Anchor discriminator
+pub const GLOBAL_CONFIG_DISCRIMINATOR: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217];
+
+pub const GLOBAL_CONFIG_SIZE: usize = 8 + 1 + 2 + 8 * 8 + 32 * 5 + 8 * 16;
+
+/// 解码 `GlobalConfig`（不含 discriminator），失败时返回具体原因
+pub fn global_config_decode(data: &[u8]) -> Result<GlobalConfig, DecodeError> {
+    Ok(borsh::from_slice::<GlobalConfig>(DecodeError::take(data, GLOBAL_CONFIG_SIZE)?)?)
+}
+
+/// `GlobalConfig` 末尾 padding（`[u64; 16]`）的字节长度
+const GLOBAL_CONFIG_PADDING_LEN: usize = 8 * 16;
+
+/// 严格解码 `GlobalConfig`（不含 discriminator），末尾 padding 出现非零字节时返回 `NonZeroPadding`
+pub fn global_config_decode_strict(data: &[u8]) -> Result<GlobalConfig, DecodeError> {
+    let global_config = global_config_decode(data)?;
+    let padding_start = GLOBAL_CONFIG_SIZE - GLOBAL_CONFIG_PADDING_LEN;
+    let padding = data.get(padding_start..GLOBAL_CONFIG_SIZE).unwrap_or_default();
+    DecodeError::check_zero_padding(padding, 8 + padding_start)?;
+    Ok(global_config)
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct BondingCurveParam {
+    pub migrate_type: u8,
+    pub migrate_cpmm_fee_on: u8,
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub total_quote_fund_raising: u64,
+    pub total_locked_amount: u64,
+    pub cliff_period: u64,
+    pub unlock_period: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct PlatformCurveParam {
+    pub epoch: u64,
+    pub index: u8,
+    pub global_config: Pubkey,
+    pub bonding_curve_param: BondingCurveParam,
+    #[serde(skip, default = "zeroed_padding")]
+    pub padding: [u64; 50],
+}
+
+impl Default for PlatformCurveParam {
+    fn default() -> Self {
+        Self {
+            epoch: 0,
+            index: 0,
+            global_config: Pubkey::default(),
+            bonding_curve_param: BondingCurveParam::default(),
+            padding: [0u64; 50],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct PlatformConfig {
+    /// 账户最近一次更新时所在的 Solana epoch，由程序在写入账户时刷新。
+    /// 它不是结构体布局版本号，不能用于判断账户布局
+    pub epoch: u64,
+    pub platform_fee_wallet: Pubkey,
+    pub platform_nft_wallet: Pubkey,
+    pub platform_scale: u64,
+    pub creator_scale: u64,
+    pub burn_scale: u64,
+    pub fee_rate: u64,
+    #[serde(with = "serde_big_array::BigArray")]
+    pub name: [u8; 64],
+    #[serde(with = "serde_big_array::BigArray")]
+    pub web: [u8; 256],
+    #[serde(with = "serde_big_array::BigArray")]
+    pub img: [u8; 256],
+    pub cpswap_config: Pubkey,
+    pub creator_fee_rate: u64,
+    pub transfer_fee_extension_auth: Pubkey,
+    pub platform_vesting_wallet: Pubkey,
+    pub platform_vesting_scale: u64,
+    pub platform_cp_creator: Pubkey,
+    #[serde(skip, default = "zeroed_padding")]
+    pub padding: [u8; 108],
+    pub curve_params: Vec<PlatformCurveParam>,
+}
+
+impl Default for PlatformConfig {
+    fn default() -> Self {
+        Self {
+            epoch: 0,
+            platform_fee_wallet: Pubkey::default(),
+            platform_nft_wallet: Pubkey::default(),
+            platform_scale: 0,
+            creator_scale: 0,
+            burn_scale: 0,
+            fee_rate: 0,
+            name: [0u8; 64],
+            web: [0u8; 256],
+            img: [0u8; 256],
+            cpswap_config: Pubkey::default(),
+            creator_fee_rate: 0,
+            transfer_fee_extension_auth: Pubkey::default(),
+            platform_vesting_wallet: Pubkey::default(),
+            platform_vesting_scale: 0,
+            platform_cp_creator: Pubkey::default(),
+            padding: [0u8; 108],
+            curve_params: Vec::new(),
+        }
+    }
+}
+
+/// `PlatformConfig` 账户的 Anchor discriminator
+pub const PLATFORM_CONFIG_DISCRIMINATOR: [u8; 8] = [160, 78, 128, 0, 248, 83, 230, 160];
+
+pub const PLATFORM_CONFIG_SIZE: usize =
+    8 + 32 * 2 + 8 * 4 + 64 + 256 + 256 + 32 + 8 + 32 + 32 + 8 + 32 + 108;
+
+/// 单个 `PlatformCurveParam` 的 borsh 编码长度
+pub const PLATFORM_CURVE_PARAM_SIZE: usize = 8 + 1 + 32 + (1 + 1 + 8 * 6) + 8 * 50;
+
+/// `PlatformConfig.curve_params` 允许的最大元素数量
+pub const MAX_CURVE_PARAMS: usize = 128;
+
+/// 在 borsh 解码之前检查 `curve_params` 的长度前缀（不含 discriminator 的账户数据）
+///
+/// 长度前缀位于定长部分之后，超过 [`MAX_CURVE_PARAMS`] 或超过剩余数据最多能容纳的元素数量时
+/// 返回 `TooManyElements`，避免伪造的长度前缀导致大量内存分配
+pub fn check_curve_params_len(data: &[u8]) -> Result<(), DecodeError> {
+    let Some(count) = read_u32_le(data, PLATFORM_CONFIG_SIZE) else {
+        return Ok(());
+    };
+    let count = count as usize;
+    let remaining = data.len() - PLATFORM_CONFIG_SIZE - 4;
+    let max = MAX_CURVE_PARAMS.min(remaining / PLATFORM_CURVE_PARAM_SIZE);
+    if count > max {
+        return Err(DecodeError::TooManyElements { count, max });
+    }
+    Ok(())
+}
+
+/// 解码 `PlatformConfig`（不含 discriminator），失败时返回具体原因
+///
+/// `PLATFORM_CONFIG_SIZE` 只是定长部分的长度，末尾的 `curve_params` 为变长 Vec，
+/// 因此传入 discriminator 之后的全部账户数据；Vec 之后多余的字节（账户预留空间）会被忽略。
+/// `curve_params` 的长度前缀先经过 [`check_curve_params_len`] 检查
+pub fn platform_config_decode(data: &[u8]) -> Result<PlatformConfig, DecodeError> {
+    DecodeError::check_len(data, PLATFORM_CONFIG_SIZE)?;
+    check_curve_params_len(data)?;
+    let mut buf = data;
+    Ok(<PlatformConfig as BorshDeserialize>::deserialize(&mut buf)?)
+}
@@ -0,0 +1,117 @@
+use core::fmt;
+
+/// 账户数据解码失败的原因
+#[derive(Debug)]
+pub enum DecodeError {
+    /// 数据长度不足
+    TooShort { expected: usize, actual: usize },
+    /// 数据长度超出已知布局（通常意味着程序升级后追加了新字段）
+    UnexpectedLength { expected: usize, actual: usize },
+    /// 账户 discriminator 与预期的账户类型不符
+    DiscriminatorMismatch,
+    /// 严格模式下 padding 中出现非零字节（通常意味着程序升级后启用了预留空间），
+    /// `offset` 为第一个非零字节在账户数据中的偏移（含 8 字节 discriminator）
+    NonZeroPadding { offset: usize },
+    /// 变长 Vec 的长度前缀超过上限，或超过剩余数据最多能容纳的元素数量，
+    /// 在分配内存之前拒绝（通常意味着数据损坏或伪造）
+    TooManyElements { count: usize, max: usize },
+    /// borsh 反序列化失败（字段取值非法、数据不足或有多余字节等）
+    Borsh(borsh::io::Error),
+    /// 事件帧的格式版本不受支持，见 `DexEvent::decode`
+    UnsupportedVersion { version: u8 },
+    /// 事件帧的变体标签不对应任何 `DexEvent` 变体
+    UnknownVariant { tag: u8 },
+    /// 事件帧的内容反序列化失败（数据被截断或已损坏）
+    #[cfg(feature = "std")]
+    Bincode(bincode::Error),
+    /// 解码结果重新 borsh 序列化后与原始数据不一致（通常意味着链上布局与结构体不再匹配），
+    /// `first_diff_offset` 为第一个不同字节在账户数据中的偏移（含 8 字节 discriminator）
+    RoundTripMismatch { first_diff_offset: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::TooShort { expected, actual } => {
+                write!(f, "account data too short: expected {expected} bytes, got {actual}")
+            }
+            DecodeError::UnexpectedLength { expected, actual } => {
+                write!(
+                    f,
+                    "unexpected account data length: expected at most {expected} bytes, got {actual}"
+                )
+            }
+            DecodeError::DiscriminatorMismatch => write!(f, "account discriminator mismatch"),
+            DecodeError::NonZeroPadding { offset } => {
+                write!(f, "non-zero padding byte at account data offset {offset}")
+            }
+            DecodeError::TooManyElements { count, max } => {
+                write!(f, "vec length prefix {count} exceeds the limit of {max} elements")
+            }
+            DecodeError::Borsh(e) => write!(f, "borsh decode failed: {e}"),
+            DecodeError::UnsupportedVersion { version } => {
+                write!(f, "unsupported event encoding version {version}")
+            }
+            DecodeError::UnknownVariant { tag } => write!(f, "unknown event variant tag {tag}"),
+            #[cfg(feature = "std")]
+            DecodeError::Bincode(e) => write!(f, "event body decode failed: {e}"),
+            DecodeError::RoundTripMismatch { first_diff_offset } => {
+                write!(f, "re-encoded account differs at account data offset {first_diff_offset}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::TooShort { .. }
+            | DecodeError::UnexpectedLength { .. }
+            | DecodeError::DiscriminatorMismatch
+            | DecodeError::NonZeroPadding { .. }
+            | DecodeError::TooManyElements { .. }
+            | DecodeError::UnsupportedVersion { .. }
+            | DecodeError::UnknownVariant { .. }
+            | DecodeError::RoundTripMismatch { .. } => None,
+            DecodeError::Borsh(e) => Some(e),
+            DecodeError::Bincode(e) => Some(e),
+        }
+    }
+}
+
+impl From<borsh::io::Error> for DecodeError {
+    fn from(e: borsh::io::Error) -> Self {
+        DecodeError::Borsh(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<bincode::Error> for DecodeError {
+    fn from(e: bincode::Error) -> Self {
+        DecodeError::Bincode(e)
+    }
+}
+
+impl DecodeError {
+    /// 检查数据长度，不足 `expected` 时返回 `TooShort`
+    pub fn check_len(data: &[u8], expected: usize) -> Result<(), DecodeError> {
+        if data.len() < expected {
+            return Err(DecodeError::TooShort { expected, actual: data.len() });
+        }
+        Ok(())
+    }
+
+    /// 检查 padding 是否全为 0，`offset` 为 `padding` 在账户数据中的起始偏移
+    pub fn check_zero_padding(padding: &[u8], offset: usize) -> Result<(), DecodeError> {
+        match padding.iter().position(|&b| b != 0) {
+            Some(i) => Err(DecodeError::NonZeroPadding { offset: offset + i }),
+            None => Ok(()),
+        }
+    }
+
+    /// 取出 `data` 的前 `len` 字节，不足时返回 `TooShort`
+    pub fn take(data: &[u8], len: usize) -> Result<&[u8], DecodeError> {
+        data.get(..len).ok_or(DecodeError::TooShort { expected: len, actual: data.len() })
+    }
+}
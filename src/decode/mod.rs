@@ -0,0 +1,82 @@
+//! 账户数据解码
+//!
+//! 只依赖 `core` 和 `alloc`：关闭默认的 `std` feature、启用 `no-std` 时 crate 中只编译这个模块，
+//! 可以在 SBF 程序、wasm 等没有 std 的环境中解码账户，不引入 gRPC、tokio 等流式订阅依赖：
+//!
+//! ```toml
+//! solana-streamer-sdk = { version = "1", default-features = false, features = ["no-std"] }
+//! ```
+//!
+//! 启用 `std` 时这里的类型和函数同时从 `streaming::event_parser` 下的原有路径导出
+
+pub mod bonk;
+mod error;
+
+use borsh::BorshDeserialize;
+
+pub use error::DecodeError;
+
+/// 账户数据的前缀格式，决定数据主体从哪里开始
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DiscriminatorScheme {
+    /// Anchor 程序：8 字节 discriminator
+    #[default]
+    Anchor8,
+    /// 4 字节小端长度前缀，后接数据主体
+    Len4,
+    /// 无前缀，数据主体从第 0 字节开始
+    None,
+}
+
+impl DiscriminatorScheme {
+    /// 前缀长度（字节）
+    pub const fn prefix_len(&self) -> usize {
+        match self {
+            Self::Anchor8 => 8,
+            Self::Len4 => 4,
+            Self::None => 0,
+        }
+    }
+}
+
+/// 按 `scheme` 去掉前缀后按 borsh 解码账户
+///
+/// 要求前缀之后至少有 `size` 字节（`Len4` 格式下长度前缀也不能小于 `size`）。从主体开始处解码，
+/// 末尾的变长字段（Vec 等）可以超出 `size`，解码完成后剩余的字节（账户预留空间）被忽略
+pub fn decode_account_body<T: BorshDeserialize>(
+    scheme: DiscriminatorScheme,
+    data: &[u8],
+    size: usize,
+) -> Option<T> {
+    try_decode_account_body(scheme, data, size).ok()
+}
+
+/// 与 [`decode_account_body`] 相同，失败时返回具体原因
+pub fn try_decode_account_body<T: BorshDeserialize>(
+    scheme: DiscriminatorScheme,
+    data: &[u8],
+    size: usize,
+) -> Result<T, DecodeError> {
+    let prefix_len = scheme.prefix_len();
+    // size 过大时按溢出处理为数据不足
+    DecodeError::check_len(data, prefix_len.saturating_add(size))?;
+    if scheme == DiscriminatorScheme::Len4 {
+        let declared = read_u32_le(data, 0).unwrap_or_default() as usize;
+        if declared < size {
+            return Err(DecodeError::TooShort { expected: size, actual: declared });
+        }
+    }
+    let mut body = data.get(prefix_len..).unwrap_or_default();
+    Ok(T::deserialize(&mut body)?)
+}
+
+/// 检查账户数据的前 8 字节是否为指定的 Anchor discriminator
+pub fn verify_discriminator(data: &[u8], expected: &[u8; 8]) -> bool {
+    data.get(..8) == Some(expected.as_slice())
+}
+
+/// 安全地从字节数组中读取u32
+pub fn read_u32_le(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+    Some(u32::from_le_bytes(bytes))
+}
@@ -1,3 +1,14 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "no-std")))]
+compile_error!("enable the default `std` feature, or `no-std` for the decode-only build");
+
+#[cfg(feature = "std")]
 pub mod common;
+pub mod decode;
+#[cfg(feature = "std")]
 pub mod protos;
+#[cfg(feature = "std")]
 pub mod streaming;
@@ -0,0 +1,1343 @@
+//! 事件流适配器
+//!
+//! 每个适配器都提供一个与传输无关的有状态结构体（可在回调中直接使用），
+//! 以及 [`DexEventStreamExt`] 上对应的 `Stream` 组合方法。
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::Duration;
+
+use futures::channel::mpsc;
+use futures::{SinkExt, Stream, StreamExt};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::common::{BackpressureConfig, BackpressureStrategy, MetricsManager};
+use crate::streaming::event_parser::common::EventType;
+use crate::streaming::event_parser::core::common_event_parser::HeartbeatEvent;
+use crate::streaming::event_parser::protocols::bonk::{
+    BonkGlobalConfigChangedEvent, BonkImpliedTradeEvent, BonkTradingLiveEvent, GlobalConfig,
+    PoolState, PoolStatus,
+};
+use crate::streaming::event_parser::{DexEvent, Protocol};
+
+/// 账户状态变化：上一次缓存的状态与当前状态
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateChange<T> {
+    pub previous: Option<T>,
+    pub current: T,
+}
+
+/// 按账户地址缓存最近一次账户事件，为每个新事件附带上一次的状态
+///
+/// 非账户事件（交易、区块等）不会被缓存，`previous` 始终为 `None`
+#[derive(Debug, Default)]
+pub struct PreviousStateTracker {
+    cache: HashMap<Pubkey, DexEvent>,
+}
+
+impl PreviousStateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录新事件并返回包含上一次状态的变化
+    pub fn track(&mut self, event: DexEvent) -> StateChange<DexEvent> {
+        let previous = match event.account_pubkey() {
+            Some(pubkey) => self.cache.insert(pubkey, event.clone()),
+            None => None,
+        };
+        StateChange { previous, current: event }
+    }
+
+    /// 获取账户最近一次的状态
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&DexEvent> {
+        self.cache.get(pubkey)
+    }
+
+    /// 当前缓存的账户数量
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// 带有单调递增序号的事件
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sequenced<T> {
+    pub seq: u64,
+    pub event: T,
+}
+
+/// 为事件分配单调递增的序号
+///
+/// 重连后以持久化的最后序号加一作为 `start` 继续计数，下游可据此检测缺口并确定性地恢复
+#[derive(Debug, Default)]
+pub struct SequenceStamper {
+    next: u64,
+}
+
+impl SequenceStamper {
+    /// 从 `start` 开始编号
+    pub fn new(start: u64) -> Self {
+        Self { next: start }
+    }
+
+    /// 为事件分配下一个序号
+    pub fn stamp<T>(&mut self, event: T) -> Sequenced<T> {
+        let seq = self.next;
+        self.next = self.next.wrapping_add(1);
+        Sequenced { seq, event }
+    }
+
+    /// 下一个将被分配的序号
+    pub fn next_seq(&self) -> u64 {
+        self.next
+    }
+}
+
+/// 单个池子的动量状态
+#[derive(Debug, Clone, Copy)]
+struct MomentumEntry {
+    real_quote: u64,
+    timestamp_us: i64,
+    velocity: f64,
+}
+
+/// 按池子地址跟踪 Bonk `PoolState.real_quote` 的变化速度（quote/秒）
+///
+/// 时间戳取自事件的 `recv_us`。超过 `stale_after` 没有更新的池子速度视为 0，
+/// 并可通过 [`MomentumTracker::evict_stale`] 移除
+#[derive(Debug)]
+pub struct MomentumTracker {
+    entries: HashMap<Pubkey, MomentumEntry>,
+    stale_after: Duration,
+    /// 已观察到的最新时间戳，作为判断过期的当前时间
+    latest_us: i64,
+}
+
+impl MomentumTracker {
+    pub fn new(stale_after: Duration) -> Self {
+        Self { entries: HashMap::new(), stale_after, latest_us: 0 }
+    }
+
+    /// 处理事件，仅 `BonkPoolStateAccountEvent` 会更新状态
+    pub fn observe(&mut self, event: &DexEvent) {
+        if let DexEvent::BonkPoolStateAccountEvent(e) = event {
+            self.update(e.base.pubkey, e.pool_state.real_quote, e.metadata.recv_us);
+        }
+    }
+
+    /// 记录一次 `real_quote` 采样
+    ///
+    /// 时间戳不晚于上一次采样的更新会被忽略
+    pub fn update(&mut self, pubkey: Pubkey, real_quote: u64, timestamp_us: i64) {
+        self.latest_us = self.latest_us.max(timestamp_us);
+        match self.entries.get_mut(&pubkey) {
+            Some(entry) => {
+                if timestamp_us <= entry.timestamp_us {
+                    return;
+                }
+                let elapsed_us = timestamp_us.saturating_sub(entry.timestamp_us);
+                let elapsed_secs = elapsed_us as f64 / 1_000_000.0;
+                entry.velocity = (real_quote as f64 - entry.real_quote as f64) / elapsed_secs;
+                entry.real_quote = real_quote;
+                entry.timestamp_us = timestamp_us;
+            }
+            None => {
+                self.entries
+                    .insert(pubkey, MomentumEntry { real_quote, timestamp_us, velocity: 0.0 });
+            }
+        }
+    }
+
+    /// 池子最近的 quote 流入速度（quote/秒），流出时为负数
+    ///
+    /// 未知或已过期的池子返回 0
+    pub fn velocity(&self, pubkey: &Pubkey) -> f64 {
+        match self.entries.get(pubkey) {
+            Some(entry) if !self.is_stale(entry) => entry.velocity,
+            _ => 0.0,
+        }
+    }
+
+    /// 移除过期的池子，返回移除数量
+    pub fn evict_stale(&mut self) -> usize {
+        let before = self.entries.len();
+        let stale_after_us = i64::try_from(self.stale_after.as_micros()).unwrap_or(i64::MAX);
+        let latest_us = self.latest_us;
+        self.entries
+            .retain(|_, entry| latest_us.saturating_sub(entry.timestamp_us) <= stale_after_us);
+        before - self.entries.len()
+    }
+
+    fn is_stale(&self, entry: &MomentumEntry) -> bool {
+        let stale_after_us = i64::try_from(self.stale_after.as_micros()).unwrap_or(i64::MAX);
+        self.latest_us.saturating_sub(entry.timestamp_us) > stale_after_us
+    }
+
+    /// 当前跟踪的池子数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 按账户地址统计滑动窗口内的更新频率（次/秒）
+///
+/// 时间戳取自事件的 `recv_us`，以已观察到的最新时间戳作为当前时间。窗口内没有更新的
+/// 账户频率为 0，可通过 [`RateTracker::evict_idle`] 移除以限制内存占用
+#[derive(Debug)]
+pub struct RateTracker {
+    entries: HashMap<Pubkey, VecDeque<i64>>,
+    window: Duration,
+    /// 已观察到的最新时间戳，作为滑动窗口的右边界
+    latest_us: i64,
+}
+
+impl RateTracker {
+    pub fn new(window: Duration) -> Self {
+        Self { entries: HashMap::new(), window, latest_us: 0 }
+    }
+
+    /// 处理事件，只统计账户事件
+    pub fn observe(&mut self, event: &DexEvent) {
+        if let Some(pubkey) = event.account_pubkey() {
+            self.record(pubkey, event.metadata().recv_us);
+        }
+    }
+
+    /// 记录账户的一次更新
+    pub fn record(&mut self, pubkey: Pubkey, timestamp_us: i64) {
+        self.latest_us = self.latest_us.max(timestamp_us);
+        let window_start = self.window_start_us();
+        let samples = self.entries.entry(pubkey).or_default();
+        samples.push_back(timestamp_us);
+        while samples.front().is_some_and(|&ts| ts <= window_start) {
+            samples.pop_front();
+        }
+    }
+
+    /// 账户在当前窗口内的更新频率（次/秒）
+    pub fn rate(&self, pubkey: &Pubkey) -> f64 {
+        self.entries.get(pubkey).map_or(0.0, |samples| self.rate_of(samples))
+    }
+
+    /// 更新频率最高的 `n` 个账户，按频率降序排列
+    pub fn top_n(&self, n: usize) -> Vec<(Pubkey, f64)> {
+        let mut rates: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(pubkey, samples)| (*pubkey, self.rate_of(samples)))
+            .filter(|(_, rate)| *rate > 0.0)
+            .collect();
+        rates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        rates.truncate(n);
+        rates
+    }
+
+    /// 移除窗口内没有更新的账户，返回移除数量
+    pub fn evict_idle(&mut self) -> usize {
+        let before = self.entries.len();
+        let window_start = self.window_start_us();
+        self.entries.retain(|_, samples| {
+            while samples.front().is_some_and(|&ts| ts <= window_start) {
+                samples.pop_front();
+            }
+            !samples.is_empty()
+        });
+        before - self.entries.len()
+    }
+
+    fn rate_of(&self, samples: &VecDeque<i64>) -> f64 {
+        let window_secs = self.window.as_secs_f64();
+        if window_secs <= 0.0 {
+            return 0.0;
+        }
+        let window_start = self.window_start_us();
+        let count = samples.iter().rev().take_while(|&&ts| ts > window_start).count();
+        count as f64 / window_secs
+    }
+
+    fn window_start_us(&self) -> i64 {
+        let window_us = i64::try_from(self.window.as_micros()).unwrap_or(i64::MAX);
+        self.latest_us.saturating_sub(window_us)
+    }
+
+    /// 当前跟踪的账户数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 跟踪 Bonk 池子状态，在池子从其他状态进入 `PoolStatus::Trade` 时生成一次
+/// `BonkTradingLiveEvent`
+///
+/// 只有观察到状态变化才会生成事件：首次见到即处于 `Trade` 的池子只记录状态，
+/// 之后重复的 `Trade` 更新也不会再次生成
+#[derive(Debug, Default)]
+pub struct TradingLiveTracker {
+    /// 池子上一次是否处于 `Trade` 状态
+    trading: HashMap<Pubkey, bool>,
+}
+
+impl TradingLiveTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 处理事件，池子进入 `Trade` 状态时返回 `BonkTradingLiveEvent`
+    pub fn observe(&mut self, event: &DexEvent) -> Option<DexEvent> {
+        let DexEvent::BonkPoolStateAccountEvent(e) = event else {
+            return None;
+        };
+        let is_trade = e.pool_state.status() == Some(PoolStatus::Trade);
+        let was_trade = self.trading.insert(e.base.pubkey, is_trade)?;
+        if !is_trade || was_trade {
+            return None;
+        }
+        let mut metadata = e.metadata.clone();
+        metadata.event_type = EventType::BonkTradingLive;
+        Some(DexEvent::BonkTradingLiveEvent(BonkTradingLiveEvent {
+            metadata,
+            pubkey: e.base.pubkey,
+            mint: e.pool_state.base_mint,
+            slot: e.metadata.slot,
+        }))
+    }
+
+    /// 当前跟踪的池子数量
+    pub fn len(&self) -> usize {
+        self.trading.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trading.is_empty()
+    }
+}
+
+/// 跟踪 Bonk `GlobalConfig` 账户，配置字段发生变化时生成 `BonkGlobalConfigChangedEvent`
+///
+/// 首次见到的配置只记录快照；之后的更新与上一次快照比较，见 [`GlobalConfig::diff`]，
+/// 没有字段变化时不生成事件
+#[derive(Debug, Default)]
+pub struct GlobalConfigChangeTracker {
+    /// 每个配置账户最近一次的快照
+    configs: HashMap<Pubkey, GlobalConfig>,
+}
+
+impl GlobalConfigChangeTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 处理事件，配置字段发生变化时返回 `BonkGlobalConfigChangedEvent`
+    pub fn observe(&mut self, event: &DexEvent) -> Option<DexEvent> {
+        let DexEvent::BonkGlobalConfigAccountEvent(e) = event else {
+            return None;
+        };
+        let prev = self.configs.insert(e.base.pubkey, e.global_config.clone())?;
+        let changes = e.global_config.diff(&prev);
+        if changes.is_empty() {
+            return None;
+        }
+        let mut metadata = e.metadata.clone();
+        metadata.event_type = EventType::BonkGlobalConfigChanged;
+        Some(DexEvent::BonkGlobalConfigChangedEvent(BonkGlobalConfigChangedEvent {
+            metadata,
+            pubkey: e.base.pubkey,
+            changes,
+            slot: e.metadata.slot,
+        }))
+    }
+
+    /// 当前跟踪的配置账户数量
+    pub fn len(&self) -> usize {
+        self.configs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+}
+
+/// 跟踪 Bonk 池子状态，根据相邻两次快照的储备变化生成 `BonkImpliedTradeEvent`
+///
+/// 首次见到的池子只记录快照；之后按 [`PoolState::diff`] 推断交易方向，储备没有按买入或
+/// 卖出的方式变化（例如只有 `epoch` 或状态变化）时不生成事件
+#[derive(Debug, Default)]
+pub struct PoolTradeTracker {
+    /// 每个池子最近一次的快照
+    pools: HashMap<Pubkey, PoolState>,
+}
+
+impl PoolTradeTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 处理事件，储备变化构成一笔交易时返回 `BonkImpliedTradeEvent`
+    pub fn observe(&mut self, event: &DexEvent) -> Option<DexEvent> {
+        let DexEvent::BonkPoolStateAccountEvent(e) = event else {
+            return None;
+        };
+        let prev = self.pools.insert(e.base.pubkey, e.pool_state.clone())?;
+        let delta = e.pool_state.diff(&prev);
+        let direction = delta.direction?;
+        let mut metadata = e.metadata.clone();
+        metadata.event_type = EventType::BonkImpliedTrade;
+        Some(DexEvent::BonkImpliedTradeEvent(BonkImpliedTradeEvent {
+            metadata,
+            pubkey: e.base.pubkey,
+            base_mint: e.pool_state.base_mint,
+            direction,
+            base_amount: u64::try_from(delta.real_base.unsigned_abs()).unwrap_or(u64::MAX),
+            quote_amount: u64::try_from(delta.real_quote.unsigned_abs()).unwrap_or(u64::MAX),
+            price_fixed: e.pool_state.price_fixed(),
+            slot: e.metadata.slot,
+        }))
+    }
+
+    /// 当前跟踪的池子数量
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+}
+
+/// 两个相邻观察到的 slot 之间的空缺，由 [`GapDetector`] 报告
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotGap {
+    /// 空缺之前观察到的最大 slot
+    pub from: u64,
+    /// 空缺之后收到的 slot
+    pub to: u64,
+}
+
+impl SlotGap {
+    /// 两者之间没有观察到的 slot 数量
+    pub fn missed(&self) -> u64 {
+        self.to.saturating_sub(self.from).saturating_sub(1)
+    }
+}
+
+/// 跟踪已观察到的最大 slot，新事件的 slot 比最大 slot 大 `threshold` 以上时报告空缺
+///
+/// 并非每个 slot 都有订阅的账户更新，`threshold` 应大于正常情况下相邻事件的 slot 间隔，
+/// 避免把链上无活动误报为丢失更新。slot 不大于最大 slot 的事件（乱序或同一 slot）和
+/// slot 为 0 的事件不参与比较
+#[derive(Debug, Clone)]
+pub struct GapDetector {
+    threshold: u64,
+    max_slot: Option<u64>,
+    gap_count: u64,
+}
+
+impl GapDetector {
+    pub fn new(threshold: u64) -> Self {
+        Self { threshold, max_slot: None, gap_count: 0 }
+    }
+
+    /// 处理事件，slot 跳跃超过阈值时返回空缺
+    pub fn observe(&mut self, event: &DexEvent) -> Option<SlotGap> {
+        self.observe_slot(event.metadata().slot)
+    }
+
+    /// 记录一个 slot，与之前的最大 slot 相差超过阈值时返回空缺
+    pub fn observe_slot(&mut self, slot: u64) -> Option<SlotGap> {
+        if slot == 0 {
+            return None;
+        }
+        let Some(max_slot) = self.max_slot else {
+            self.max_slot = Some(slot);
+            return None;
+        };
+        if slot <= max_slot {
+            return None;
+        }
+        self.max_slot = Some(slot);
+        if slot - max_slot <= self.threshold {
+            return None;
+        }
+        self.gap_count += 1;
+        Some(SlotGap { from: max_slot, to: slot })
+    }
+
+    /// 已观察到的最大 slot
+    pub fn max_slot(&self) -> Option<u64> {
+        self.max_slot
+    }
+
+    /// 已报告的空缺数量
+    pub fn gap_count(&self) -> u64 {
+        self.gap_count
+    }
+
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+}
+
+/// 迟于已输出水位的事件的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LateEventPolicy {
+    /// 丢弃并计数
+    #[default]
+    Drop,
+    /// 暂存，由 [`SlotOrderingBuffer::take_late_events`] 取出
+    Collect,
+}
+
+/// 按 slot 重排事件的缓冲区
+///
+/// 事件按 slot 暂存，收到的最大 slot 比某个 slot 大 `lookback` 以上时，该 slot 及更早的事件
+/// 按 slot 非递减顺序输出，同一 slot 内按 `EventMetadata::chain_position` 排序。
+/// slot 不大于已输出水位的迟到事件（该 slot 已经输出过）按 [`LateEventPolicy`] 丢弃或暂存
+#[derive(Debug, Default)]
+pub struct SlotOrderingBuffer {
+    lookback: u64,
+    policy: LateEventPolicy,
+    pending: BTreeMap<u64, Vec<DexEvent>>,
+    /// 已输出的最大 slot
+    watermark: Option<u64>,
+    /// 收到的最大 slot
+    max_slot: u64,
+    late_events: Vec<DexEvent>,
+    late_count: u64,
+}
+
+impl SlotOrderingBuffer {
+    /// 暂存最近 `lookback` 个 slot 的事件，迟到事件直接丢弃
+    pub fn new(lookback: u64) -> Self {
+        Self { lookback, ..Default::default() }
+    }
+
+    /// 设置迟到事件的处理方式
+    pub fn with_late_policy(mut self, policy: LateEventPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 加入一个事件，返回因窗口前移而可以输出的事件（按 slot 排序）
+    pub fn push(&mut self, event: DexEvent) -> Vec<DexEvent> {
+        let slot = event.metadata().slot;
+        if self.watermark.is_some_and(|watermark| slot <= watermark) {
+            self.late_count += 1;
+            if self.policy == LateEventPolicy::Collect {
+                self.late_events.push(event);
+            }
+            return Vec::new();
+        }
+        self.pending.entry(slot).or_default().push(event);
+        self.max_slot = self.max_slot.max(slot);
+
+        let Some(ready_until) = self.max_slot.checked_sub(self.lookback) else {
+            return Vec::new();
+        };
+        let remaining = self.pending.split_off(&ready_until.saturating_add(1));
+        let ready = std::mem::replace(&mut self.pending, remaining);
+        self.emit(ready)
+    }
+
+    /// 输出全部暂存的事件（如上游结束时）
+    pub fn drain(&mut self) -> Vec<DexEvent> {
+        let ready = std::mem::take(&mut self.pending);
+        self.emit(ready)
+    }
+
+    fn emit(&mut self, ready: BTreeMap<u64, Vec<DexEvent>>) -> Vec<DexEvent> {
+        if let Some((&slot, _)) = ready.last_key_value() {
+            self.watermark = Some(self.watermark.map_or(slot, |watermark| watermark.max(slot)));
+        }
+        let mut events = Vec::new();
+        for (_, mut slot_events) in ready {
+            slot_events.sort_by_key(|event| event.metadata().chain_position());
+            events.extend(slot_events);
+        }
+        events
+    }
+
+    /// 取出暂存的迟到事件（仅 `LateEventPolicy::Collect`）
+    pub fn take_late_events(&mut self) -> Vec<DexEvent> {
+        std::mem::take(&mut self.late_events)
+    }
+
+    /// 迟到事件的数量（含丢弃和暂存的）
+    pub fn late_count(&self) -> u64 {
+        self.late_count
+    }
+
+    /// 已输出的最大 slot
+    pub fn watermark(&self) -> Option<u64> {
+        self.watermark
+    }
+
+    /// 暂存中的事件数量
+    pub fn len(&self) -> usize {
+        self.pending.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// 一个 slot 内各类事件的数量，由 [`SlotAggregator`] 输出
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlotAggregate {
+    pub slot: u64,
+    pub counts: HashMap<EventType, u64>,
+}
+
+impl SlotAggregate {
+    /// 该 slot 内的事件总数
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+}
+
+/// slot 小于当前统计窗口的迟到事件的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LateSlotPolicy {
+    /// 丢弃并计数
+    #[default]
+    Drop,
+    /// 计入当前统计中的 slot
+    FoldIntoCurrent,
+}
+
+/// 按 slot 统计各 `EventType` 的事件数量
+///
+/// 同一时刻只统计一个 slot，收到更大 slot 的事件时输出当前 slot 的 [`SlotAggregate`] 并开始
+/// 统计新 slot。slot 小于当前 slot 的迟到事件按 [`LateSlotPolicy`] 丢弃或计入当前 slot
+#[derive(Debug, Default)]
+pub struct SlotAggregator {
+    policy: LateSlotPolicy,
+    current: Option<SlotAggregate>,
+    late_count: u64,
+}
+
+impl SlotAggregator {
+    /// 迟到事件直接丢弃
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置迟到事件的处理方式
+    pub fn with_late_policy(mut self, policy: LateSlotPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 统计一个事件，slot 前进时返回上一个 slot 的统计结果
+    pub fn observe(&mut self, event: &DexEvent) -> Option<SlotAggregate> {
+        let metadata = event.metadata();
+        let slot = metadata.slot;
+        let finished = match self.current_slot() {
+            Some(current_slot) if slot < current_slot => {
+                self.late_count += 1;
+                if self.policy == LateSlotPolicy::Drop {
+                    return None;
+                }
+                None
+            }
+            Some(current_slot) if slot == current_slot => None,
+            _ => self.current.replace(SlotAggregate { slot, counts: HashMap::new() }),
+        };
+        if let Some(current) = self.current.as_mut() {
+            *current.counts.entry(metadata.event_type.clone()).or_default() += 1;
+        }
+        finished
+    }
+
+    /// 输出当前 slot 的统计结果（如上游结束时），尚未收到事件时返回 `None`
+    pub fn flush(&mut self) -> Option<SlotAggregate> {
+        self.current.take()
+    }
+
+    /// 当前统计中的 slot
+    pub fn current_slot(&self) -> Option<u64> {
+        self.current.as_ref().map(|current| current.slot)
+    }
+
+    /// 迟到事件的数量（含丢弃和计入当前 slot 的）
+    pub fn late_count(&self) -> u64 {
+        self.late_count
+    }
+}
+
+/// 有界通道已满时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// 等待消费者取走事件，暂停读取上游
+    #[default]
+    Block,
+    /// 丢弃排队中最早的事件，保留新事件
+    DropOldest,
+    /// 丢弃新到达的事件
+    DropNewest,
+}
+
+/// `DexEvent` 流的组合方法
+pub trait DexEventStreamExt: Stream<Item = DexEvent> {
+    /// 为每个事件附带同一账户上一次的状态，见 [`PreviousStateTracker`]
+    fn with_previous(self) -> impl Stream<Item = StateChange<DexEvent>>
+    where
+        Self: Sized,
+    {
+        let mut tracker = PreviousStateTracker::new();
+        self.map(move |event| tracker.track(event))
+    }
+
+    /// 为每个事件附带从 `start` 开始的单调递增序号，见 [`SequenceStamper`]
+    fn with_sequence(self, start: u64) -> impl Stream<Item = Sequenced<DexEvent>>
+    where
+        Self: Sized,
+    {
+        let mut stamper = SequenceStamper::new(start);
+        self.map(move |event| stamper.stamp(event))
+    }
+
+    /// 在事件到达消费者之前对其进行转换（如脱敏或补充字段）
+    ///
+    /// 可与 `StreamExt::inspect`（tap）和 `StreamExt::filter` 组合成处理链
+    fn map_event<F>(self, f: F) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+        F: FnMut(DexEvent) -> DexEvent,
+    {
+        self.map(f)
+    }
+
+    /// 超过 `interval` 没有真实事件到达时，发出携带最近已知 slot 的 `HeartbeatEvent`
+    ///
+    /// 用于在链上无活动时驱动下游基于时间的逻辑，上游流结束时适配器也随之结束
+    fn idle_heartbeat(self, interval: Duration) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+    {
+        let stream = Box::pin(self);
+        futures::stream::unfold((stream, 0u64), move |(mut stream, last_slot)| async move {
+            match tokio::time::timeout(interval, stream.next()).await {
+                Ok(Some(event)) => {
+                    let slot = last_slot.max(event.metadata().slot);
+                    Some((event, (stream, slot)))
+                }
+                Ok(None) => None,
+                Err(_) => {
+                    let heartbeat = DexEvent::HeartbeatEvent(HeartbeatEvent::new(last_slot));
+                    Some((heartbeat, (stream, last_slot)))
+                }
+            }
+        })
+    }
+
+    /// 只输出池子进入 `Trade` 状态的 `BonkTradingLiveEvent`，见 [`TradingLiveTracker`]
+    fn trading_live(self) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+    {
+        let mut tracker = TradingLiveTracker::new();
+        self.filter_map(move |event| futures::future::ready(tracker.observe(&event)))
+    }
+
+    /// 只输出 `BonkGlobalConfigChangedEvent`，见 [`GlobalConfigChangeTracker`]
+    fn global_config_changes(self) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+    {
+        let mut tracker = GlobalConfigChangeTracker::new();
+        self.filter_map(move |event| futures::future::ready(tracker.observe(&event)))
+    }
+
+    /// 只输出由池状态变化推断的 `BonkImpliedTradeEvent`，见 [`PoolTradeTracker`]
+    fn implied_trades(self) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+    {
+        let mut tracker = PoolTradeTracker::new();
+        self.filter_map(move |event| futures::future::ready(tracker.observe(&event)))
+    }
+
+    /// 原样输出事件，slot 跳跃超过 `threshold` 时调用 `on_gap`，见 [`GapDetector`]
+    ///
+    /// ```ignore
+    /// let events = events.detect_slot_gaps(50, |gap| {
+    ///     log::warn!("missed {} slots between {} and {}", gap.missed(), gap.from, gap.to)
+    /// });
+    /// ```
+    fn detect_slot_gaps<F>(self, threshold: u64, mut on_gap: F) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+        F: FnMut(SlotGap),
+    {
+        let mut detector = GapDetector::new(threshold);
+        self.inspect(move |event| {
+            if let Some(gap) = detector.observe(event) {
+                on_gap(gap);
+            }
+        })
+    }
+
+    /// 按 slot 重排事件，见 [`SlotOrderingBuffer`]
+    ///
+    /// 迟到事件被丢弃，上游流结束时输出全部暂存的事件
+    fn order_by_slot(self, lookback: u64) -> impl Stream<Item = DexEvent>
+    where
+        Self: Sized,
+    {
+        let state = (Box::pin(self), SlotOrderingBuffer::new(lookback), VecDeque::new(), false);
+        futures::stream::unfold(state, |(mut stream, mut buffer, mut ready, mut done)| async move {
+            loop {
+                if let Some(event) = ready.pop_front() {
+                    return Some((event, (stream, buffer, ready, done)));
+                }
+                if done {
+                    return None;
+                }
+                match stream.next().await {
+                    Some(event) => ready.extend(buffer.push(event)),
+                    None => {
+                        ready.extend(buffer.drain());
+                        done = true;
+                    }
+                }
+            }
+        })
+    }
+
+    /// 按 slot 统计各类事件的数量，见 [`SlotAggregator`]
+    ///
+    /// slot 前进时输出上一个 slot 的统计结果，上游流结束时输出最后一个 slot 的统计结果
+    ///
+    /// ```ignore
+    /// let mut aggregates = events.aggregate_by_slot(LateSlotPolicy::Drop);
+    /// while let Some(aggregate) = aggregates.next().await {
+    ///     dashboard.update(aggregate.slot, &aggregate.counts);
+    /// }
+    /// ```
+    fn aggregate_by_slot(self, late_policy: LateSlotPolicy) -> impl Stream<Item = SlotAggregate>
+    where
+        Self: Sized,
+    {
+        let aggregator = SlotAggregator::new().with_late_policy(late_policy);
+        let state = (Box::pin(self), aggregator, false);
+        futures::stream::unfold(state, |(mut stream, mut aggregator, done)| async move {
+            if done {
+                return None;
+            }
+            loop {
+                match stream.next().await {
+                    Some(event) => {
+                        if let Some(aggregate) = aggregator.observe(&event) {
+                            return Some((aggregate, (stream, aggregator, false)));
+                        }
+                    }
+                    None => {
+                        let aggregate = aggregator.flush()?;
+                        return Some((aggregate, (stream, aggregator, true)));
+                    }
+                }
+            }
+        })
+    }
+
+    /// 按协议将事件拆分到各自的有界通道，每个通道可以挂接独立的消费者
+    ///
+    /// 路由任务通过 `tokio::spawn` 启动，须在 tokio runtime 中调用。每个协议的通道容量为
+    /// `backpressure.permits`；通道已满时 `Block` 策略会暂停路由，`Drop` 策略丢弃该协议的事件，
+    /// 不影响其他协议。`Common` 事件（区块、Token 账户等）和未列出的协议会被丢弃
+    fn split_by_protocol(
+        self,
+        protocols: &[Protocol],
+        backpressure: BackpressureConfig,
+    ) -> HashMap<Protocol, mpsc::Receiver<DexEvent>>
+    where
+        Self: Sized + Send + 'static,
+    {
+        let mut senders = HashMap::new();
+        let mut receivers = HashMap::new();
+        for protocol in protocols {
+            let (tx, rx) = mpsc::channel(backpressure.permits.max(1));
+            senders.insert(protocol.clone(), tx);
+            receivers.insert(protocol.clone(), rx);
+        }
+        tokio::spawn(async move {
+            let mut stream = Box::pin(self);
+            while let Some(event) = stream.next().await {
+                let Some(protocol) = Protocol::from_protocol_type(&event.metadata().protocol)
+                else {
+                    continue;
+                };
+                let Some(tx) = senders.get_mut(&protocol) else {
+                    continue;
+                };
+                let closed = match backpressure.strategy {
+                    BackpressureStrategy::Block => tx.send(event).await.is_err(),
+                    BackpressureStrategy::Drop => {
+                        tx.try_send(event).is_err_and(|e| e.is_disconnected())
+                    }
+                };
+                // 消费者已丢弃接收端，不再向该协议路由；全部关闭时结束任务
+                if closed {
+                    senders.remove(&protocol);
+                    if senders.is_empty() {
+                        break;
+                    }
+                }
+            }
+        });
+        receivers
+    }
+
+    /// 将事件转发到容量为 `capacity` 的有界通道，消费者跟不上时按 `overflow` 处理
+    ///
+    /// 转发任务通过 `tokio::spawn` 启动，须在 tokio runtime 中调用，接收端被丢弃后任务结束。
+    /// 被丢弃的事件计入 [`MetricsManager`] 的丢弃事件计数（需开启指标）。`DropOldest` 在转发
+    /// 任务中最多排队 `capacity` 个事件，另有一个事件已交给通道、不会被丢弃
+    fn into_bounded_channel(
+        self,
+        capacity: usize,
+        overflow: OverflowPolicy,
+    ) -> mpsc::Receiver<DexEvent>
+    where
+        Self: Sized + Send + 'static,
+    {
+        let capacity = capacity.max(1);
+        if overflow == OverflowPolicy::DropOldest {
+            let (mut tx, rx) = mpsc::channel(0);
+            tokio::spawn(async move {
+                let mut stream = Box::pin(self);
+                let mut pending = VecDeque::with_capacity(capacity);
+                let mut done = false;
+                while !done || !pending.is_empty() {
+                    tokio::select! {
+                        ready = futures::future::poll_fn(|cx| tx.poll_ready(cx)),
+                            if !pending.is_empty() =>
+                        {
+                            let Some(event) = pending.pop_front() else { continue };
+                            if ready.is_err() || tx.start_send(event).is_err() {
+                                break;
+                            }
+                        }
+                        event = stream.next(), if !done => match event {
+                            Some(event) => {
+                                if pending.len() == capacity {
+                                    pending.pop_front();
+                                    MetricsManager::global().increment_dropped_events();
+                                }
+                                pending.push_back(event);
+                            }
+                            None => done = true,
+                        },
+                    }
+                }
+            });
+            return rx;
+        }
+
+        // futures 通道为每个发送端额外保留一个位置
+        let (mut tx, rx) = mpsc::channel(capacity - 1);
+        tokio::spawn(async move {
+            let mut stream = Box::pin(self);
+            while let Some(event) = stream.next().await {
+                let closed = match overflow {
+                    OverflowPolicy::DropNewest => match tx.try_send(event) {
+                        Err(e) if e.is_full() => {
+                            MetricsManager::global().increment_dropped_events();
+                            false
+                        }
+                        result => result.is_err(),
+                    },
+                    _ => tx.send(event).await.is_err(),
+                };
+                if closed {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+}
+
+impl<S: Stream<Item = DexEvent>> DexEventStreamExt for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::EventMetadata;
+    use crate::streaming::event_parser::protocols::bonk::BonkPoolStateAccountEvent;
+    use crate::streaming::grpc::AccountEventBase;
+
+    /// 脚本中的一条账户更新：`(slot, write_version)`，`write_version` 同时作为 slot 内的 `seq`
+    type Version = (u64, u64);
+
+    fn scripted_event(pubkey: Pubkey, (slot, write_version): Version) -> DexEvent {
+        DexEvent::BonkPoolStateAccountEvent(BonkPoolStateAccountEvent {
+            metadata: EventMetadata {
+                slot,
+                seq: write_version,
+                write_version: Some(write_version),
+                event_type: EventType::AccountBonkPoolState,
+                ..Default::default()
+            },
+            base: AccountEventBase { pubkey, ..Default::default() },
+            ..Default::default()
+        })
+    }
+
+    fn version(event: &DexEvent) -> Version {
+        let metadata = event.metadata();
+        (metadata.slot, metadata.write_version.unwrap_or_default())
+    }
+
+    /// 所有排列，用于穷举到达顺序
+    fn permutations(items: &[Version]) -> Vec<Vec<Version>> {
+        if items.len() <= 1 {
+            return vec![items.to_vec()];
+        }
+        let mut result = Vec::new();
+        for i in 0..items.len() {
+            let mut rest = items.to_vec();
+            let first = rest.remove(i);
+            for mut tail in permutations(&rest) {
+                tail.insert(0, first);
+                result.push(tail);
+            }
+        }
+        result
+    }
+
+    /// 按脚本顺序把事件送入 `SlotOrderingBuffer`，返回输出的和迟到的 `(slot, write_version)`
+    fn run_ordering_script(script: &[Version], lookback: u64) -> (Vec<Version>, Vec<Version>) {
+        let pubkey = Pubkey::new_unique();
+        let mut buffer =
+            SlotOrderingBuffer::new(lookback).with_late_policy(LateEventPolicy::Collect);
+        let mut emitted = Vec::new();
+        for &step in script {
+            emitted.extend(buffer.push(scripted_event(pubkey, step)));
+        }
+        emitted.extend(buffer.drain());
+        let late = buffer.take_late_events();
+        assert_eq!(buffer.late_count(), late.len() as u64);
+        (emitted.iter().map(version).collect(), late.iter().map(version).collect())
+    }
+
+    #[test]
+    fn ordering_buffer_sorts_every_interleaving() {
+        let updates = [(10, 1), (10, 2), (11, 3), (11, 4), (12, 5), (13, 6)];
+        for script in permutations(&updates) {
+            // lookback 大于脚本的 slot 跨度时不会有迟到事件，输出即完整的链上顺序
+            let (emitted, late) = run_ordering_script(&script, 4);
+            assert_eq!(emitted, updates, "script {script:?}");
+            assert!(late.is_empty(), "script {script:?}");
+
+            // lookback 较小时，迟到事件被单独收集，其余事件仍按顺序输出且不丢失
+            let (emitted, late) = run_ordering_script(&script, 1);
+            assert!(emitted.windows(2).all(|w| w[0] < w[1]), "script {script:?}: {emitted:?}");
+            let mut all: Vec<_> = emitted.iter().chain(&late).copied().collect();
+            all.sort();
+            assert_eq!(all, updates, "script {script:?}");
+        }
+    }
+
+    #[test]
+    fn late_events_behind_the_watermark_are_dropped_by_default() {
+        let pubkey = Pubkey::new_unique();
+        let mut buffer = SlotOrderingBuffer::new(1);
+        assert!(buffer.push(scripted_event(pubkey, (11, 2))).is_empty());
+        let emitted = buffer.push(scripted_event(pubkey, (12, 3)));
+        assert_eq!(emitted.iter().map(version).collect::<Vec<_>>(), [(11, 2)]);
+
+        assert!(buffer.push(scripted_event(pubkey, (10, 1))).is_empty());
+        assert!(buffer.push(scripted_event(pubkey, (11, 1))).is_empty());
+        assert_eq!(buffer.late_count(), 2);
+        assert!(buffer.take_late_events().is_empty());
+        assert_eq!(buffer.drain().iter().map(version).collect::<Vec<_>>(), [(12, 3)]);
+    }
+
+    #[test]
+    fn dedup_keeps_only_newer_versions_per_account() {
+        use crate::streaming::grpc::{AccountPrettyBuilder, DedupLayer};
+
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // 重复投递和旧版本在两个账户间交错到达
+        let script = [
+            (a, 10, 2),
+            (b, 10, 1),
+            (a, 10, 1),
+            (a, 10, 2),
+            (b, 11, 5),
+            (a, 11, 3),
+            (b, 10, 4),
+            (b, 11, 5),
+        ];
+        let mut dedup = DedupLayer::new(16);
+        let accepted: Vec<_> = script
+            .iter()
+            .filter(|&&(pubkey, slot, write_version)| {
+                let account = AccountPrettyBuilder::new()
+                    .pubkey(pubkey)
+                    .slot(slot)
+                    .write_version(write_version)
+                    .build();
+                dedup.accept(&account)
+            })
+            .copied()
+            .collect();
+        assert_eq!(accepted, [(a, 10, 2), (b, 10, 1), (b, 11, 5), (a, 11, 3)]);
+        assert_eq!(dedup.dropped_count(), 4);
+    }
+
+    #[test]
+    fn global_config_tracker_reports_changes_after_the_first_snapshot() {
+        use crate::streaming::event_parser::protocols::bonk::{
+            BonkGlobalConfigAccountEvent, GlobalConfig,
+        };
+
+        let pubkey = Pubkey::new_unique();
+        let snapshot = |slot: u64, migrate_fee: u64| {
+            DexEvent::BonkGlobalConfigAccountEvent(BonkGlobalConfigAccountEvent {
+                metadata: EventMetadata { slot, ..Default::default() },
+                base: AccountEventBase { pubkey, ..Default::default() },
+                global_config: GlobalConfig { migrate_fee, ..Default::default() },
+            })
+        };
+        let mut tracker = GlobalConfigChangeTracker::new();
+        assert_eq!(tracker.observe(&snapshot(10, 100)), None);
+        assert_eq!(tracker.observe(&snapshot(11, 100)), None);
+        assert_eq!(tracker.observe(&scripted_event(pubkey, (12, 1))), None);
+
+        let Some(DexEvent::BonkGlobalConfigChangedEvent(changed)) =
+            tracker.observe(&snapshot(13, 250))
+        else {
+            panic!("migrate_fee change should be reported");
+        };
+        assert_eq!((changed.pubkey, changed.slot), (pubkey, 13));
+        assert_eq!(changed.metadata.event_type, EventType::BonkGlobalConfigChanged);
+        let fields: Vec<_> = changed.changes.iter().map(|c| c.field.as_ref()).collect();
+        assert_eq!(fields, ["migrate_fee"]);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn implied_trades_follow_reserve_deltas_after_the_baseline() {
+        use crate::streaming::event_parser::protocols::bonk::{PoolState, TradeDirection};
+
+        let pubkey = Pubkey::new_unique();
+        let base_mint = Pubkey::new_unique();
+        let snapshot = |slot: u64, epoch: u64, real_base: u64, real_quote: u64| {
+            DexEvent::BonkPoolStateAccountEvent(BonkPoolStateAccountEvent {
+                metadata: EventMetadata { slot, ..Default::default() },
+                base: AccountEventBase { pubkey, ..Default::default() },
+                pool_state: PoolState {
+                    epoch,
+                    base_decimals: 6,
+                    quote_decimals: 9,
+                    virtual_base: 1_073_000_000_000,
+                    virtual_quote: 30_000_000_000,
+                    real_base,
+                    real_quote,
+                    base_mint,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+        let updates = [
+            // 首次见到的池子只作为基线
+            snapshot(10, 800, 73_000, 10_000),
+            // 买入：base 和 quote 储备同时增加
+            snapshot(11, 800, 73_500, 10_020),
+            // 只有 epoch 变化，不是交易
+            snapshot(12, 801, 73_500, 10_020),
+            // 卖出：两者同时减少
+            snapshot(13, 801, 73_200, 10_008),
+        ];
+        let trades: Vec<_> =
+            futures::stream::iter(updates.clone()).implied_trades().collect().await;
+
+        let trades: Vec<_> = trades
+            .into_iter()
+            .map(|event| match event {
+                DexEvent::BonkImpliedTradeEvent(trade) => trade,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        let summary: Vec<_> =
+            trades.iter().map(|t| (t.slot, t.direction, t.base_amount, t.quote_amount)).collect();
+        assert_eq!(
+            summary,
+            [(11, TradeDirection::Buy, 500, 20), (13, TradeDirection::Sell, 300, 12)]
+        );
+        assert!(trades.iter().all(|t| t.pubkey == pubkey && t.base_mint == base_mint));
+        assert_eq!(trades[0].metadata.event_type, EventType::BonkImpliedTrade);
+        let DexEvent::BonkPoolStateAccountEvent(sold) = &updates[3] else { unreachable!() };
+        assert_eq!(trades[1].price_fixed, sold.pool_state.price_fixed());
+    }
+
+    #[test]
+    fn momentum_tracker_survives_extreme_timestamps() {
+        let pubkey = Pubkey::new_unique();
+        let mut tracker = MomentumTracker::new(Duration::MAX);
+        tracker.update(pubkey, 0, i64::MIN);
+        tracker.update(pubkey, u64::MAX, i64::MAX);
+        assert!(tracker.velocity(&pubkey) > 0.0);
+        assert_eq!(tracker.evict_stale(), 0);
+
+        let mut tracker = MomentumTracker::new(Duration::from_secs(1));
+        tracker.update(pubkey, 0, i64::MIN);
+        tracker.update(Pubkey::new_unique(), 0, i64::MAX);
+        assert_eq!(tracker.velocity(&pubkey), 0.0);
+        assert_eq!(tracker.evict_stale(), 1);
+    }
+
+    #[tokio::test]
+    async fn with_sequence_assigns_contiguous_increasing_numbers() {
+        let pubkey = Pubkey::new_unique();
+        // slot 重复和乱序不影响序号
+        let events =
+            [(12, 1), (10, 2), (10, 3), (11, 4), (12, 5)].map(|step| scripted_event(pubkey, step));
+        let sequenced: Vec<_> =
+            futures::stream::iter(events.clone()).with_sequence(41).collect().await;
+        let seqs: Vec<_> = sequenced.iter().map(|s| s.seq).collect();
+        assert_eq!(seqs, [41, 42, 43, 44, 45]);
+        let arrival: Vec<_> = sequenced.into_iter().map(|s| s.event).collect();
+        assert_eq!(arrival, events);
+    }
+
+    #[tokio::test]
+    async fn slot_jump_beyond_the_threshold_is_reported_as_a_gap() {
+        let pubkey = Pubkey::new_unique();
+        // 乱序、同一 slot 和 slot 0 都不参与比较
+        let slots = [100, 101, 99, 101, 0, 150, 155];
+        let events = slots.map(|slot| scripted_event(pubkey, (slot, 1)));
+        let mut gaps = Vec::new();
+        let passed: Vec<_> = futures::stream::iter(events.clone())
+            .detect_slot_gaps(10, |gap| gaps.push(gap))
+            .collect()
+            .await;
+        assert_eq!(passed, events);
+        assert_eq!(gaps, [SlotGap { from: 101, to: 150 }]);
+
+        let mut detector = GapDetector::new(10);
+        for slot in slots {
+            detector.observe_slot(slot);
+        }
+        assert_eq!((detector.max_slot(), detector.gap_count()), (Some(155), 1));
+        // 恰好等于阈值的跳跃不算空缺
+        assert_eq!(detector.observe_slot(165), None);
+        assert_eq!(detector.observe_slot(176), Some(SlotGap { from: 165, to: 176 }));
+    }
+
+    /// 先让转发任务处理完所有已就绪的事件再读取通道，模拟跟不上的消费者
+    ///
+    /// 返回收到的 slot、读取通道前上游已被拉取的事件数和期间新增的丢弃计数
+    async fn receive_slowly(policy: OverflowPolicy) -> (Vec<u64>, usize, u64) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let pubkey = Pubkey::new_unique();
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let counter = pulled.clone();
+        let events = futures::stream::iter(0..10).map(move |slot| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            scripted_event(pubkey, (slot, 0))
+        });
+        let dropped = MetricsManager::global().get_dropped_events_count();
+        let receiver = events.into_bounded_channel(3, policy);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let pulled = pulled.load(Ordering::Relaxed);
+        let slots = receiver.map(|event| event.slot()).collect().await;
+        (slots, pulled, MetricsManager::global().get_dropped_events_count() - dropped)
+    }
+
+    #[tokio::test]
+    async fn bounded_channel_applies_the_overflow_policy() {
+        // 通道中放满 3 个事件后暂停读取上游
+        let (slots, pulled, dropped) = receive_slowly(OverflowPolicy::Block).await;
+        assert_eq!(pulled, 3);
+        assert_eq!(slots, (0..10).collect::<Vec<_>>());
+        assert_eq!(dropped, 0);
+
+        let (slots, pulled, dropped) = receive_slowly(OverflowPolicy::DropNewest).await;
+        assert_eq!(pulled, 10);
+        assert_eq!(slots, [0, 1, 2]);
+        assert_eq!(dropped, 7);
+
+        // 最后 3 个事件保留在转发任务中，另有一个较早的事件已交给通道
+        let (slots, pulled, dropped) = receive_slowly(OverflowPolicy::DropOldest).await;
+        assert_eq!(pulled, 10);
+        assert_eq!(slots.len(), 4, "{slots:?}");
+        assert!(slots[0] < 7, "{slots:?}");
+        assert_eq!(slots[1..], [7, 8, 9]);
+        assert_eq!(dropped, 6);
+    }
+
+    fn typed_event(slot: u64, event_type: EventType) -> DexEvent {
+        let mut event = scripted_event(Pubkey::new_unique(), (slot, 0));
+        event.metadata_mut().event_type = event_type;
+        event
+    }
+
+    fn counts(entries: &[(EventType, u64)]) -> HashMap<EventType, u64> {
+        entries.iter().cloned().collect()
+    }
+
+    #[tokio::test]
+    async fn aggregates_three_slots_by_event_type() {
+        use EventType::{AccountBonkGlobalConfig as Config, AccountBonkPoolState as Pool};
+
+        let events = vec![
+            typed_event(100, Pool.clone()),
+            typed_event(100, Config.clone()),
+            typed_event(100, Pool.clone()),
+            typed_event(101, Pool.clone()),
+            // 迟到事件：FoldIntoCurrent 计入 slot 101，Drop 丢弃
+            typed_event(100, Config.clone()),
+            typed_event(102, Config.clone()),
+            typed_event(102, Config.clone()),
+        ];
+
+        let folded: Vec<_> = futures::stream::iter(events.clone())
+            .aggregate_by_slot(LateSlotPolicy::FoldIntoCurrent)
+            .collect()
+            .await;
+        assert_eq!(
+            folded,
+            [
+                SlotAggregate {
+                    slot: 100,
+                    counts: counts(&[(Pool.clone(), 2), (Config.clone(), 1)])
+                },
+                SlotAggregate {
+                    slot: 101,
+                    counts: counts(&[(Pool.clone(), 1), (Config.clone(), 1)])
+                },
+                SlotAggregate { slot: 102, counts: counts(&[(Config.clone(), 2)]) },
+            ]
+        );
+
+        let mut aggregator = SlotAggregator::new();
+        let dropped: Vec<_> = events.iter().filter_map(|e| aggregator.observe(e)).collect();
+        assert_eq!(dropped[1], SlotAggregate { slot: 101, counts: counts(&[(Pool, 1)]) });
+        assert_eq!(aggregator.late_count(), 1);
+        assert_eq!(aggregator.flush().map(|a| a.total()), Some(2));
+        assert_eq!(aggregator.flush(), None);
+    }
+}
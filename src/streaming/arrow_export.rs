@@ -0,0 +1,193 @@
+//! Arrow 列式导出（需要启用 `arrow` feature）
+//!
+//! 将一批 `BonkPoolStateAccountEvent` 转换为一个 `RecordBatch`，便于批量写入 Parquet 等
+//! 列式存储。列的顺序和类型见 [`pool_state_arrow_schema`]，新增列只追加在末尾：
+//!
+//! - 数值字段按原类型导出（u64 → `UInt64`，u8 → `UInt8`），不做精度换算
+//! - 地址导出为 32 字节的 `FixedSizeBinary`
+//! - `vesting_schedule` 展开为 `vesting_` 前缀的列，`padding` 不导出
+//!
+//! ```ignore
+//! let batch = pool_states_to_record_batch(&events);
+//! writer.write(&batch)?;
+//! ```
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, FixedSizeBinaryBuilder, Int64Array, UInt64Array, UInt8Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::protocols::bonk::BonkPoolStateAccountEvent;
+
+const PUBKEY_BYTES: i32 = 32;
+
+/// `pool_states_to_record_batch` 输出的 schema，所有列均不可为空
+pub fn pool_state_arrow_schema() -> Schema {
+    let u64_field = |name: &str| Field::new(name, DataType::UInt64, false);
+    let u8_field = |name: &str| Field::new(name, DataType::UInt8, false);
+    let pubkey_field =
+        |name: &str| Field::new(name, DataType::FixedSizeBinary(PUBKEY_BYTES), false);
+    Schema::new(vec![
+        u64_field("slot"),
+        Field::new("recv_us", DataType::Int64, false),
+        pubkey_field("pubkey"),
+        u64_field("lamports"),
+        u64_field("epoch"),
+        u8_field("status"),
+        u8_field("base_decimals"),
+        u8_field("quote_decimals"),
+        u8_field("migrate_type"),
+        u64_field("supply"),
+        u64_field("total_base_sell"),
+        u64_field("virtual_base"),
+        u64_field("virtual_quote"),
+        u64_field("real_base"),
+        u64_field("real_quote"),
+        u64_field("total_quote_fund_raising"),
+        u64_field("quote_protocol_fee"),
+        u64_field("platform_fee"),
+        u64_field("migrate_fee"),
+        u64_field("vesting_total_locked_amount"),
+        u64_field("vesting_cliff_period"),
+        u64_field("vesting_unlock_period"),
+        u64_field("vesting_start_time"),
+        u64_field("vesting_allocated_share_amount"),
+        pubkey_field("global_config"),
+        pubkey_field("platform_config"),
+        pubkey_field("base_mint"),
+        pubkey_field("quote_mint"),
+        pubkey_field("base_vault"),
+        pubkey_field("quote_vault"),
+        pubkey_field("creator"),
+        u8_field("token_program_flag"),
+        u8_field("amm_creator_fee_on"),
+        u64_field("platform_vesting_share"),
+    ])
+}
+
+/// 将 `events` 按 [`pool_state_arrow_schema`] 转换为一个 `RecordBatch`，每个事件一行
+pub fn pool_states_to_record_batch(events: &[BonkPoolStateAccountEvent]) -> RecordBatch {
+    type Event = BonkPoolStateAccountEvent;
+
+    let u64_column = |value: fn(&Event) -> u64| -> ArrayRef {
+        Arc::new(UInt64Array::from_iter_values(events.iter().map(value)))
+    };
+    let u8_column = |value: fn(&Event) -> u8| -> ArrayRef {
+        Arc::new(UInt8Array::from_iter_values(events.iter().map(value)))
+    };
+    let pubkey_column = |value: fn(&Event) -> Pubkey| -> ArrayRef {
+        let mut builder = FixedSizeBinaryBuilder::with_capacity(events.len(), PUBKEY_BYTES);
+        for event in events {
+            builder.append_value(value(event)).expect("pubkey is always 32 bytes");
+        }
+        Arc::new(builder.finish())
+    };
+
+    let columns: Vec<ArrayRef> = vec![
+        u64_column(|e| e.metadata.slot),
+        Arc::new(Int64Array::from_iter_values(events.iter().map(|e| e.metadata.recv_us))),
+        pubkey_column(|e| e.base.pubkey),
+        u64_column(|e| e.base.lamports),
+        u64_column(|e| e.pool_state.epoch),
+        u8_column(|e| e.pool_state.status),
+        u8_column(|e| e.pool_state.base_decimals),
+        u8_column(|e| e.pool_state.quote_decimals),
+        u8_column(|e| e.pool_state.migrate_type),
+        u64_column(|e| e.pool_state.supply),
+        u64_column(|e| e.pool_state.total_base_sell),
+        u64_column(|e| e.pool_state.virtual_base),
+        u64_column(|e| e.pool_state.virtual_quote),
+        u64_column(|e| e.pool_state.real_base),
+        u64_column(|e| e.pool_state.real_quote),
+        u64_column(|e| e.pool_state.total_quote_fund_raising),
+        u64_column(|e| e.pool_state.quote_protocol_fee),
+        u64_column(|e| e.pool_state.platform_fee),
+        u64_column(|e| e.pool_state.migrate_fee),
+        u64_column(|e| e.pool_state.vesting_schedule.total_locked_amount),
+        u64_column(|e| e.pool_state.vesting_schedule.cliff_period),
+        u64_column(|e| e.pool_state.vesting_schedule.unlock_period),
+        u64_column(|e| e.pool_state.vesting_schedule.start_time),
+        u64_column(|e| e.pool_state.vesting_schedule.allocated_share_amount),
+        pubkey_column(|e| e.pool_state.global_config),
+        pubkey_column(|e| e.pool_state.platform_config),
+        pubkey_column(|e| e.pool_state.base_mint),
+        pubkey_column(|e| e.pool_state.quote_mint),
+        pubkey_column(|e| e.pool_state.base_vault),
+        pubkey_column(|e| e.pool_state.quote_vault),
+        pubkey_column(|e| e.pool_state.creator),
+        u8_column(|e| e.pool_state.token_program_flag),
+        u8_column(|e| e.pool_state.amm_creator_fee_on.as_u8()),
+        u64_column(|e| e.pool_state.platform_vesting_share),
+    ];
+    RecordBatch::try_new(Arc::new(pool_state_arrow_schema()), columns)
+        .expect("columns always match pool_state_arrow_schema")
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{Int64Type, UInt64Type, UInt8Type};
+
+    use super::*;
+    use crate::streaming::event_parser::common::EventMetadata;
+    use crate::streaming::event_parser::protocols::bonk::{AmmCreatorFeeOn, PoolState};
+    use crate::streaming::grpc::AccountEventBase;
+
+    fn pool_state_event(slot: u64, real_quote: u64, creator: Pubkey) -> BonkPoolStateAccountEvent {
+        BonkPoolStateAccountEvent {
+            metadata: EventMetadata { slot, recv_us: slot as i64 * 1_000, ..Default::default() },
+            base: AccountEventBase {
+                pubkey: Pubkey::new_unique(),
+                lamports: 2_039_280,
+                ..Default::default()
+            },
+            pool_state: PoolState {
+                real_quote,
+                creator,
+                amm_creator_fee_on: AmmCreatorFeeOn::BothToken,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn two_events_become_two_rows_with_their_values() {
+        let creators = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let events =
+            [pool_state_event(100, 5_000, creators[0]), pool_state_event(101, 7_500, creators[1])];
+        let batch = pool_states_to_record_batch(&events);
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().as_ref(), &pool_state_arrow_schema());
+        let column = |name: &str| batch.column_by_name(name).unwrap();
+        let u64_values = |name: &str| column(name).as_primitive::<UInt64Type>().values().to_vec();
+        assert_eq!(u64_values("slot"), [100, 101]);
+        assert_eq!(u64_values("real_quote"), [5_000, 7_500]);
+        assert_eq!(u64_values("lamports"), [2_039_280, 2_039_280]);
+        assert_eq!(
+            column("recv_us").as_primitive::<Int64Type>().values().to_vec(),
+            [100_000, 101_000]
+        );
+        assert_eq!(
+            column("amm_creator_fee_on").as_primitive::<UInt8Type>().values().to_vec(),
+            [1, 1]
+        );
+
+        let pubkeys = |name: &str| -> Vec<Pubkey> {
+            let column = column(name).as_fixed_size_binary();
+            (0..column.len()).map(|row| Pubkey::try_from(column.value(row)).unwrap()).collect()
+        };
+        assert_eq!(pubkeys("creator"), creators.to_vec());
+        assert_eq!(pubkeys("pubkey"), events.iter().map(|e| e.base.pubkey).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn empty_input_is_an_empty_batch() {
+        let batch = pool_states_to_record_batch(&[]);
+        assert_eq!(batch.num_rows(), 0);
+        assert_eq!(batch.num_columns(), pool_state_arrow_schema().fields().len());
+    }
+}
@@ -0,0 +1,93 @@
+//! 通过 tokio broadcast 通道将事件分发给多个消费者
+//!
+//! 每个消费者通过 [`BroadcastFanout::subscribe`] 获得独立的 [`BroadcastSubscriber`]。
+//! broadcast 通道容量有限，消费过慢的订阅者会被跳过最早的事件；跳过的数量通过
+//! `RecvError::Lagged(n)` 返回并按订阅者累计，消费者据此判断是否需要重新同步。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::streaming::event_parser::DexEvent;
+
+/// 事件广播分发器
+///
+/// ```ignore
+/// let fanout = Arc::new(BroadcastFanout::new(4096));
+/// let mut subscriber = fanout.subscribe();
+/// grpc.subscribe_events_immediate(..., fanout.clone().into_callback()).await?;
+/// loop {
+///     match subscriber.recv().await {
+///         Ok(event) => handle(event),
+///         Err(RecvError::Lagged(n)) => resync(n),
+///         Err(RecvError::Closed) => break,
+///     }
+/// }
+/// ```
+pub struct BroadcastFanout {
+    tx: broadcast::Sender<DexEvent>,
+    /// 没有任何订阅者时丢弃的事件数量
+    unsubscribed_count: AtomicU64,
+}
+
+impl BroadcastFanout {
+    /// 创建分发器，`capacity` 为每个订阅者最多可落后的事件数量
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self { tx, unsubscribed_count: AtomicU64::new(0) }
+    }
+
+    /// 新增一个订阅者，只会收到订阅之后发送的事件
+    pub fn subscribe(&self) -> BroadcastSubscriber {
+        BroadcastSubscriber { rx: self.tx.subscribe(), lagged_count: 0 }
+    }
+
+    /// 发送事件给所有订阅者，不会阻塞调用方
+    pub fn send(&self, event: DexEvent) {
+        if self.tx.send(event).is_err() {
+            self.unsubscribed_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 生成可直接传给订阅接口的回调
+    pub fn into_callback(self: Arc<Self>) -> impl Fn(DexEvent) + Send + Sync + 'static {
+        move |event: DexEvent| self.send(event)
+    }
+
+    /// 当前订阅者数量
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+
+    /// 因没有订阅者而丢弃的事件数量
+    pub fn get_unsubscribed_count(&self) -> u64 {
+        self.unsubscribed_count.load(Ordering::Relaxed)
+    }
+}
+
+/// 单个订阅者，记录自身因落后而错过的事件数量
+pub struct BroadcastSubscriber {
+    rx: broadcast::Receiver<DexEvent>,
+    lagged_count: u64,
+}
+
+impl BroadcastSubscriber {
+    /// 接收下一个事件
+    ///
+    /// 落后过多时返回 `RecvError::Lagged(n)`（`n` 为本次错过的事件数量），之后的调用从
+    /// 仍在通道中的最早事件继续接收；分发器被丢弃后返回 `RecvError::Closed`
+    pub async fn recv(&mut self) -> Result<DexEvent, RecvError> {
+        let result = self.rx.recv().await;
+        if let Err(RecvError::Lagged(n)) = &result {
+            self.lagged_count = self.lagged_count.saturating_add(*n);
+        }
+        result
+    }
+
+    /// 该订阅者累计错过的事件数量
+    pub fn lagged_count(&self) -> u64 {
+        self.lagged_count
+    }
+}
@@ -1,4 +1,8 @@
 use super::constants::*;
+use solana_sdk::pubkey::Pubkey;
+use std::future::Future;
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
 
 /// Connection configuration
 #[derive(Debug, Clone)]
@@ -9,6 +13,12 @@ pub struct ConnectionConfig {
     pub request_timeout: u64,
     /// Maximum decoding message size in bytes (default: 10MB)
     pub max_decoding_message_size: usize,
+    /// Maximum ratio between the decompressed and the compressed size of a gzip message
+    /// (default: 100)
+    pub max_decompression_ratio: u32,
+    /// Extra gRPC metadata `(name, value)` pairs sent with every request,
+    /// in addition to `x-token` (default: empty)
+    pub headers: Vec<(String, String)>,
 }
 
 impl Default for ConnectionConfig {
@@ -17,21 +27,164 @@ impl Default for ConnectionConfig {
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
             request_timeout: DEFAULT_REQUEST_TIMEOUT,
             max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+            max_decompression_ratio: DEFAULT_MAX_DECOMPRESSION_RATIO,
+            headers: Vec::new(),
         }
     }
 }
 
+/// Backpressure handling strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackpressureStrategy {
+    /// Wait for the parser to free up buffer space (default)
+    #[default]
+    Block,
+    /// Drop the incoming message when the buffer is full
+    Drop,
+}
+
+/// Backpressure configuration for the buffer between the network reader and the parser
+#[derive(Debug, Clone)]
+pub struct BackpressureConfig {
+    /// Buffer capacity in messages (default: 1000)
+    pub permits: usize,
+    /// Strategy applied when the buffer is full (default: Block)
+    pub strategy: BackpressureStrategy,
+}
+
+impl Default for BackpressureConfig {
+    fn default() -> Self {
+        Self { permits: DEFAULT_CHANNEL_SIZE, strategy: BackpressureStrategy::default() }
+    }
+}
+
 /// Common client configuration
 #[derive(Debug, Clone)]
 pub struct StreamClientConfig {
     /// Connection configuration
     pub connection: ConnectionConfig,
+    /// Backpressure configuration
+    pub backpressure: BackpressureConfig,
     /// Whether performance monitoring is enabled (default: false)
     pub enable_metrics: bool,
+    /// Account updates owned by these programs are dropped before parsing
+    pub exclude_owners: Vec<Pubkey>,
+    /// Also drop account updates owned by `NOISE_PROGRAM_OWNERS` (default: false)
+    pub exclude_noise_owners: bool,
+    /// Account updates with fewer lamports are dropped before parsing (default: 0, disabled)
+    pub min_lamports: u64,
+    /// Runtime that drives the connection, reader, parser and metrics tasks.
+    /// `None` spawns on the ambient runtime of the caller (default: None)
+    pub runtime: Option<Handle>,
 }
 
 impl Default for StreamClientConfig {
     fn default() -> Self {
-        Self { connection: ConnectionConfig::default(), enable_metrics: false }
+        Self {
+            connection: ConnectionConfig::default(),
+            backpressure: BackpressureConfig::default(),
+            enable_metrics: false,
+            exclude_owners: Vec::new(),
+            exclude_noise_owners: false,
+            min_lamports: 0,
+            runtime: None,
+        }
+    }
+}
+
+impl StreamClientConfig {
+    /// Drop account updates owned by the given programs
+    ///
+    /// Yellowstone account filters can only include owners, so the exclusion is applied
+    /// on the client right after the update is received, before it enters the parse buffer.
+    pub fn exclude_owners(mut self, owners: Vec<Pubkey>) -> Self {
+        self.exclude_owners = owners;
+        self
+    }
+
+    /// Drop account updates owned by common noise programs (Vote, System, Stake, Config)
+    pub fn exclude_noise_owners(mut self, enabled: bool) -> Self {
+        self.exclude_noise_owners = enabled;
+        self
+    }
+
+    /// Drop account updates holding fewer than `lamports`, e.g. dust or closing accounts
+    ///
+    /// Applied on the client before the update enters the parse buffer; dropped updates are
+    /// counted in `MetricsManager::get_low_lamports_filtered_count`.
+    pub fn min_lamports(mut self, lamports: u64) -> Self {
+        self.min_lamports = lamports;
+        self
+    }
+
+    /// Send an extra gRPC metadata header with every request
+    ///
+    /// Header names must be lowercase ASCII; invalid names or values fail on connect.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.connection.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Limit the size of a single gRPC message, e.g. to allow large account snapshots
+    ///
+    /// Messages above the limit end the subscription with a `StreamError::Transport` whose
+    /// status code is `OutOfRange` (see `StreamError::is_message_too_large`). The limit applies
+    /// to both the compressed and the decompressed size of a message.
+    pub fn with_max_message_size(mut self, bytes: usize) -> Self {
+        self.connection.max_decoding_message_size = bytes;
+        self
+    }
+
+    /// Limit how much a single gzip-compressed message may expand when decompressed
+    ///
+    /// Subscriptions opened by `SubscriptionManager` accept gzip-compressed messages. A message
+    /// that decompresses to more than `ratio` times its compressed size ends the subscription
+    /// with the same `OutOfRange` error as an oversized message. Mostly-zero account data
+    /// compresses very well, so keep the ratio generous when subscribing to large accounts.
+    pub fn with_max_decompression_ratio(mut self, ratio: u32) -> Self {
+        self.connection.max_decompression_ratio = ratio;
+        self
+    }
+
+    /// Drive all streaming tasks on the given runtime instead of the ambient one
+    ///
+    /// Callbacks passed to the subscribe methods run on this runtime's worker threads,
+    /// which is why they must be `Send + Sync + 'static`.
+    pub fn with_runtime(mut self, runtime: Handle) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Spawn a task on the configured runtime, or on the ambient runtime if none is set
+    pub(crate) fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        match &self.runtime {
+            Some(runtime) => runtime.spawn(future),
+            None => tokio::spawn(future),
+        }
+    }
+
+    /// Run a future to completion on the configured runtime and wait for its output
+    pub(crate) async fn run<F>(&self, future: F) -> anyhow::Result<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        match &self.runtime {
+            Some(runtime) => Ok(runtime.spawn(future).await?),
+            None => Ok(future.await),
+        }
+    }
+
+    /// All owners whose account updates should be dropped
+    pub fn excluded_owners(&self) -> Vec<Pubkey> {
+        let mut owners = self.exclude_owners.clone();
+        if self.exclude_noise_owners {
+            owners.extend_from_slice(NOISE_PROGRAM_OWNERS);
+        }
+        owners
     }
 }
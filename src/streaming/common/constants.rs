@@ -1,10 +1,13 @@
 // 流处理相关的常量定义
 
+use solana_sdk::pubkey::Pubkey;
+
 // 默认配置常量
 pub const DEFAULT_CONNECT_TIMEOUT: u64 = 10;
 pub const DEFAULT_REQUEST_TIMEOUT: u64 = 60;
 pub const DEFAULT_CHANNEL_SIZE: usize = 1000;
 pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 1024 * 1024 * 10;
+pub const DEFAULT_MAX_DECOMPRESSION_RATIO: u32 = 100;
 
 // 性能监控相关常量
 pub const DEFAULT_METRICS_WINDOW_SECONDS: u64 = 5;
@@ -16,3 +19,11 @@ pub const SLOW_PROCESSING_THRESHOLD_US: f64 = 3000.0;
 pub const SOLANA_BLOCK_TIME_ADJUSTMENT_MS: i64 = 500;
 // 默认最大延迟阈值（毫秒）
 pub const MAX_LATENCY_THRESHOLD_MS: i64 = 1000;
+
+// 常见噪声账户的 owner 程序（Vote、System、Stake、Config）
+pub const NOISE_PROGRAM_OWNERS: &[Pubkey] = &[
+    solana_sdk::pubkey!("Vote111111111111111111111111111111111111111"),
+    solana_sdk::pubkey!("11111111111111111111111111111111"),
+    solana_sdk::pubkey!("Stake11111111111111111111111111111111111111"),
+    solana_sdk::pubkey!("Config1111111111111111111111111111111111111"),
+];
@@ -22,6 +22,7 @@ fn create_metrics_callback(
         let processing_time_us = metadata.handle_us as f64;
         let recv_us = metadata.recv_us;
         let block_time_ms = metadata.block_time_ms;
+        MetricsManager::global().record_parse_latency(&metadata.event_type, processing_time_us);
 
         callback(event);
 
@@ -55,6 +56,8 @@ pub async fn process_grpc_transaction(
 
             if let Some(event) = account_event {
                 let processing_time_us = event.metadata().handle_us as f64;
+                MetricsManager::global()
+                    .record_parse_latency(&event.metadata().event_type, processing_time_us);
                 callback(event);
                 update_metrics(MetricsEventType::Account, 1, processing_time_us);
             }
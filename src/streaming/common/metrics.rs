@@ -1,6 +1,11 @@
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
 
 use super::constants::*;
+use crate::streaming::event_parser::common::EventType as DexEventType;
 
 /// Event type enumeration
 #[derive(Debug, Clone, Copy)]
@@ -171,6 +176,10 @@ pub struct PerformanceMetrics {
     pub block_meta_metrics: EventMetricsSnapshot,
     pub processing_stats: ProcessingTimeStats,
     pub dropped_events_count: u64,
+    pub buffer_high_water_mark: u64,
+    pub buffer_overflow_count: u64,
+    pub low_lamports_filtered_count: u64,
+    pub reconnect_count: u64,
 }
 
 impl PerformanceMetrics {
@@ -190,6 +199,10 @@ impl PerformanceMetrics {
             block_meta_metrics: default_metrics,
             processing_stats: default_stats,
             dropped_events_count: 0,
+            buffer_high_water_mark: 0,
+            buffer_overflow_count: 0,
+            low_lamports_filtered_count: 0,
+            reconnect_count: 0,
         }
     }
 }
@@ -202,6 +215,13 @@ pub struct HighPerformanceMetrics {
     processing_stats: AtomicProcessingTimeStats,
     // 丢弃事件指标
     dropped_events_count: AtomicU64,
+    // 解析缓冲区指标
+    buffer_high_water_mark: AtomicU64,
+    buffer_overflow_count: AtomicU64,
+    // 低于 min_lamports 被丢弃的账户更新
+    low_lamports_filtered_count: AtomicU64,
+    // 订阅断开后重连成功的次数
+    reconnect_count: AtomicU64,
 }
 
 impl HighPerformanceMetrics {
@@ -216,6 +236,10 @@ impl HighPerformanceMetrics {
             ],
             processing_stats: AtomicProcessingTimeStats::new_const(),
             dropped_events_count: AtomicU64::new(0),
+            buffer_high_water_mark: AtomicU64::new(0),
+            buffer_overflow_count: AtomicU64::new(0),
+            low_lamports_filtered_count: AtomicU64::new(0),
+            reconnect_count: AtomicU64::new(0),
         }
     }
 
@@ -266,6 +290,30 @@ impl HighPerformanceMetrics {
         self.dropped_events_count.load(Ordering::Relaxed)
     }
 
+    /// 获取解析缓冲区的最高水位
+    #[inline]
+    pub fn get_buffer_high_water_mark(&self) -> u64 {
+        self.buffer_high_water_mark.load(Ordering::Relaxed)
+    }
+
+    /// 获取解析缓冲区溢出次数
+    #[inline]
+    pub fn get_buffer_overflow_count(&self) -> u64 {
+        self.buffer_overflow_count.load(Ordering::Relaxed)
+    }
+
+    /// 获取因 lamports 低于阈值被丢弃的账户更新数量
+    #[inline]
+    pub fn get_low_lamports_filtered_count(&self) -> u64 {
+        self.low_lamports_filtered_count.load(Ordering::Relaxed)
+    }
+
+    /// 获取订阅重连成功的次数
+    #[inline]
+    pub fn get_reconnect_count(&self) -> u64 {
+        self.reconnect_count.load(Ordering::Relaxed)
+    }
+
     /// 更新窗口指标（后台任务调用）
     fn update_window_metrics(&self, event_type: EventType, window_duration_nanos: u64) {
         let now_nanos =
@@ -282,6 +330,67 @@ impl HighPerformanceMetrics {
     }
 }
 
+/// 解析耗时直方图的桶上界（微秒），最后一个桶收集超出上界的样本
+pub const PARSE_LATENCY_BUCKETS_US: &[u64] =
+    &[1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000];
+
+/// 解析耗时直方图（按事件类型）
+#[derive(Debug)]
+pub struct ParseLatencyHistogram {
+    /// 每个桶的计数，长度为 `PARSE_LATENCY_BUCKETS_US.len() + 1`
+    buckets: Vec<AtomicU64>,
+    total_time_us: AtomicU64,
+    total_events: AtomicU64,
+}
+
+/// 解析耗时直方图快照
+#[derive(Debug, Clone)]
+pub struct ParseLatencySnapshot {
+    /// 桶上界（微秒）
+    pub bounds_us: &'static [u64],
+    /// 每个桶的计数（非累积），比 `bounds_us` 多一个溢出桶
+    pub counts: Vec<u64>,
+    pub total_time_us: u64,
+    pub total_events: u64,
+}
+
+impl ParseLatencyHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: (0..=PARSE_LATENCY_BUCKETS_US.len()).map(|_| AtomicU64::new(0)).collect(),
+            total_time_us: AtomicU64::new(0),
+            total_events: AtomicU64::new(0),
+        }
+    }
+
+    /// 记录一次解析耗时
+    #[inline]
+    pub fn observe(&self, time_us: f64) {
+        let time_us_int = time_us.max(0.0) as u64;
+        let index = PARSE_LATENCY_BUCKETS_US
+            .iter()
+            .position(|&bound| time_us_int <= bound)
+            .unwrap_or(PARSE_LATENCY_BUCKETS_US.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.total_time_us.fetch_add(time_us_int, Ordering::Relaxed);
+        self.total_events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 获取快照（非阻塞）
+    pub fn snapshot(&self) -> ParseLatencySnapshot {
+        ParseLatencySnapshot {
+            bounds_us: PARSE_LATENCY_BUCKETS_US,
+            counts: self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect(),
+            total_time_us: self.total_time_us.load(Ordering::Relaxed),
+            total_events: self.total_events.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 按事件类型划分的解析耗时直方图
+static PARSE_LATENCY_HISTOGRAMS: Lazy<DashMap<DexEventType, Arc<ParseLatencyHistogram>>> =
+    Lazy::new(DashMap::new);
+
 /// Global singleton instance - zero-cost static allocation
 static GLOBAL_METRICS: HighPerformanceMetrics = HighPerformanceMetrics::new_const();
 
@@ -304,6 +413,11 @@ impl MetricsManager {
 
     /// Initialize and start background task (call once at startup)
     pub fn init(enable_metrics: bool) {
+        Self::init_on(enable_metrics, None);
+    }
+
+    /// Initialize and start background task on the given runtime
+    pub fn init_on(enable_metrics: bool, runtime: Option<&tokio::runtime::Handle>) {
         METRICS_ENABLED.store(enable_metrics, Ordering::Relaxed);
 
         // Start background task only once
@@ -312,7 +426,7 @@ impl MetricsManager {
                 .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
                 .is_ok()
         {
-            tokio::spawn(async {
+            let task = async {
                 let mut interval = tokio::time::interval(std::time::Duration::from_millis(500));
                 loop {
                     interval.tick().await;
@@ -325,7 +439,11 @@ impl MetricsManager {
                     GLOBAL_METRICS
                         .update_window_metrics(EventType::BlockMeta, window_duration_nanos);
                 }
-            });
+            };
+            match runtime {
+                Some(runtime) => runtime.spawn(task),
+                None => tokio::spawn(task),
+            };
         }
     }
 
@@ -408,6 +526,26 @@ impl MetricsManager {
         GLOBAL_METRICS.get_dropped_events_count()
     }
 
+    /// 获取解析缓冲区的最高水位
+    pub fn get_buffer_high_water_mark(&self) -> u64 {
+        GLOBAL_METRICS.get_buffer_high_water_mark()
+    }
+
+    /// 获取解析缓冲区溢出次数
+    pub fn get_buffer_overflow_count(&self) -> u64 {
+        GLOBAL_METRICS.get_buffer_overflow_count()
+    }
+
+    /// 获取因 lamports 低于阈值被丢弃的账户更新数量
+    pub fn get_low_lamports_filtered_count(&self) -> u64 {
+        GLOBAL_METRICS.get_low_lamports_filtered_count()
+    }
+
+    /// 获取订阅重连成功的次数
+    pub fn get_reconnect_count(&self) -> u64 {
+        GLOBAL_METRICS.get_reconnect_count()
+    }
+
     /// 打印性能指标（非阻塞）
     pub fn print_metrics(&self) {
         println!("\n📊 Performance Metrics");
@@ -419,6 +557,21 @@ impl MetricsManager {
             println!("\n⚠️  Dropped Events: {}", dropped_count);
         }
 
+        // 打印解析缓冲区指标
+        let overflow_count = self.get_buffer_overflow_count();
+        println!("   Buffer High-Water Mark: {}", self.get_buffer_high_water_mark());
+        if overflow_count > 0 {
+            println!("⚠️  Buffer Overflows: {}", overflow_count);
+        }
+        let low_lamports_count = self.get_low_lamports_filtered_count();
+        if low_lamports_count > 0 {
+            println!("   Low-Lamports Accounts Filtered: {}", low_lamports_count);
+        }
+        let reconnect_count = self.get_reconnect_count();
+        if reconnect_count > 0 {
+            println!("   Reconnects: {}", reconnect_count);
+        }
+
         // 打印事件指标表格（包含处理时间统计）
         println!("┌─────────────┬──────────────┬──────────────────┬─────────────┬─────────────┐");
         println!("│ Event Type  │ Process Count│ Events Processed │ Last(μs)    │ Avg(μs)     │");
@@ -442,11 +595,19 @@ impl MetricsManager {
 
     /// 启动自动性能监控任务
     pub async fn start_auto_monitoring(&self) -> Option<tokio::task::JoinHandle<()>> {
+        self.start_auto_monitoring_on(None).await
+    }
+
+    /// 在指定 runtime 上启动自动性能监控任务
+    pub async fn start_auto_monitoring_on(
+        &self,
+        runtime: Option<&tokio::runtime::Handle>,
+    ) -> Option<tokio::task::JoinHandle<()>> {
         if !self.is_enabled() {
             return None;
         }
 
-        let handle = tokio::spawn(async {
+        let task = async {
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(
                 DEFAULT_METRICS_PRINT_INTERVAL_SECONDS,
             ));
@@ -454,7 +615,11 @@ impl MetricsManager {
                 interval.tick().await;
                 MetricsManager::global().print_metrics();
             }
-        });
+        };
+        let handle = match runtime {
+            Some(runtime) => runtime.spawn(task),
+            None => tokio::spawn(task),
+        };
         Some(handle)
     }
 
@@ -467,6 +632,10 @@ impl MetricsManager {
             block_meta_metrics: self.get_event_metrics(EventType::BlockMeta),
             processing_stats: self.get_processing_stats(),
             dropped_events_count: self.get_dropped_events_count(),
+            buffer_high_water_mark: self.get_buffer_high_water_mark(),
+            buffer_overflow_count: self.get_buffer_overflow_count(),
+            low_lamports_filtered_count: self.get_low_lamports_filtered_count(),
+            reconnect_count: self.get_reconnect_count(),
         }
     }
 
@@ -551,4 +720,69 @@ impl MetricsManager {
             log::debug!("Dropped events count reached: {}", new_count);
         }
     }
+
+    /// 记录单个事件的解析耗时（按解析出的事件类型）
+    #[inline]
+    pub fn record_parse_latency(&self, event_type: &DexEventType, time_us: f64) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Some(histogram) = PARSE_LATENCY_HISTOGRAMS.get(event_type) {
+            histogram.observe(time_us);
+            return;
+        }
+        self.parse_latency_histogram(event_type).observe(time_us);
+    }
+
+    /// 获取指定事件类型的解析耗时直方图句柄，不存在时创建
+    pub fn parse_latency_histogram(&self, event_type: &DexEventType) -> Arc<ParseLatencyHistogram> {
+        PARSE_LATENCY_HISTOGRAMS
+            .entry(event_type.clone())
+            .or_insert_with(|| Arc::new(ParseLatencyHistogram::new()))
+            .clone()
+    }
+
+    /// 获取所有已记录事件类型的解析耗时直方图句柄
+    pub fn parse_latency_histograms(&self) -> Vec<(DexEventType, Arc<ParseLatencyHistogram>)> {
+        PARSE_LATENCY_HISTOGRAMS
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// 记录解析缓冲区当前深度，更新最高水位
+    #[inline]
+    pub fn record_buffer_depth(&self, depth: usize) {
+        if !self.is_enabled() {
+            return;
+        }
+        GLOBAL_METRICS.buffer_high_water_mark.fetch_max(depth as u64, Ordering::Relaxed);
+    }
+
+    /// 增加解析缓冲区溢出计数（缓冲区已满时调用）
+    #[inline]
+    pub fn increment_buffer_overflow(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+        GLOBAL_METRICS.buffer_overflow_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 增加因 lamports 低于阈值被丢弃的账户更新计数
+    #[inline]
+    pub fn increment_low_lamports_filtered(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+        GLOBAL_METRICS.low_lamports_filtered_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 增加订阅重连成功计数
+    #[inline]
+    pub fn increment_reconnects(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+        GLOBAL_METRICS.reconnect_count.fetch_add(1, Ordering::Relaxed);
+    }
 }
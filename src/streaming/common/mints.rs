@@ -0,0 +1,60 @@
+//! 常见 mint 的符号和精度
+//!
+//! 内置 WSOL 和 USDC，其他 quote 代币可通过 [`register_mint`] 注册，
+//! 用于格式化金额时无需再通过 RPC 查询 mint 账户。
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+
+/// USDC mint 地址
+pub const USDC_MINT: Pubkey = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+/// mint 的符号和精度
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintInfo {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+static MINT_REGISTRY: Lazy<DashMap<Pubkey, MintInfo>> = Lazy::new(|| {
+    let registry = DashMap::new();
+    registry.insert(
+        spl_token::native_mint::ID,
+        MintInfo { symbol: "WSOL".to_string(), decimals: spl_token::native_mint::DECIMALS },
+    );
+    registry.insert(USDC_MINT, MintInfo { symbol: "USDC".to_string(), decimals: 6 });
+    registry
+});
+
+/// 注册或覆盖 `mint` 的符号和精度
+pub fn register_mint(mint: Pubkey, symbol: impl Into<String>, decimals: u8) {
+    MINT_REGISTRY.insert(mint, MintInfo { symbol: symbol.into(), decimals });
+}
+
+/// 查询已知 mint 的符号和精度，未注册的 mint 返回 `None`
+pub fn mint_info(mint: &Pubkey) -> Option<MintInfo> {
+    MINT_REGISTRY.get(mint).map(|info| info.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_mints_are_known() {
+        let wsol = mint_info(&spl_token::native_mint::ID).unwrap();
+        assert_eq!(wsol, MintInfo { symbol: "WSOL".to_string(), decimals: 9 });
+        assert_eq!(mint_info(&USDC_MINT).unwrap().decimals, 6);
+        assert_eq!(mint_info(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn registered_mint_is_returned_and_can_be_overridden() {
+        let mint = Pubkey::new_unique();
+        register_mint(mint, "BONK", 5);
+        assert_eq!(mint_info(&mint), Some(MintInfo { symbol: "BONK".to_string(), decimals: 5 }));
+        register_mint(mint, "BONK2", 6);
+        assert_eq!(mint_info(&mint).unwrap().symbol, "BONK2");
+    }
+}
@@ -1,15 +1,19 @@
 // 公用模块 - 包含流处理相关的通用功能
 pub mod config;
 pub mod metrics;
+pub mod mints;
 pub mod constants;
 pub mod subscription;
 pub mod event_processor;
 pub mod simd_utils;
+pub mod waiters;
 
 // 重新导出主要类型
 pub use config::*;
 pub use metrics::*;
+pub use mints::*;
 pub use constants::*;
 pub use subscription::*;
 pub use event_processor::*;
-pub use simd_utils::*;
\ No newline at end of file
+pub use simd_utils::*;
+pub use waiters::*;
\ No newline at end of file
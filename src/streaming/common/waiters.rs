@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::oneshot;
+
+use crate::streaming::event_parser::DexEvent;
+
+/// 等待指定账户下一次更新的登记表
+///
+/// 没有等待者时 `notify` 只做一次空检查，不会克隆事件
+#[derive(Clone, Default)]
+pub struct PubkeyWaiters {
+    inner: Arc<DashMap<Pubkey, Vec<oneshot::Sender<DexEvent>>>>,
+}
+
+impl PubkeyWaiters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记一个等待者，账户下一次出现在事件中时收到该事件
+    ///
+    /// 登记前清理所有账户下已放弃（receiver 已释放，如等待超时）的等待者，
+    /// 避免从未更新的账户一直占用登记表
+    pub fn register(&self, pubkey: Pubkey) -> oneshot::Receiver<DexEvent> {
+        self.prune();
+        let (tx, rx) = oneshot::channel();
+        self.inner.entry(pubkey).or_default().push(tx);
+        rx
+    }
+
+    /// 移除 receiver 已释放的等待者，以及不再有等待者的账户
+    pub fn prune(&self) {
+        self.inner.retain(|_, senders| {
+            senders.retain(|sender| !sender.is_closed());
+            !senders.is_empty()
+        });
+    }
+
+    /// 将账户事件分发给该账户的所有等待者
+    pub fn notify(&self, event: &DexEvent) {
+        if self.inner.is_empty() {
+            return;
+        }
+        let Some(pubkey) = event.account_pubkey() else {
+            return;
+        };
+        if let Some((_, senders)) = self.inner.remove(&pubkey) {
+            for sender in senders.into_iter().filter(|sender| !sender.is_closed()) {
+                let _ = sender.send(event.clone());
+            }
+        }
+    }
+
+    /// 当前等待中的账户数量
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn account_event(pubkey: Pubkey) -> DexEvent {
+        let account = AccountPrettyBuilder::new().pubkey(pubkey).lamports(0).build();
+        AccountEventParser::parse_account_event(&[], account, None).unwrap()
+    }
+
+    #[test]
+    fn register_prunes_abandoned_waiters_of_other_accounts() {
+        let waiters = PubkeyWaiters::new();
+        drop(waiters.register(Pubkey::new_unique()));
+        assert_eq!(waiters.len(), 1);
+
+        let _rx = waiters.register(Pubkey::new_unique());
+        assert_eq!(waiters.len(), 1);
+    }
+
+    #[test]
+    fn notify_delivers_to_waiters_of_the_account() {
+        let waiters = PubkeyWaiters::new();
+        let pubkey = Pubkey::new_unique();
+        let mut rx = waiters.register(pubkey);
+        let mut other = waiters.register(Pubkey::new_unique());
+
+        waiters.notify(&account_event(pubkey));
+
+        assert_eq!(rx.try_recv().unwrap().account_pubkey(), Some(pubkey));
+        assert!(other.try_recv().is_err());
+        assert_eq!(waiters.len(), 1);
+    }
+}
@@ -0,0 +1,33 @@
+use borsh::BorshDeserialize;
+
+/// 逐字段读取 borsh 数据，可以跳过不需要的字段并在任意位置停止
+///
+/// 用于只关心大账户前几个字段的高频场景，避免解码整个结构体
+#[derive(Debug, Clone, Copy)]
+pub struct BorshReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> BorshReader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    /// 读取下一个字段，数据不足或格式错误时返回 `None`
+    #[inline]
+    pub fn read<T: BorshDeserialize>(&mut self) -> Option<T> {
+        T::deserialize(&mut self.buf).ok()
+    }
+
+    /// 跳过 `len` 字节（定长字段），数据不足时返回 `None`
+    #[inline]
+    pub fn skip(&mut self, len: usize) -> Option<()> {
+        self.buf = self.buf.get(len..)?;
+        Some(())
+    }
+
+    /// 尚未读取的数据
+    pub fn remaining(&self) -> &'a [u8] {
+        self.buf
+    }
+}
@@ -1,5 +1,6 @@
-use crate::streaming::event_parser::common::{
-    types::EventType, ACCOUNT_EVENT_TYPES, BLOCK_EVENT_TYPES,
+use crate::streaming::event_parser::{
+    common::{types::EventType, ACCOUNT_EVENT_TYPES, BLOCK_EVENT_TYPES},
+    protocols::Protocol,
 };
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -22,3 +23,76 @@ impl EventTypeFilter {
         self.include.iter().any(|event| BLOCK_EVENT_TYPES.contains(event))
     }
 }
+
+/// 订阅过滤器，同时按协议和事件类型过滤
+///
+/// 默认不做任何限制；每次 `with_*` 调用追加一个允许的协议或事件类型，
+/// 某一维度为空时表示该维度全部允许
+///
+/// ```ignore
+/// let filter = EventFilter::new()
+///     .with_protocol(Protocol::Bonk)
+///     .with_event_type(EventType::AccountBonkPoolState);
+/// grpc.subscribe_events_immediate(
+///     &filter.protocols(),
+///     ...,
+///     filter.event_type_filter(),
+///     ...,
+/// )
+/// .await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    protocols: Vec<Protocol>,
+    event_types: Vec<EventType>,
+}
+
+impl EventFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 允许指定协议
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        if !self.protocols.contains(&protocol) {
+            self.protocols.push(protocol);
+        }
+        self
+    }
+
+    /// 允许指定事件类型
+    pub fn with_event_type(mut self, event_type: EventType) -> Self {
+        if !self.event_types.contains(&event_type) {
+            self.event_types.push(event_type);
+        }
+        self
+    }
+
+    pub fn allows_protocol(&self, protocol: &Protocol) -> bool {
+        self.protocols.is_empty() || self.protocols.contains(protocol)
+    }
+
+    pub fn allows_event_type(&self, event_type: &EventType) -> bool {
+        self.event_types.is_empty() || self.event_types.contains(event_type)
+    }
+
+    /// 传给订阅接口的协议列表，未指定协议时为全部协议
+    pub fn protocols(&self) -> Vec<Protocol> {
+        if self.protocols.is_empty() {
+            Protocol::ALL.to_vec()
+        } else {
+            self.protocols.clone()
+        }
+    }
+
+    /// 传给订阅接口的事件类型过滤器，未指定事件类型时为 `None`（不过滤）
+    ///
+    /// 账户解析在 borsh 解码前先根据 discriminator 判断事件类型，被排除的账户不会被解码
+    pub fn event_type_filter(&self) -> Option<EventTypeFilter> {
+        if self.event_types.is_empty() {
+            None
+        } else {
+            Some(EventTypeFilter { include: self.event_types.clone() })
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use std::fmt::Debug;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 /// 高性能时钟管理器，减少系统调用开销并最小化延迟
 #[derive(Debug)]
@@ -116,6 +116,64 @@ impl Default for HighPerformanceClock {
 static HIGH_PERF_CLOCK: once_cell::sync::OnceCell<HighPerformanceClock> =
     once_cell::sync::OnceCell::new();
 
+/// 时间来源，通过 `AccountEventStream::with_clock` 替换事件时间戳（`recv_us`、`handle_us`）
+/// 所用的时钟，未设置时使用高性能时钟
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+
+    /// 当前时间的 Unix 时间戳（微秒），早于 Unix 纪元时为负数
+    fn now_micros(&self) -> i64 {
+        system_time_to_micros(self.now())
+    }
+}
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// 手动控制的时钟，时间只在调用 [`MockClock::set`] / [`MockClock::advance`] 时变化
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug)]
+pub struct MockClock {
+    now: parking_lot::Mutex<SystemTime>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    pub fn new(now: SystemTime) -> Self {
+        Self { now: parking_lot::Mutex::new(now) }
+    }
+
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock() = now;
+    }
+
+    pub fn advance(&self, duration: std::time::Duration) {
+        *self.now.lock() += duration;
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock()
+    }
+}
+
+/// `SystemTime` 转换为 Unix 时间戳（微秒），早于 Unix 纪元时为负数
+fn system_time_to_micros(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_micros() as i64,
+        Err(err) => -(err.duration().as_micros() as i64),
+    }
+}
+
 /// 获取全局高性能时钟实例（最简单的实现）
 #[inline(always)]
 pub fn get_high_perf_clock() -> i64 {
@@ -1,6 +1,11 @@
+pub mod borsh_reader;
 pub mod filter;
 pub mod high_performance_clock;
+pub mod reserve;
 pub mod types;
 pub mod utils;
+pub use crate::decode::DecodeError;
+pub use borsh_reader::BorshReader;
+pub use reserve::ReserveSnapshot;
 pub use types::*;
 pub use utils::*;
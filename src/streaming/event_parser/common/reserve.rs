@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// 与协议无关的池子储备快照
+///
+/// 各协议的池子状态转换为同一结构后，可以统一进行跨协议的流动性汇总和比价。
+/// 储备为参与定价的有效储备（如 Bonk 曲线为虚拟储备加实际储备），单位为最小单位
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReserveSnapshot {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    /// 每个 base token 对应的 quote token 数量（已按精度换算），无法计算时为 `None`
+    pub price: Option<f64>,
+}
@@ -2,7 +2,10 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use crossbeam_queue::ArrayQueue;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
-use std::{borrow::Cow, fmt, str::FromStr, sync::Arc};
+use yellowstone_grpc_proto::geyser::CommitmentLevel;
+use std::sync::atomic::{self, AtomicU64};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{borrow::Cow, cmp::Ordering, fmt, str::FromStr, sync::Arc};
 
 use crate::streaming::{common::SimdUtils, event_parser::DexEvent};
 
@@ -133,14 +136,21 @@ pub enum EventType {
     AccountRaydiumClmmTickArrayState,
     AccountRaydiumCpmmAmmConfig,
     AccountRaydiumCpmmPoolState,
+    AccountRaydiumLaunchpadPoolState,
 
     NonceAccount,
     TokenAccount,
+    AccountClosed,
+    UnknownAccount,
 
     // Common events
     BlockMeta,
     SetComputeUnitLimit,
     SetComputeUnitPrice,
+    Heartbeat,
+    BonkTradingLive,
+    BonkGlobalConfigChanged,
+    BonkImpliedTrade,
     Unknown,
 }
 
@@ -159,11 +169,215 @@ pub const ACCOUNT_EVENT_TYPES: &[EventType] = &[
     EventType::AccountRaydiumClmmTickArrayState,
     EventType::AccountRaydiumCpmmAmmConfig,
     EventType::AccountRaydiumCpmmPoolState,
+    EventType::AccountRaydiumLaunchpadPoolState,
     EventType::TokenAccount,
     EventType::NonceAccount,
+    EventType::AccountClosed,
+    EventType::UnknownAccount,
 ];
 pub const BLOCK_EVENT_TYPES: &[EventType] = &[EventType::BlockMeta];
 
+/// 全部事件类型，按声明顺序排列
+const ALL_EVENT_TYPES: &[EventType] = &[
+    EventType::PumpSwapBuy,
+    EventType::PumpSwapSell,
+    EventType::PumpSwapCreatePool,
+    EventType::PumpSwapDeposit,
+    EventType::PumpSwapWithdraw,
+    EventType::PumpFunCreateToken,
+    EventType::PumpFunCreateV2Token,
+    EventType::PumpFunBuy,
+    EventType::PumpFunSell,
+    EventType::PumpFunMigrate,
+    EventType::BonkBuyExactIn,
+    EventType::BonkBuyExactOut,
+    EventType::BonkSellExactIn,
+    EventType::BonkSellExactOut,
+    EventType::BonkInitialize,
+    EventType::BonkInitializeV2,
+    EventType::BonkInitializeWithToken2022,
+    EventType::BonkMigrateToAmm,
+    EventType::BonkMigrateToCpswap,
+    EventType::RaydiumCpmmSwapBaseInput,
+    EventType::RaydiumCpmmSwapBaseOutput,
+    EventType::RaydiumCpmmDeposit,
+    EventType::RaydiumCpmmInitialize,
+    EventType::RaydiumCpmmWithdraw,
+    EventType::RaydiumClmmSwap,
+    EventType::RaydiumClmmSwapV2,
+    EventType::RaydiumClmmClosePosition,
+    EventType::RaydiumClmmIncreaseLiquidityV2,
+    EventType::RaydiumClmmDecreaseLiquidityV2,
+    EventType::RaydiumClmmCreatePool,
+    EventType::RaydiumClmmOpenPositionWithToken22Nft,
+    EventType::RaydiumClmmOpenPositionV2,
+    EventType::RaydiumAmmV4SwapBaseIn,
+    EventType::RaydiumAmmV4SwapBaseOut,
+    EventType::RaydiumAmmV4Deposit,
+    EventType::RaydiumAmmV4Initialize2,
+    EventType::RaydiumAmmV4Withdraw,
+    EventType::RaydiumAmmV4WithdrawPnl,
+    EventType::MeteoraDammV2Swap,
+    EventType::MeteoraDammV2Swap2,
+    EventType::MeteoraDammV2InitializePool,
+    EventType::MeteoraDammV2InitializeCustomizablePool,
+    EventType::MeteoraDammV2InitializePoolWithDynamicConfig,
+    EventType::AccountRaydiumAmmV4AmmInfo,
+    EventType::AccountPumpSwapGlobalConfig,
+    EventType::AccountPumpSwapPool,
+    EventType::AccountBonkPoolState,
+    EventType::AccountBonkGlobalConfig,
+    EventType::AccountBonkPlatformConfig,
+    EventType::AccountBonkVestingRecord,
+    EventType::AccountPumpFunBondingCurve,
+    EventType::AccountPumpFunGlobal,
+    EventType::AccountRaydiumClmmAmmConfig,
+    EventType::AccountRaydiumClmmPoolState,
+    EventType::AccountRaydiumClmmTickArrayState,
+    EventType::AccountRaydiumCpmmAmmConfig,
+    EventType::AccountRaydiumCpmmPoolState,
+    EventType::AccountRaydiumLaunchpadPoolState,
+    EventType::NonceAccount,
+    EventType::TokenAccount,
+    EventType::AccountClosed,
+    EventType::UnknownAccount,
+    EventType::BlockMeta,
+    EventType::SetComputeUnitLimit,
+    EventType::SetComputeUnitPrice,
+    EventType::Heartbeat,
+    EventType::BonkTradingLive,
+    EventType::BonkGlobalConfigChanged,
+    EventType::BonkImpliedTrade,
+    EventType::Unknown,
+];
+
+impl EventType {
+    /// 全部事件类型，按声明顺序排列，可用于列出 CLI 或配置文件中可选的取值
+    pub fn all() -> &'static [EventType] {
+        ALL_EVENT_TYPES
+    }
+
+    /// 账户事件类型对应的 8 字节 Anchor discriminator，可用于构造 offset 0 的 memcmp 过滤
+    ///
+    /// 直接取自各协议 `discriminators` 模块中解码时使用的常量。非账户事件，以及没有 8 字节
+    /// discriminator 的账户类型（Token、Nonce 等通用账户，Raydium AMM V4）返回 `None`。
+    /// Anchor discriminator 只由账户结构体名决定，不同程序的同名账户（如各协议的
+    /// `PoolState`）取值相同，订阅时需同时按 owner 过滤
+    pub fn discriminator(&self) -> Option<[u8; 8]> {
+        use crate::streaming::event_parser::protocols::{
+            bonk, pumpfun, pumpswap, raydium_amm_v4, raydium_clmm, raydium_cpmm,
+        };
+
+        let discriminator: &[u8] = match self {
+            EventType::AccountBonkPoolState | EventType::AccountRaydiumLaunchpadPoolState => {
+                bonk::discriminators::POOL_STATE_ACCOUNT
+            }
+            EventType::AccountBonkGlobalConfig => bonk::discriminators::GLOBAL_CONFIG_ACCOUNT,
+            EventType::AccountBonkPlatformConfig => bonk::discriminators::PLATFORM_CONFIG_ACCOUNT,
+            EventType::AccountPumpFunBondingCurve => pumpfun::discriminators::BONDING_CURVE_ACCOUNT,
+            EventType::AccountPumpFunGlobal => pumpfun::discriminators::GLOBAL_ACCOUNT,
+            EventType::AccountPumpSwapGlobalConfig => {
+                pumpswap::discriminators::GLOBAL_CONFIG_ACCOUNT
+            }
+            EventType::AccountPumpSwapPool => pumpswap::discriminators::POOL_ACCOUNT,
+            EventType::AccountRaydiumAmmV4AmmInfo => raydium_amm_v4::discriminators::AMM_INFO,
+            EventType::AccountRaydiumClmmAmmConfig => raydium_clmm::discriminators::AMM_CONFIG,
+            EventType::AccountRaydiumClmmPoolState => raydium_clmm::discriminators::POOL_STATE,
+            EventType::AccountRaydiumClmmTickArrayState => {
+                raydium_clmm::discriminators::TICK_ARRAY_STATE
+            }
+            EventType::AccountRaydiumCpmmAmmConfig => raydium_cpmm::discriminators::AMM_CONFIG,
+            EventType::AccountRaydiumCpmmPoolState => raydium_cpmm::discriminators::POOL_STATE,
+            _ => return None,
+        };
+        discriminator.try_into().ok()
+    }
+
+    /// 稳定的 snake_case 名称，用作指标标签（如 `bonk_pool_state`）
+    ///
+    /// 与 `Display`/`Debug` 输出不同，该名称在版本之间保持不变，可控制指标基数
+    pub fn metric_label(&self) -> &'static str {
+        match self {
+            EventType::PumpSwapBuy => "pumpswap_buy",
+            EventType::PumpSwapSell => "pumpswap_sell",
+            EventType::PumpSwapCreatePool => "pumpswap_create_pool",
+            EventType::PumpSwapDeposit => "pumpswap_deposit",
+            EventType::PumpSwapWithdraw => "pumpswap_withdraw",
+            EventType::PumpFunCreateToken => "pumpfun_create_token",
+            EventType::PumpFunCreateV2Token => "pumpfun_create_v2_token",
+            EventType::PumpFunBuy => "pumpfun_buy",
+            EventType::PumpFunSell => "pumpfun_sell",
+            EventType::PumpFunMigrate => "pumpfun_migrate",
+            EventType::BonkBuyExactIn => "bonk_buy_exact_in",
+            EventType::BonkBuyExactOut => "bonk_buy_exact_out",
+            EventType::BonkSellExactIn => "bonk_sell_exact_in",
+            EventType::BonkSellExactOut => "bonk_sell_exact_out",
+            EventType::BonkInitialize => "bonk_initialize",
+            EventType::BonkInitializeV2 => "bonk_initialize_v2",
+            EventType::BonkInitializeWithToken2022 => "bonk_initialize_with_token2022",
+            EventType::BonkMigrateToAmm => "bonk_migrate_to_amm",
+            EventType::BonkMigrateToCpswap => "bonk_migrate_to_cpswap",
+            EventType::RaydiumCpmmSwapBaseInput => "raydium_cpmm_swap_base_input",
+            EventType::RaydiumCpmmSwapBaseOutput => "raydium_cpmm_swap_base_output",
+            EventType::RaydiumCpmmDeposit => "raydium_cpmm_deposit",
+            EventType::RaydiumCpmmInitialize => "raydium_cpmm_initialize",
+            EventType::RaydiumCpmmWithdraw => "raydium_cpmm_withdraw",
+            EventType::RaydiumClmmSwap => "raydium_clmm_swap",
+            EventType::RaydiumClmmSwapV2 => "raydium_clmm_swap_v2",
+            EventType::RaydiumClmmClosePosition => "raydium_clmm_close_position",
+            EventType::RaydiumClmmIncreaseLiquidityV2 => "raydium_clmm_increase_liquidity_v2",
+            EventType::RaydiumClmmDecreaseLiquidityV2 => "raydium_clmm_decrease_liquidity_v2",
+            EventType::RaydiumClmmCreatePool => "raydium_clmm_create_pool",
+            EventType::RaydiumClmmOpenPositionWithToken22Nft => {
+                "raydium_clmm_open_position_with_token22_nft"
+            }
+            EventType::RaydiumClmmOpenPositionV2 => "raydium_clmm_open_position_v2",
+            EventType::RaydiumAmmV4SwapBaseIn => "raydium_amm_v4_swap_base_in",
+            EventType::RaydiumAmmV4SwapBaseOut => "raydium_amm_v4_swap_base_out",
+            EventType::RaydiumAmmV4Deposit => "raydium_amm_v4_deposit",
+            EventType::RaydiumAmmV4Initialize2 => "raydium_amm_v4_initialize2",
+            EventType::RaydiumAmmV4Withdraw => "raydium_amm_v4_withdraw",
+            EventType::RaydiumAmmV4WithdrawPnl => "raydium_amm_v4_withdraw_pnl",
+            EventType::MeteoraDammV2Swap => "meteora_damm_v2_swap",
+            EventType::MeteoraDammV2Swap2 => "meteora_damm_v2_swap2",
+            EventType::MeteoraDammV2InitializePool => "meteora_damm_v2_initialize_pool",
+            EventType::MeteoraDammV2InitializeCustomizablePool => {
+                "meteora_damm_v2_initialize_customizable_pool"
+            }
+            EventType::MeteoraDammV2InitializePoolWithDynamicConfig => {
+                "meteora_damm_v2_initialize_pool_with_dynamic_config"
+            }
+            EventType::AccountRaydiumAmmV4AmmInfo => "raydium_amm_v4_amm_info",
+            EventType::AccountPumpSwapGlobalConfig => "pumpswap_global_config",
+            EventType::AccountPumpSwapPool => "pumpswap_pool",
+            EventType::AccountBonkPoolState => "bonk_pool_state",
+            EventType::AccountBonkGlobalConfig => "bonk_global_config",
+            EventType::AccountBonkPlatformConfig => "bonk_platform_config",
+            EventType::AccountBonkVestingRecord => "bonk_vesting_record",
+            EventType::AccountPumpFunBondingCurve => "pumpfun_bonding_curve",
+            EventType::AccountPumpFunGlobal => "pumpfun_global",
+            EventType::AccountRaydiumClmmAmmConfig => "raydium_clmm_amm_config",
+            EventType::AccountRaydiumClmmPoolState => "raydium_clmm_pool_state",
+            EventType::AccountRaydiumClmmTickArrayState => "raydium_clmm_tick_array_state",
+            EventType::AccountRaydiumCpmmAmmConfig => "raydium_cpmm_amm_config",
+            EventType::AccountRaydiumCpmmPoolState => "raydium_cpmm_pool_state",
+            EventType::AccountRaydiumLaunchpadPoolState => "raydium_launchpad_pool_state",
+            EventType::NonceAccount => "nonce_account",
+            EventType::TokenAccount => "token_account",
+            EventType::AccountClosed => "account_closed",
+            EventType::UnknownAccount => "unknown_account",
+            EventType::BlockMeta => "block_meta",
+            EventType::SetComputeUnitLimit => "set_compute_unit_limit",
+            EventType::SetComputeUnitPrice => "set_compute_unit_price",
+            EventType::Heartbeat => "heartbeat",
+            EventType::BonkTradingLive => "bonk_trading_live",
+            EventType::BonkGlobalConfigChanged => "bonk_global_config_changed",
+            EventType::BonkImpliedTrade => "bonk_implied_trade",
+            EventType::Unknown => "unknown",
+        }
+    }
+}
+
 impl fmt::Display for EventType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -232,16 +446,45 @@ impl fmt::Display for EventType {
             }
             EventType::AccountRaydiumCpmmAmmConfig => write!(f, "AccountRaydiumCpmmAmmConfig"),
             EventType::AccountRaydiumCpmmPoolState => write!(f, "AccountRaydiumCpmmPoolState"),
+            EventType::AccountRaydiumLaunchpadPoolState => {
+                write!(f, "AccountRaydiumLaunchpadPoolState")
+            }
             EventType::TokenAccount => write!(f, "TokenAccount"),
             EventType::NonceAccount => write!(f, "NonceAccount"),
+            EventType::AccountClosed => write!(f, "AccountClosed"),
+            EventType::UnknownAccount => write!(f, "UnknownAccount"),
             EventType::BlockMeta => write!(f, "BlockMeta"),
             EventType::SetComputeUnitLimit => write!(f, "SetComputeUnitLimit"),
             EventType::SetComputeUnitPrice => write!(f, "SetComputeUnitPrice"),
+            EventType::Heartbeat => write!(f, "Heartbeat"),
+            EventType::BonkTradingLive => write!(f, "BonkTradingLive"),
+            EventType::BonkGlobalConfigChanged => write!(f, "BonkGlobalConfigChanged"),
+            EventType::BonkImpliedTrade => write!(f, "BonkImpliedTrade"),
             EventType::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// 从字符串解析事件类型，用于 CLI 参数和配置文件
+///
+/// 接受 [`EventType::metric_label`] 的 snake_case 名称（如 `bonk_pool_state`）、对应的
+/// kebab-case 写法（`bonk-pool-state`）以及 `Display` 输出（`AccountBonkPoolState`），
+/// 因此 `to_string()` 和 `metric_label()` 的结果都能解析回原值
+impl FromStr for EventType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label = s.trim().replace('-', "_");
+        EventType::all()
+            .iter()
+            .find(|event_type| {
+                event_type.metric_label() == label || event_type.to_string() == label
+            })
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Unsupported event type: {}", s))
+    }
+}
+
 /// Parse result
 #[derive(Debug, Clone)]
 pub struct ParseResult<T> {
@@ -296,6 +539,45 @@ pub struct SwapData {
     pub description: Option<Cow<'static, str>>,
 }
 
+/// 订阅的确认级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Commitment {
+    /// 节点已处理，延迟最低，可能被回滚
+    Processed,
+    /// 已被超级多数确认
+    #[default]
+    Confirmed,
+    /// 已最终确认，不会被回滚
+    Finalized,
+}
+
+impl From<Commitment> for CommitmentLevel {
+    fn from(commitment: Commitment) -> Self {
+        match commitment {
+            Commitment::Processed => CommitmentLevel::Processed,
+            Commitment::Confirmed => CommitmentLevel::Confirmed,
+            Commitment::Finalized => CommitmentLevel::Finalized,
+        }
+    }
+}
+
+impl From<CommitmentLevel> for Commitment {
+    fn from(level: CommitmentLevel) -> Self {
+        match level {
+            CommitmentLevel::Processed => Commitment::Processed,
+            CommitmentLevel::Confirmed => Commitment::Confirmed,
+            CommitmentLevel::Finalized => Commitment::Finalized,
+        }
+    }
+}
+
+impl Commitment {
+    /// 从订阅请求中的 `commitment` 字段转换，未设置或取值未知时返回 `None`
+    pub fn from_request(commitment: Option<i32>) -> Option<Commitment> {
+        commitment.and_then(|level| CommitmentLevel::try_from(level).ok()).map(Commitment::from)
+    }
+}
+
 /// Event metadata
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EventMetadata {
@@ -312,9 +594,90 @@ pub struct EventMetadata {
     pub swap_data: Option<SwapData>,
     pub outer_index: i64,
     pub inner_index: Option<i64>,
+    /// 解析时分配的全局递增序号，用于同一链上位置的事件之间的排序
+    #[serde(default)]
+    pub seq: u64,
+    /// 收到事件的订阅所使用的确认级别，来源不确定时（如回放、shred 流）为 `None`
+    #[serde(default)]
+    pub commitment: Option<Commitment>,
+    /// 产生该事件的原始账户数据，仅在事件流开启 `with_raw_data` 时保留，交易事件始终为 `None`。
+    /// 克隆事件时共享同一份数据，不参与序列化
+    #[serde(skip)]
+    pub raw_data: Option<Arc<[u8]>>,
+    /// 账户更新的写入版本号，仅来自 gRPC 订阅的账户事件有值，交易事件和快照为 `None`
+    #[serde(default)]
+    pub write_version: Option<u64>,
+}
+
+/// 可持久化的处理进度，重启后通过 `ReconnectingSubscription::resume_from` 从该位置继续订阅
+///
+/// 派生的排序按 `slot`、`write_version` 的顺序比较，`None` 小于任何版本号
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Checkpoint {
+    pub slot: u64,
+    pub write_version: Option<u64>,
+}
+
+impl Checkpoint {
+    /// 该位置是否在 `checkpoint` 之后，即对应的事件尚未处理
+    ///
+    /// 只比较 slot：与 `checkpoint` 同一 slot 的事件视为未处理（可能重复投递，但不会遗漏）
+    pub fn is_after(&self, checkpoint: &Checkpoint) -> bool {
+        self.slot >= checkpoint.slot
+    }
+
+    /// 与 [`Self::is_after`] 相同，但 slot 相同且两者都有写入版本号时按写入版本号比较
+    ///
+    /// 写入版本号是单个节点内的计数器，只有恢复时连接的仍是记录进度时的同一个节点（且未重启）
+    /// 才有意义，否则会丢弃尚未处理的事件
+    pub fn is_after_with_write_version(&self, checkpoint: &Checkpoint) -> bool {
+        match self.slot.cmp(&checkpoint.slot) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => match (self.write_version, checkpoint.write_version) {
+                (Some(version), Some(processed)) => version > processed,
+                _ => true,
+            },
+        }
+    }
+}
+
+/// 下一个事件序号
+static EVENT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// 分配一个全局递增的事件序号，由解析器在创建 `EventMetadata` 时调用
+#[inline]
+pub fn next_event_seq() -> u64 {
+    EVENT_SEQ.fetch_add(1, atomic::Ordering::Relaxed)
+}
+
+/// 事件的链上位置：slot、交易在 slot 中的索引、指令位置，最后按 `seq` 区分
+///
+/// 按字段顺序比较，用于按链上顺序排序，例如
+/// `events.sort_by_key(|e| e.metadata().chain_position())`；也可以作为 `BinaryHeap` 的键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChainPosition {
+    pub slot: u64,
+    pub transaction_index: Option<u64>,
+    pub outer_index: i64,
+    pub inner_index: Option<i64>,
+    pub seq: u64,
 }
 
 impl EventMetadata {
+    /// 事件的链上位置，见 [`ChainPosition`]
+    pub fn chain_position(&self) -> ChainPosition {
+        ChainPosition {
+            slot: self.slot,
+            transaction_index: self.transaction_index,
+            outer_index: self.outer_index,
+            inner_index: self.inner_index,
+            seq: self.seq,
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         signature: Signature,
@@ -343,9 +706,38 @@ impl EventMetadata {
             outer_index,
             inner_index,
             transaction_index,
+            seq: next_event_seq(),
+            commitment: None,
+            raw_data: None,
+            write_version: None,
         }
     }
 
+    /// 该事件对应的处理进度
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { slot: self.slot, write_version: self.write_version }
+    }
+
+    /// 本地收到事件的时刻，由 `recv_us`（UTC 微秒）换算
+    pub fn received_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_micros(self.recv_us.max(0) as u64)
+    }
+
+    /// 链上区块时间，由 `block_time_ms` 换算，未知时（如账户事件）返回 `None`
+    pub fn block_timestamp(&self) -> Option<SystemTime> {
+        if self.block_time_ms <= 0 {
+            return None;
+        }
+        Some(UNIX_EPOCH + Duration::from_millis(self.block_time_ms as u64))
+    }
+
+    /// 从出块到本地收到事件的延迟（`received_at - block_timestamp`）
+    ///
+    /// 区块时间未知，或本地时钟早于区块时间时返回 `None`
+    pub fn processing_latency(&self) -> Option<Duration> {
+        self.received_at().duration_since(self.block_timestamp()?).ok()
+    }
+
     pub fn set_swap_data(&mut self, swap_data: SwapData) {
         self.swap_data = Some(swap_data);
     }
@@ -703,3 +1095,137 @@ pub fn parse_swap_data_from_next_grpc_instructions(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffled_events_sort_into_chain_order() {
+        let metadata = |slot, transaction_index, outer_index, inner_index, seq| EventMetadata {
+            slot,
+            transaction_index,
+            outer_index,
+            inner_index,
+            seq,
+            ..Default::default()
+        };
+        let ordered = vec![
+            metadata(10, None, 0, None, 9),
+            metadata(10, Some(0), 0, None, 3),
+            metadata(10, Some(0), 1, None, 1),
+            metadata(10, Some(0), 1, Some(0), 2),
+            metadata(10, Some(2), 0, None, 0),
+            metadata(11, Some(0), 0, None, 4),
+        ];
+        let mut shuffled = ordered.clone();
+        shuffled.reverse();
+        shuffled.swap(0, 3);
+
+        shuffled.sort_by_key(EventMetadata::chain_position);
+        assert_eq!(shuffled, ordered);
+    }
+
+    #[test]
+    fn every_event_type_round_trips_through_from_str() {
+        for event_type in EventType::all() {
+            let label = event_type.metric_label();
+            for text in [event_type.to_string(), label.to_owned(), label.replace('_', "-")] {
+                assert_eq!(&text.parse::<EventType>().unwrap(), event_type, "{text}");
+            }
+        }
+        // 名称互不相同，解析结果不会落到别的类型上
+        let mut labels: Vec<_> = EventType::all().iter().map(EventType::metric_label).collect();
+        labels.sort();
+        labels.dedup();
+        assert_eq!(labels.len(), EventType::all().len());
+    }
+
+    #[test]
+    fn unknown_event_type_string_is_an_error() {
+        for text in ["bonk_pool", "", "AccountBonkPoolState2"] {
+            let error = text.parse::<EventType>().unwrap_err();
+            assert!(error.to_string().contains("Unsupported event type"), "{error}");
+        }
+    }
+
+    #[test]
+    fn account_discriminators_round_trip_through_the_registry() {
+        use crate::streaming::event_parser::core::dispatcher::EventDispatcher;
+        use crate::streaming::event_parser::Protocol;
+
+        let mut found = 0;
+        for event_type in ACCOUNT_EVENT_TYPES {
+            let Some(discriminator) = event_type.discriminator() else { continue };
+            found += 1;
+            // Raydium Launchpad 即 Bonk 程序，同一个 PoolState 由 Bonk 解析器识别
+            let expected = match event_type {
+                EventType::AccountRaydiumLaunchpadPoolState => EventType::AccountBonkPoolState,
+                other => other.clone(),
+            };
+            let identified = Protocol::ALL.into_iter().any(|protocol| {
+                EventDispatcher::identify_account(protocol, &discriminator)
+                    == Some(expected.clone())
+            });
+            assert!(identified, "{event_type:?} {discriminator:?}");
+        }
+        assert_eq!(found, 13);
+
+        for event_type in [
+            EventType::BonkBuyExactIn,
+            EventType::PumpSwapBuy,
+            EventType::BlockMeta,
+            EventType::TokenAccount,
+            EventType::AccountRaydiumAmmV4AmmInfo,
+        ] {
+            assert_eq!(event_type.discriminator(), None, "{event_type:?}");
+        }
+    }
+
+    #[test]
+    fn processing_latency_is_receive_time_minus_block_time() {
+        let block_time_ms = 1_700_000_000_000;
+        let metadata = EventMetadata {
+            block_time_ms,
+            recv_us: block_time_ms * 1_000 + 350_250,
+            ..Default::default()
+        };
+        assert_eq!(
+            metadata.block_timestamp(),
+            Some(UNIX_EPOCH + Duration::from_millis(block_time_ms as u64))
+        );
+        assert_eq!(
+            metadata.received_at(),
+            UNIX_EPOCH + Duration::from_micros(1_700_000_000_350_250)
+        );
+        assert_eq!(metadata.processing_latency(), Some(Duration::from_micros(350_250)));
+
+        // 区块时间未知（账户事件），或本地时钟落后于区块时间
+        let account = EventMetadata { block_time_ms: 0, ..metadata.clone() };
+        assert_eq!((account.block_timestamp(), account.processing_latency()), (None, None));
+        let skewed = EventMetadata { recv_us: (block_time_ms - 5) * 1_000, ..metadata };
+        assert_eq!(skewed.processing_latency(), None);
+    }
+
+    fn checkpoint(slot: u64, write_version: Option<u64>) -> Checkpoint {
+        Checkpoint { slot, write_version }
+    }
+
+    #[test]
+    fn equal_slot_events_are_unprocessed_by_default() {
+        let resume = checkpoint(100, Some(50));
+        assert!(checkpoint(100, Some(10)).is_after(&resume));
+        assert!(checkpoint(101, Some(1)).is_after(&resume));
+        assert!(!checkpoint(99, Some(90)).is_after(&resume));
+    }
+
+    #[test]
+    fn write_version_is_compared_only_when_opted_in() {
+        let resume = checkpoint(100, Some(50));
+        assert!(!checkpoint(100, Some(50)).is_after_with_write_version(&resume));
+        assert!(!checkpoint(100, Some(10)).is_after_with_write_version(&resume));
+        assert!(checkpoint(100, Some(51)).is_after_with_write_version(&resume));
+        assert!(checkpoint(100, None).is_after_with_write_version(&resume));
+        assert!(!checkpoint(99, Some(90)).is_after_with_write_version(&resume));
+    }
+}
@@ -1,5 +1,14 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use borsh::BorshDeserialize;
+
+use crate::streaming::event_parser::common::DecodeError;
+
+pub use crate::decode::{
+    decode_account_body, read_u32_le, try_decode_account_body, verify_discriminator,
+    DiscriminatorScheme,
+};
+
 /// 获取当前时间戳
 pub fn current_timestamp() -> i64 {
     SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_secs() as i64
@@ -13,6 +22,34 @@ pub fn extract_discriminator(length: usize, data: &[u8]) -> Option<(&[u8], &[u8]
     Some((&data[..length], &data[length..]))
 }
 
+/// 按前缀格式提取账户数据主体，返回长度为 `size` 的切片
+///
+/// `Len4` 格式下还会校验长度前缀不小于 `size`
+pub fn extract_account_body(
+    scheme: DiscriminatorScheme,
+    data: &[u8],
+    size: usize,
+) -> Option<&[u8]> {
+    if scheme == DiscriminatorScheme::Len4 && (read_u32_le(data, 0)? as usize) < size {
+        return None;
+    }
+    let start = scheme.prefix_len();
+    data.get(start..start.checked_add(size)?)
+}
+
+/// 去掉 8 字节 Anchor discriminator 后按 borsh 解码账户，见 [`decode_account_body`]
+pub fn decode_anchor_account<T: BorshDeserialize>(data: &[u8], size: usize) -> Option<T> {
+    decode_account_body(DiscriminatorScheme::Anchor8, data, size)
+}
+
+/// 与 [`decode_anchor_account`] 相同，失败时返回具体原因
+pub fn try_decode_anchor_account<T: BorshDeserialize>(
+    data: &[u8],
+    size: usize,
+) -> Result<T, DecodeError> {
+    try_decode_account_body(DiscriminatorScheme::Anchor8, data, size)
+}
+
 /// 从日志中提取程序数据
 pub fn extract_program_data(log: &str) -> Option<&str> {
     const PROGRAM_DATA_PREFIX: &str = "Program data: ";
@@ -26,34 +63,31 @@ pub fn extract_program_log<'a>(log: &'a str, prefix: &str) -> Option<&'a str> {
 
 /// 安全地从字节数组中读取u64
 pub fn read_u64_le(data: &[u8], offset: usize) -> Option<u64> {
-    if data.len() < offset + 8 {
-        return None;
-    }
-    let bytes: [u8; 8] = data[offset..offset + 8].try_into().ok()?;
+    let bytes: [u8; 8] = data.get(offset..offset.checked_add(8)?)?.try_into().ok()?;
     Some(u64::from_le_bytes(bytes))
 }
 
+/// 计算 `value * numerator / denominator`，中间结果使用 u128 避免溢出
+///
+/// 分母为 0 或结果超出 u64 时返回 `None`
+pub fn checked_mul_div(value: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    let result =
+        (value as u128).checked_mul(numerator as u128)?.checked_div(denominator as u128)?;
+    u64::try_from(result).ok()
+}
+
 pub fn read_i32_le(data: &[u8], offset: usize) -> Option<i32> {
-    if data.len() < offset + 4 {
-        return None;
-    }
-    let bytes: [u8; 4] = data[offset..offset + 4].try_into().ok()?;
+    let bytes: [u8; 4] = data.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
     Some(i32::from_le_bytes(bytes))
 }
 
 pub fn read_u128_le(data: &[u8], offset: usize) -> Option<u128> {
-    if data.len() < offset + 16 {
-        return None;
-    }
-    let bytes: [u8; 16] = data[offset..offset + 16].try_into().ok()?;
+    let bytes: [u8; 16] = data.get(offset..offset.checked_add(16)?)?.try_into().ok()?;
     Some(u128::from_le_bytes(bytes))
 }
 
 pub fn read_u8_le(data: &[u8], offset: usize) -> Option<u8> {
-    if data.len() < offset + 1 {
-        return None;
-    }
-    let bytes: [u8; 1] = data[offset..offset + 1].try_into().ok()?;
+    let bytes: [u8; 1] = data.get(offset..offset.checked_add(1)?)?.try_into().ok()?;
     Some(u8::from_le_bytes(bytes))
 }
 
@@ -71,21 +105,9 @@ pub fn read_option_bool(data: &[u8], offset: &mut usize) -> Option<Option<bool>>
     Some(Some(value != 0))
 }
 
-/// 安全地从字节数组中读取u32
-pub fn read_u32_le(data: &[u8], offset: usize) -> Option<u32> {
-    if data.len() < offset + 4 {
-        return None;
-    }
-    let bytes: [u8; 4] = data[offset..offset + 4].try_into().ok()?;
-    Some(u32::from_le_bytes(bytes))
-}
-
 /// 安全地从字节数组中读取u16
 pub fn read_u16_le(data: &[u8], offset: usize) -> Option<u16> {
-    if data.len() < offset + 2 {
-        return None;
-    }
-    let bytes: [u8; 2] = data[offset..offset + 2].try_into().ok()?;
+    let bytes: [u8; 2] = data.get(offset..offset.checked_add(2)?)?.try_into().ok()?;
     Some(u16::from_le_bytes(bytes))
 }
 
@@ -108,3 +130,54 @@ pub fn format_pubkey_short(pubkey: &solana_sdk::pubkey::Pubkey) -> String {
         format!("{}...{}", &s[..4], &s[s.len() - 4..])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, BorshDeserialize)]
+    struct Body {
+        amount: u64,
+        index: u32,
+    }
+
+    const BODY_SIZE: usize = 8 + 4;
+
+    fn body_bytes() -> Vec<u8> {
+        [7u64.to_le_bytes().as_slice(), 9u32.to_le_bytes().as_slice()].concat()
+    }
+
+    #[test]
+    fn checked_mul_div_uses_a_wide_intermediate() {
+        assert_eq!(checked_mul_div(u64::MAX, u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(checked_mul_div(u64::MAX, 3, 4), Some(u64::MAX / 4 * 3 + 2));
+        assert_eq!(checked_mul_div(u64::MAX, 2, 1), None);
+        assert_eq!(checked_mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    fn body_is_decoded_after_each_scheme_prefix() {
+        let body = body_bytes();
+        let cases = [
+            (DiscriminatorScheme::Anchor8, [[0xAA; 8].as_slice(), &body].concat()),
+            (DiscriminatorScheme::Len4, [&(BODY_SIZE as u32).to_le_bytes(), &body[..]].concat()),
+            (DiscriminatorScheme::None, body.clone()),
+        ];
+        for (scheme, data) in cases {
+            assert_eq!(extract_account_body(scheme, &data, BODY_SIZE), Some(body.as_slice()));
+            let decoded = decode_account_body::<Body>(scheme, &data, BODY_SIZE);
+            assert_eq!(decoded, Some(Body { amount: 7, index: 9 }), "{scheme:?}");
+        }
+    }
+
+    #[test]
+    fn len4_prefix_shorter_than_the_body_is_rejected() {
+        let data = [3u32.to_le_bytes().as_slice(), &body_bytes()].concat();
+        assert_eq!(extract_account_body(DiscriminatorScheme::Len4, &data, BODY_SIZE), None);
+        let error = try_decode_account_body::<Body>(DiscriminatorScheme::Len4, &data, BODY_SIZE);
+        assert!(
+            matches!(error, Err(DecodeError::TooShort { expected: BODY_SIZE, actual: 3 })),
+            "{error:?}"
+        );
+    }
+}
@@ -1,7 +1,14 @@
 use crate::streaming::event_parser::common::filter::EventTypeFilter;
 use crate::streaming::event_parser::common::high_performance_clock::elapsed_micros_since;
-use crate::streaming::event_parser::common::{EventMetadata, EventType, ProtocolType};
+use crate::streaming::event_parser::common::{
+    next_event_seq, EventMetadata, EventType, ProtocolType,
+};
+use crate::streaming::event_parser::core::coverage::CoverageReport;
+use crate::streaming::event_parser::core::decode_cache::AccountDecodeCache;
+use crate::streaming::event_parser::core::parser_metrics::ParserMetrics;
+use crate::streaming::event_parser::core::parser_registry::ParserRegistry;
 use crate::streaming::event_parser::core::traits::DexEvent;
+use crate::streaming::event_parser::protocols::ProtocolSet;
 use crate::streaming::event_parser::Protocol;
 use crate::streaming::grpc::AccountPretty;
 use serde::{Deserialize, Serialize};
@@ -13,6 +20,7 @@ use spl_token_2022::{
     extension::StateWithExtensions,
     state::{Account as Account2022, Mint as Mint2022},
 };
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// 通用账户事件
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -53,24 +61,250 @@ pub struct TokenInfoEvent {
     pub decimals: u8,
 }
 
+/// 账户已关闭事件：lamports 为 0（数据通常已清空）
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountClosedEvent {
+    pub metadata: EventMetadata,
+    pub pubkey: Pubkey,
+    /// 关闭后的 owner（通常已被重新分配给 System Program）
+    pub owner: Pubkey,
+}
+
+/// 未被任何解析器识别的非空账户
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnknownAccountEvent {
+    pub metadata: EventMetadata,
+    pub pubkey: Pubkey,
+    pub executable: bool,
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub rent_epoch: u64,
+    /// 账户数据长度
+    pub data_len: usize,
+    /// 账户数据的前 8 字节（不足 8 字节时为 `None`），通常为 Anchor discriminator
+    pub discriminator: Option<[u8; 8]>,
+}
+
+/// 只根据 owner 和 discriminator 判断账户会命中的协议解析器，不解码账户数据
+///
+/// 用于构建订阅过滤器和覆盖率分析。只识别协议账户（不包括 Token、Nonce 等通用账户），
+/// 不检查账户长度，也不考虑协议是否在运行时被禁用
+pub fn identify(account: &AccountPretty) -> Option<(Protocol, EventType)> {
+    use crate::streaming::event_parser::core::dispatcher::EventDispatcher;
+
+    let discriminator = account.data.get(..8)?;
+    let protocol = EventDispatcher::match_protocol_by_program_id(&account.owner)?;
+    let event_type = EventDispatcher::identify_account(protocol.clone(), discriminator)?;
+    Some((protocol, event_type))
+}
+
+/// 解码 base64 编码的账户数据（RPC 返回的格式），返回本 crate 会将其解析成的事件
+///
+/// 用于调试和命令行工具：直接粘贴浏览器或 RPC 中的账户数据，无需手动构建 `AccountPretty`。
+/// 同时接受标准字母表和 URL 安全字母表，考虑所有协议，解码失败或无法识别时返回 `None`
+///
+/// ```ignore
+/// let event = decode_account_str(data_b64, &BONK_PROGRAM_ID, &pool)?;
+/// println!("{event:?}");
+/// ```
+pub fn decode_account_str(data_b64: &str, owner: &Pubkey, pubkey: &Pubkey) -> Option<DexEvent> {
+    // lamports 非 0，以免账户被当作已关闭
+    let account = AccountPretty::from_base64_data(*pubkey, *owner, data_b64, 1, 0)?;
+    AccountEventParser::parse_account_event_in(ProtocolSet::all(), account, None)
+}
+
 pub struct AccountEventParser {}
 
+/// 是否跳过可执行账户，默认关闭
+static REJECT_EXECUTABLE: AtomicBool = AtomicBool::new(false);
+
 impl AccountEventParser {
+    /// 开启后直接跳过可执行（程序）账户，不再解析
+    ///
+    /// 协议的数据账户（池子、配置等）不可能是可执行账户，开启后可排除这类异常数据
+    pub fn set_reject_executable(enabled: bool) {
+        REJECT_EXECUTABLE.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn rejects_executable() -> bool {
+        REJECT_EXECUTABLE.load(Ordering::Relaxed)
+    }
+
     pub fn parse_account_event(
         protocols: &[Protocol],
         account: AccountPretty,
         event_type_filter: Option<&EventTypeFilter>,
     ) -> Option<DexEvent> {
+        Self::parse_account_event_in(ProtocolSet::from(protocols), account, event_type_filter)
+    }
+
+    /// 与 [`Self::parse_account_event`] 相同，只考虑 `protocols` 中的协议
+    ///
+    /// 不在集合中的协议的账户不会进入该协议的解析器，例如 `ProtocolSet::only(Protocol::Bonk)`
+    /// 只解码 Bonk 账户。Token、Nonce 等通用账户不属于任何协议，不受影响
+    pub fn parse_account_event_in(
+        protocols: ProtocolSet,
+        account: AccountPretty,
+        event_type_filter: Option<&EventTypeFilter>,
+    ) -> Option<DexEvent> {
+        let cache = AccountDecodeCache::global();
+        Self::parse_with(ParserRegistry::global(), protocols, account, event_type_filter, cache)
+    }
+
+    /// 与 [`Self::parse_account_event`] 相同，使用单个事件流的 `cache` 代替全局缓存
+    pub fn parse_account_event_cached(
+        protocols: &[Protocol],
+        account: AccountPretty,
+        event_type_filter: Option<&EventTypeFilter>,
+        cache: &AccountDecodeCache,
+    ) -> Option<DexEvent> {
+        let protocols = ProtocolSet::from(protocols);
+        Self::parse_with(ParserRegistry::global(), protocols, account, event_type_filter, cache)
+    }
+
+    /// 与 [`Self::parse_account_event_cached`] 相同，使用 `registry` 代替全局注册表：
+    /// 协议的启用状态和自定义账户解析器只取自该实例，见 `AccountEventStream::with_parser_registry`
+    pub fn parse_account_event_with_registry(
+        registry: &ParserRegistry,
+        protocols: &[Protocol],
+        account: AccountPretty,
+        event_type_filter: Option<&EventTypeFilter>,
+        cache: &AccountDecodeCache,
+    ) -> Option<DexEvent> {
+        let protocols = ProtocolSet::from(protocols);
+        Self::parse_with(registry, protocols, account, event_type_filter, cache)
+    }
+
+    /// `cache` 开启时先查询缓存，账户内容未变化时直接复用上一次的解码结果
+    fn parse_with(
+        registry: &ParserRegistry,
+        protocols: ProtocolSet,
+        account: AccountPretty,
+        event_type_filter: Option<&EventTypeFilter>,
+        cache: &AccountDecodeCache,
+    ) -> Option<DexEvent> {
+        if account.executable && Self::rejects_executable() {
+            return None;
+        }
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "parse_account",
+            event_type = tracing::field::Empty,
+            pubkey = %account.pubkey,
+            slot = account.slot,
+            data_len = account.data.len(),
+        )
+        .entered();
+
+        let owner = account.owner;
+        let cache_hash = cache.is_enabled().then(|| AccountDecodeCache::content_hash(&account));
+        if let Some(hash) = cache_hash {
+            if let Some(event) = cache.get(&account, hash) {
+                // 缓存结果可能来自不同的协议列表或过滤器，不满足本次条件时重新解析
+                if Self::accepts(registry, protocols, event_type_filter, &event) {
+                    cache.record_hit();
+                    CoverageReport::global().record(&owner, true);
+                    #[cfg(feature = "tracing")]
+                    span.record("event_type", event.event_type().metric_label());
+                    return Some(event);
+                }
+            }
+            cache.record_miss();
+        }
+
+        let pubkey = account.pubkey;
+        let (event, matched) =
+            Self::parse_account_event_inner(registry, protocols, account, event_type_filter);
+        CoverageReport::global().record(&owner, matched);
+        #[cfg(feature = "tracing")]
+        if let Some(event) = &event {
+            span.record("event_type", event.event_type().metric_label());
+        }
+        if let Some(hash) = cache_hash {
+            match &event {
+                Some(event) => cache.insert(pubkey, hash, event),
+                None => cache.remove(&pubkey),
+            }
+        }
+        event
+    }
+
+    /// 缓存的事件是否满足本次请求的协议列表和事件类型过滤
+    fn accepts(
+        registry: &ParserRegistry,
+        protocols: ProtocolSet,
+        event_type_filter: Option<&EventTypeFilter>,
+        event: &DexEvent,
+    ) -> bool {
+        let metadata = event.metadata();
+        if let Some(filter) = event_type_filter {
+            if !filter.include.contains(&metadata.event_type) {
+                return false;
+            }
+        }
+        match Protocol::from_protocol_type(&metadata.protocol) {
+            Some(protocol) => protocols.contains(&protocol) && registry.is_enabled(&protocol),
+            None => true,
+        }
+    }
+
+    /// 解析账户事件，同时返回是否有解析器识别了该账户（不受事件类型过滤影响）
+    fn parse_account_event_inner(
+        registry: &ParserRegistry,
+        protocols: ProtocolSet,
+        account: AccountPretty,
+        event_type_filter: Option<&EventTypeFilter>,
+    ) -> (Option<DexEvent>, bool) {
         use crate::streaming::event_parser::core::dispatcher::EventDispatcher;
 
+        let mut matched = false;
+
+        // 0. lamports 为 0 的账户在交易结束时即被回收，即使残留数据（旧版 Anchor 关闭账户时
+        // 只写入关闭标记）也视为已关闭，不再按残留数据解析
+        if account.lamports == 0 {
+            let event = Self::parse_unrecognized_account(&account, Self::common_metadata(&account))
+                .filter(|event| {
+                    event_type_filter
+                        .is_none_or(|filter| filter.include.contains(&event.metadata().event_type))
+                });
+            return (event, true);
+        }
+
         // 1. 尝试从账户 discriminator 解析（协议特定账户）
         if account.data.len() >= 8 {
             let discriminator = &account.data[0..8];
 
             // 尝试识别协议类型
             if let Some(protocol) = EventDispatcher::match_protocol_by_program_id(&account.owner) {
+                // 协议在运行时被禁用：不再回退到 Token/Nonce 解析，按未知账户输出
+                // 该 owner 有对应的解析器，覆盖率统计中不计为未知
+                if !registry.is_enabled(&protocol) {
+                    let event =
+                        Self::parse_unrecognized_account(&account, Self::common_metadata(&account))
+                            .filter(|event| {
+                                event_type_filter.is_none_or(|filter| {
+                                    filter.include.contains(&event.metadata().event_type)
+                                })
+                            });
+                    return (event, true);
+                }
                 // 检查是否在请求的协议列表中
                 if protocols.contains(&protocol) {
+                    let metrics = ParserMetrics::global();
+                    let event_type = if event_type_filter.is_some() || metrics.is_enabled() {
+                        EventDispatcher::identify_account(protocol.clone(), discriminator)
+                    } else {
+                        None
+                    };
+                    // discriminator 对应的事件类型被过滤器排除时跳过解码
+                    // 该账户仍有对应的解析器，覆盖率统计中不计为未知
+                    if let (Some(filter), Some(event_type)) = (event_type_filter, &event_type) {
+                        if !filter.include.contains(event_type) {
+                            return (None, true);
+                        }
+                    }
                     // 构建临时元数据（protocol会被dispatcher设置，event_type会在parser中设置）
                     let metadata = EventMetadata {
                         slot: account.slot,
@@ -80,24 +314,32 @@ impl AccountEventParser {
                         program_id: account.owner,
                         recv_us: account.recv_us,
                         handle_us: elapsed_micros_since(account.recv_us),
+                        seq: next_event_seq(),
+                        write_version: Self::write_version(&account),
                         ..Default::default()
                     };
 
                     // 使用 dispatcher 解析
-                    if let Some(event) = EventDispatcher::dispatch_account(
+                    let event = EventDispatcher::dispatch_account_with(
+                        registry,
                         protocol,
                         discriminator,
                         &account,
                         metadata,
-                    ) {
+                    );
+                    if let Some(event_type) = &event_type {
+                        metrics.record_attempt(event_type, event.is_some());
+                    }
+                    if let Some(event) = event {
+                        matched = true;
                         // 应用事件类型过滤
                         if let Some(filter) = event_type_filter {
                             if filter.include.contains(&event.metadata().event_type) {
-                                return Some(event);
+                                return (Some(event), true);
                             }
                             // 不匹配过滤器，继续尝试其他解析方式
                         } else {
-                            return Some(event);
+                            return (Some(event), true);
                         }
                     }
                 }
@@ -106,40 +348,116 @@ impl AccountEventParser {
 
         // 2. 尝试解析特殊账户类型（Token、Nonce等）
         // 这些是通用的，不属于特定协议
-        let metadata = EventMetadata {
-            slot: account.slot,
-            signature: account.signature,
-            protocol: ProtocolType::Common,
-            event_type: EventType::default(),
-            program_id: account.owner,
-            recv_us: account.recv_us,
-            handle_us: elapsed_micros_since(account.recv_us),
-            ..Default::default()
-        };
+        let metadata = Self::common_metadata(&account);
+
+        // 尝试下游通过 ParserRegistry 注册的账户解析器（owner 不属于内置协议）
+        if EventDispatcher::match_protocol_by_program_id(&account.owner).is_none() {
+            if let Some(event) = registry.parse(&account, metadata.clone()) {
+                matched = true;
+                if event_type_filter
+                    .is_none_or(|filter| filter.include.contains(&event.metadata().event_type))
+                {
+                    return (Some(event), true);
+                }
+            }
+        }
 
         // 尝试解析 Nonce 账户
         if let Some(event) = Self::parse_nonce_account_event(&account, metadata.clone()) {
+            matched = true;
             if let Some(filter) = event_type_filter {
                 if filter.include.contains(&event.metadata().event_type) {
-                    return Some(event);
+                    return (Some(event), true);
                 }
             } else {
-                return Some(event);
+                return (Some(event), true);
             }
         }
 
         // 尝试解析 Token 账户
-        if let Some(event) = Self::parse_token_account_event(&account, metadata) {
+        // 无法按 Token 账户解码的非空数据不输出 amount 为空的 TokenAccountEvent，按未知账户处理；
+        // 数据为空的账户（如普通钱包）仍输出 TokenAccountEvent
+        let token_event =
+            Self::parse_token_account_event(&account, metadata.clone()).filter(|event| {
+                account.data.is_empty()
+                    || !matches!(event, DexEvent::TokenAccountEvent(e) if e.amount.is_none())
+            });
+        if let Some(event) = token_event {
+            matched = true;
             if let Some(filter) = event_type_filter {
                 if filter.include.contains(&event.metadata().event_type) {
-                    return Some(event);
+                    return (Some(event), true);
                 }
             } else {
-                return Some(event);
+                return (Some(event), true);
             }
         }
 
-        None
+        // 3. 没有解析器识别：区分已关闭账户与未知账户
+        if !matched {
+            if let Some(event) = Self::parse_unrecognized_account(&account, metadata) {
+                let closed = matches!(event, DexEvent::AccountClosedEvent(_));
+                if event_type_filter
+                    .is_none_or(|filter| filter.include.contains(&event.metadata().event_type))
+                {
+                    return (Some(event), closed);
+                }
+                return (None, closed);
+            }
+        }
+
+        (None, matched)
+    }
+
+    /// 不属于特定协议的账户事件使用的元数据
+    fn common_metadata(account: &AccountPretty) -> EventMetadata {
+        EventMetadata {
+            slot: account.slot,
+            signature: account.signature,
+            protocol: ProtocolType::Common,
+            event_type: EventType::default(),
+            program_id: account.owner,
+            recv_us: account.recv_us,
+            handle_us: elapsed_micros_since(account.recv_us),
+            seq: next_event_seq(),
+            write_version: Self::write_version(account),
+            ..Default::default()
+        }
+    }
+
+    /// 账户更新的写入版本号，快照等来源没有版本号（为 0）时返回 `None`
+    pub(crate) fn write_version(account: &AccountPretty) -> Option<u64> {
+        (account.write_version != 0).then_some(account.write_version)
+    }
+
+    /// 未被识别的账户：lamports 为 0 时为已关闭账户（无论数据是否已清空），数据非空时为未知账户，
+    /// 数据为空但仍有 lamports（如普通钱包）时返回 `None`
+    pub fn parse_unrecognized_account(
+        account: &AccountPretty,
+        mut metadata: EventMetadata,
+    ) -> Option<DexEvent> {
+        if account.lamports == 0 {
+            metadata.event_type = EventType::AccountClosed;
+            return Some(DexEvent::AccountClosedEvent(AccountClosedEvent {
+                metadata,
+                pubkey: account.pubkey,
+                owner: account.owner,
+            }));
+        }
+        if account.data.is_empty() {
+            return None;
+        }
+        metadata.event_type = EventType::UnknownAccount;
+        Some(DexEvent::UnknownAccountEvent(UnknownAccountEvent {
+            metadata,
+            pubkey: account.pubkey,
+            executable: account.executable,
+            lamports: account.lamports,
+            owner: account.owner,
+            rent_epoch: account.rent_epoch,
+            data_len: account.data.len(),
+            discriminator: account.data.get(..8).and_then(|bytes| bytes.try_into().ok()),
+        }))
     }
 
     pub fn parse_token_account_event(
@@ -240,3 +558,331 @@ impl AccountEventParser {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    #[cfg(feature = "tracing")]
+    use std::collections::HashMap;
+    use std::sync::Once;
+    #[cfg(feature = "tracing")]
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::streaming::event_parser::common::filter::EventFilter;
+    use crate::streaming::event_parser::core::parser_registry::AccountParser;
+    use crate::streaming::event_parser::core::traits::{AccountEvent, RentStatus};
+    use crate::streaming::event_parser::protocols::bonk::parser::BonkPoolStateParser;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::event_parser::protocols::raydium_amm_v4::parser as raydium_amm_v4;
+    use crate::streaming::grpc::{AccountPrettyBuilder, DEFAULT_TEST_LAMPORTS};
+
+    #[test]
+    fn empty_account_with_lamports_is_token_account() {
+        let account = AccountPrettyBuilder::new().build();
+        let event = AccountEventParser::parse_account_event(&[], account, None);
+        let Some(DexEvent::TokenAccountEvent(event)) = event else {
+            panic!("expected a token account event, got {event:?}");
+        };
+        assert_eq!(event.amount, None);
+        assert_eq!(event.lamports, DEFAULT_TEST_LAMPORTS);
+    }
+
+    #[test]
+    fn zero_lamport_account_is_closed_even_with_leftover_data() {
+        let pubkey = Pubkey::new_unique();
+        for data in [vec![], vec![0xFF; 16]] {
+            let account = AccountPrettyBuilder::new().pubkey(pubkey).lamports(0).data(data).build();
+            let event = AccountEventParser::parse_account_event(&[], account, None);
+            let Some(DexEvent::AccountClosedEvent(event)) = event else {
+                panic!("expected an account closed event, got {event:?}");
+            };
+            assert_eq!(event.pubkey, pubkey);
+            assert_eq!(event.metadata.event_type, EventType::AccountClosed);
+        }
+    }
+
+    #[test]
+    fn garbage_data_is_unknown_account() {
+        let account = AccountPrettyBuilder::new().data(vec![0x5A; 5]).build();
+        let owner = account.owner;
+        let event = AccountEventParser::parse_account_event(&[], account, None);
+        let Some(DexEvent::UnknownAccountEvent(event)) = event else {
+            panic!("expected an unknown account event, got {event:?}");
+        };
+        assert_eq!(event.owner, owner);
+        assert_eq!(event.data_len, 5);
+        assert_eq!(event.discriminator, None);
+    }
+
+    #[test]
+    fn unknown_events_respect_event_type_filter() {
+        let account = AccountPrettyBuilder::new().data(vec![0x5A; 16]).build();
+        let filter = EventTypeFilter { include: vec![EventType::AccountClosed] };
+        let event = AccountEventParser::parse_account_event(&[], account, Some(&filter));
+        assert_eq!(event, None);
+    }
+
+    #[test]
+    fn disabled_protocol_falls_through_to_unknown_account() {
+        let account = AccountPrettyBuilder::new()
+            .owner(raydium_amm_v4::RAYDIUM_AMM_V4_PROGRAM_ID)
+            .discriminator([0xAA; 8])
+            .data(vec![1, 2, 3])
+            .build();
+
+        // 只修改本测试自己的注册表，不影响全局注册表和并行的其他测试
+        let registry = ParserRegistry::new();
+        registry.set_enabled(Protocol::RaydiumAmmV4, false);
+        let protocols = ProtocolSet::all();
+        let (event, matched) =
+            AccountEventParser::parse_account_event_inner(&registry, protocols, account, None);
+        assert!(ParserRegistry::global().is_enabled(&Protocol::RaydiumAmmV4));
+
+        let Some(DexEvent::UnknownAccountEvent(event)) = event else {
+            panic!("expected an unknown account event, got {event:?}");
+        };
+        assert!(matched);
+        assert_eq!(event.owner, raydium_amm_v4::RAYDIUM_AMM_V4_PROGRAM_ID);
+        assert_eq!(event.data_len, 11);
+        assert_eq!(event.discriminator, Some([0xAA; 8]));
+    }
+
+    thread_local! {
+        /// 当前测试线程中 Bonk `PoolState` 解析器被调用的次数
+        static POOL_STATE_DECODES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// 委托给内置解析器并计数：解析结果不变，计数按线程隔离，不影响并行的其他测试
+    struct CountingPoolStateParser;
+
+    impl AccountParser for CountingPoolStateParser {
+        fn discriminator(&self) -> [u8; 8] {
+            POOL_STATE_DISCRIMINATOR
+        }
+
+        fn program_id(&self) -> Pubkey {
+            BONK_PROGRAM_ID
+        }
+
+        fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+            POOL_STATE_DECODES.with(|decodes| decodes.set(decodes.get() + 1));
+            BonkPoolStateParser::default().parse(account, metadata)
+        }
+    }
+
+    /// 执行 `parse`，返回其结果以及期间 `PoolState` 解析器被调用的次数
+    fn count_pool_state_decodes(
+        parse: impl FnOnce() -> Option<DexEvent>,
+    ) -> (Option<DexEvent>, usize) {
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| {
+            ParserRegistry::global().register_account_parser(CountingPoolStateParser)
+        });
+        POOL_STATE_DECODES.with(|decodes| decodes.set(0));
+        let event = parse();
+        (event, POOL_STATE_DECODES.with(Cell::get))
+    }
+
+    fn bonk_pool_account() -> AccountPretty {
+        AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(&PoolState::default())
+            .build()
+    }
+
+    #[test]
+    fn excluded_protocol_decoder_is_never_entered() {
+        let (event, decodes) = count_pool_state_decodes(|| {
+            let protocols = ProtocolSet::only(Protocol::PumpFun);
+            AccountEventParser::parse_account_event_in(protocols, bonk_pool_account(), None)
+        });
+        assert_eq!(decodes, 0);
+        assert!(!matches!(event, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{event:?}");
+
+        let (event, decodes) = count_pool_state_decodes(|| {
+            let protocols = ProtocolSet::only(Protocol::Bonk);
+            AccountEventParser::parse_account_event_in(protocols, bonk_pool_account(), None)
+        });
+        assert_eq!(decodes, 1);
+        assert!(matches!(event, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{event:?}");
+    }
+
+    #[test]
+    fn filtered_out_account_type_is_skipped_before_decode() {
+        let filter = EventFilter::new()
+            .with_protocol(Protocol::Bonk)
+            .with_event_type(EventType::AccountBonkGlobalConfig);
+        let (event, decodes) = count_pool_state_decodes(|| {
+            AccountEventParser::parse_account_event(
+                &filter.protocols(),
+                bonk_pool_account(),
+                filter.event_type_filter().as_ref(),
+            )
+        });
+        assert_eq!(event, None);
+        assert_eq!(decodes, 0);
+
+        let filter = filter.with_event_type(EventType::AccountBonkPoolState);
+        let (event, decodes) = count_pool_state_decodes(|| {
+            AccountEventParser::parse_account_event(
+                &filter.protocols(),
+                bonk_pool_account(),
+                filter.event_type_filter().as_ref(),
+            )
+        });
+        assert!(matches!(event, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{event:?}");
+        assert_eq!(decodes, 1);
+    }
+
+    #[test]
+    fn default_event_filter_allows_everything() {
+        let filter = EventFilter::default();
+        assert_eq!(filter.protocols(), Protocol::ALL.to_vec());
+        assert!(filter.event_type_filter().is_none());
+        assert!(filter.allows_protocol(&Protocol::PumpFun));
+        assert!(filter.allows_event_type(&EventType::AccountBonkPoolState));
+
+        let (event, decodes) = count_pool_state_decodes(|| {
+            AccountEventParser::parse_account_event(&filter.protocols(), bonk_pool_account(), None)
+        });
+        assert!(matches!(event, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{event:?}");
+        assert_eq!(decodes, 1);
+    }
+
+    #[test]
+    fn decode_account_str_accepts_both_base64_alphabets() {
+        use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+        use base64::Engine;
+
+        // 0xFF 字节使两种字母表的编码结果不同（`/` 与 `_`）
+        let pool_state =
+            PoolState { supply: u64::MAX, virtual_base: u64::MAX, ..Default::default() };
+        let account = AccountPrettyBuilder::new()
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(&pool_state)
+            .build();
+        let standard = STANDARD.encode(&account.data);
+        let url_safe = URL_SAFE.encode(&account.data);
+        assert_ne!(standard, url_safe);
+
+        let pubkey = Pubkey::new_unique();
+        for data_b64 in [standard.as_str(), url_safe.as_str(), &format!(" {standard}\n")] {
+            let event = decode_account_str(data_b64, &BONK_PROGRAM_ID, &pubkey);
+            let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+                panic!("expected a pool state event, got {event:?}");
+            };
+            assert_eq!(event.base.pubkey, pubkey);
+            assert_eq!(event.pool_state, pool_state);
+        }
+
+        assert_eq!(decode_account_str("not base64!", &BONK_PROGRAM_ID, &pubkey), None);
+    }
+
+    #[test]
+    fn executable_accounts_are_skipped_only_when_rejection_is_on() {
+        let executable = || {
+            let mut account = bonk_pool_account();
+            account.executable = true;
+            account.rent_epoch = u64::MAX;
+            account
+        };
+        let event = AccountEventParser::parse_account_event(&[Protocol::Bonk], executable(), None);
+        let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+            panic!("executable account should be parsed by default: {event:?}");
+        };
+        assert!(event.executable());
+        assert_eq!(event.rent_status(), RentStatus::RentExempt);
+
+        AccountEventParser::set_reject_executable(true);
+        let rejected =
+            AccountEventParser::parse_account_event(&[Protocol::Bonk], executable(), None);
+        let accepted =
+            AccountEventParser::parse_account_event(&[Protocol::Bonk], bonk_pool_account(), None);
+        AccountEventParser::set_reject_executable(false);
+        assert_eq!(rejected, None);
+        assert!(matches!(accepted, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{accepted:?}");
+    }
+
+    #[test]
+    fn rent_epoch_sentinel_maps_to_rent_exempt() {
+        assert_eq!(RentStatus::from_rent_epoch(u64::MAX), RentStatus::RentExempt);
+        assert_eq!(RentStatus::from_rent_epoch(0), RentStatus::Epoch(0));
+        assert_eq!(RentStatus::from_rent_epoch(u64::MAX - 1), RentStatus::Epoch(u64::MAX - 1));
+    }
+
+    /// 记录每个 span 名称和字段值的最小 subscriber
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: Arc<Mutex<Vec<(String, HashMap<String, String>)>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_owned(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_owned(), format!("{value:?}"));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanCapture {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut fields = HashMap::new();
+            span.record(&mut FieldVisitor(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata().name().to_owned(), fields));
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut FieldVisitor(&mut spans[span.into_u64() as usize - 1].1));
+        }
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn parse_span_carries_the_account_fields_and_event_type() {
+        let pubkey = Pubkey::new_unique();
+        let mut account = bonk_pool_account();
+        account.pubkey = pubkey;
+        account.slot = 321;
+        let data_len = account.data.len();
+
+        let capture = SpanCapture::default();
+        let event = tracing::subscriber::with_default(capture.clone(), || {
+            AccountEventParser::parse_account_event(&[Protocol::Bonk], account, None)
+        });
+        assert!(matches!(event, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{event:?}");
+
+        let spans = capture.spans.lock().unwrap();
+        let (_, fields) = spans.iter().find(|(name, _)| name == "parse_account").unwrap();
+        assert_eq!(fields["pubkey"], pubkey.to_string());
+        assert_eq!(fields["slot"], "321");
+        assert_eq!(fields["data_len"], data_len.to_string());
+        assert_eq!(fields["event_type"], "bonk_pool_state");
+    }
+}
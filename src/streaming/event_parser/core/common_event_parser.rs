@@ -1,5 +1,7 @@
-use crate::streaming::event_parser::common::high_performance_clock::elapsed_micros_since;
-use crate::streaming::event_parser::common::types::{EventType, ProtocolType};
+use crate::streaming::event_parser::common::high_performance_clock::{
+    elapsed_micros_since, get_high_perf_clock,
+};
+use crate::streaming::event_parser::common::types::{next_event_seq, EventType, ProtocolType};
 use crate::streaming::event_parser::common::EventMetadata;
 use crate::streaming::event_parser::core::traits::DexEvent;
 use crate::streaming::event_parser::protocols::block::block_meta_event::BlockMetaEvent;
@@ -29,6 +31,90 @@ pub struct SetComputeUnitPriceEvent {
     pub micro_lamports: u64,
 }
 
+/// 空闲心跳事件，在一段时间内没有真实事件时由 `idle_heartbeat` 适配器生成
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct HeartbeatEvent {
+    #[borsh(skip)]
+    pub metadata: EventMetadata,
+    /// 最近一次已知的 slot
+    pub slot: u64,
+}
+
+impl HeartbeatEvent {
+    pub fn new(slot: u64) -> Self {
+        let metadata = EventMetadata {
+            slot,
+            protocol: ProtocolType::Common,
+            event_type: EventType::Heartbeat,
+            recv_us: get_high_perf_clock(),
+            seq: next_event_seq(),
+            ..Default::default()
+        };
+        Self { metadata, slot }
+    }
+}
+
+/// 未设置 SetComputeUnitLimit 时每条指令的默认计算单元上限
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+/// 单笔交易的计算单元上限
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// 交易顶层 Compute Budget 指令设置的计算单元价格与上限
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeBudgetInfo {
+    /// SetComputeUnitPrice 设置的价格（micro-lamports / CU）
+    pub unit_price: Option<u64>,
+    /// SetComputeUnitLimit 设置的上限
+    pub unit_limit: Option<u32>,
+    /// 非 Compute Budget 的顶层指令数量，用于推算默认上限
+    pub other_instruction_count: u32,
+}
+
+impl ComputeBudgetInfo {
+    /// 从交易的顶层指令（program id 与指令数据）中提取，同一指令出现多次时以最后一次为准
+    pub fn from_instructions<'a>(
+        instructions: impl IntoIterator<Item = (&'a Pubkey, &'a [u8])>,
+    ) -> Self {
+        let mut info = Self::default();
+        for (program_id, data) in instructions {
+            if program_id != &COMPUTE_BUDGET_PROGRAM_ID {
+                info.other_instruction_count = info.other_instruction_count.saturating_add(1);
+                continue;
+            }
+            match data.first() {
+                Some(2) => {
+                    if let Some(bytes) = data.get(1..5) {
+                        info.unit_limit = bytes.try_into().ok().map(u32::from_le_bytes);
+                    }
+                }
+                Some(3) => {
+                    if let Some(bytes) = data.get(1..9) {
+                        info.unit_price = bytes.try_into().ok().map(u64::from_le_bytes);
+                    }
+                }
+                _ => {}
+            }
+        }
+        info
+    }
+
+    /// 实际生效的计算单元上限
+    ///
+    /// 未设置时按每条非 Compute Budget 指令 200k 推算，结果不超过 1.4M
+    pub fn effective_unit_limit(&self) -> u32 {
+        self.unit_limit
+            .unwrap_or_else(|| {
+                self.other_instruction_count.saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+            })
+            .min(MAX_COMPUTE_UNIT_LIMIT)
+    }
+
+    /// 优先费（micro-lamports），即计算单元价格乘以生效的上限
+    pub fn priority_fee_microlamports(&self) -> u64 {
+        self.unit_price.unwrap_or(0).saturating_mul(self.effective_unit_limit() as u64)
+    }
+}
+
 pub struct CommonEventParser {}
 
 impl CommonEventParser {
@@ -0,0 +1,117 @@
+//! 账户解析覆盖率统计
+//!
+//! 统计一段时间内收到的账户中有多少被解析器识别，未识别的按 owner 分组计数，
+//! 用于发现尚未支持的账户类型。默认关闭，通过 `CoverageReport::global().set_enabled(true)` 开启。
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+
+/// 覆盖率快照
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageSnapshot {
+    /// 处理的账户总数
+    pub total: u64,
+    /// 被解析器识别的账户数（不受事件类型过滤影响）
+    pub matched: u64,
+    /// 未被识别的账户数，按 owner 分组
+    pub unknown_by_owner: HashMap<Pubkey, u64>,
+}
+
+impl CoverageSnapshot {
+    /// 被识别的比例，没有数据时为 0
+    pub fn matched_ratio(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.matched as f64 / self.total as f64
+    }
+
+    /// 按未识别数量降序排列的 owner
+    pub fn top_unknown_owners(&self) -> Vec<(Pubkey, u64)> {
+        let mut owners: Vec<_> = self.unknown_by_owner.iter().map(|(k, v)| (*k, *v)).collect();
+        owners.sort_by_key(|(_, n)| Reverse(*n));
+        owners
+    }
+}
+
+/// 账户解析覆盖率累加器，由 `AccountEventParser::parse_account_event` 更新
+pub struct CoverageReport {
+    enabled: AtomicBool,
+    total: AtomicU64,
+    matched: AtomicU64,
+    unknown_by_owner: DashMap<Pubkey, u64>,
+}
+
+impl CoverageReport {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            total: AtomicU64::new(0),
+            matched: AtomicU64::new(0),
+            unknown_by_owner: DashMap::new(),
+        }
+    }
+
+    /// 获取全局实例
+    pub fn global() -> &'static CoverageReport {
+        &COVERAGE_REPORT
+    }
+
+    /// 开启或关闭统计
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// 记录一个账户的解析结果
+    #[inline]
+    pub fn record(&self, owner: &Pubkey, matched: bool) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.total.fetch_add(1, Ordering::Relaxed);
+        if matched {
+            self.matched.fetch_add(1, Ordering::Relaxed);
+        } else {
+            *self.unknown_by_owner.entry(*owner).or_insert(0) += 1;
+        }
+    }
+
+    /// 获取当前统计快照
+    pub fn snapshot(&self) -> CoverageSnapshot {
+        CoverageSnapshot {
+            total: self.total.load(Ordering::Relaxed),
+            matched: self.matched.load(Ordering::Relaxed),
+            unknown_by_owner: self
+                .unknown_by_owner
+                .iter()
+                .map(|entry| (*entry.key(), *entry.value()))
+                .collect(),
+        }
+    }
+
+    /// 清空统计，开始新的统计窗口
+    pub fn reset(&self) {
+        self.total.store(0, Ordering::Relaxed);
+        self.matched.store(0, Ordering::Relaxed);
+        self.unknown_by_owner.clear();
+    }
+}
+
+impl Default for CoverageReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global coverage report instance
+static COVERAGE_REPORT: once_cell::sync::Lazy<CoverageReport> =
+    once_cell::sync::Lazy::new(CoverageReport::new);
@@ -0,0 +1,281 @@
+//! 账户解码缓存
+//!
+//! 部分账户会被反复写入完全相同的数据（no-op 写入）。开启后按账户地址记录上一次的内容哈希
+//! 和解码结果，内容未变化时直接复用上一次的解码结果，跳过 borsh 解码。
+//! 全局实例默认关闭，通过 `AccountDecodeCache::global().set_enabled(true)` 开启，不限容量；
+//! 单个事件流的缓存由 `AccountEventStream::with_decode_cache` 开启，容量有限（LRU）。
+
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::common::high_performance_clock::elapsed_micros_since;
+use crate::streaming::event_parser::common::next_event_seq;
+use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+use crate::streaming::event_parser::core::traits::DexEvent;
+use crate::streaming::grpc::AccountPretty;
+
+struct CachedDecode {
+    hash: u64,
+    event: Arc<DexEvent>,
+    /// 最近一次访问时的序号，用于识别淘汰队列中已过期的记录，不限容量时不使用
+    touched: u64,
+}
+
+/// 复用缓存的解码结果时，按本次更新刷新元数据（slot、签名、写入版本号、序号、时间）
+fn refresh_metadata(mut event: DexEvent, account: &AccountPretty) -> DexEvent {
+    let metadata = event.metadata_mut();
+    metadata.slot = account.slot;
+    metadata.signature = account.signature;
+    metadata.write_version = AccountEventParser::write_version(account);
+    metadata.seq = next_event_seq();
+    metadata.recv_us = account.recv_us;
+    metadata.handle_us = elapsed_micros_since(account.recv_us);
+    event
+}
+
+/// 按账户地址缓存最近一次的解码结果，由 `AccountEventParser::parse_account_event` 使用
+///
+/// [`Self::global`] 不限容量；[`Self::with_capacity`] 创建的缓存最多记录 `capacity` 个账户，
+/// 超出时淘汰最久未访问的账户，供单个事件流使用
+pub struct AccountDecodeCache {
+    enabled: AtomicBool,
+    /// 最多缓存的账户数量，`None` 表示不限
+    capacity: Option<usize>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    tick: AtomicU64,
+    entries: DashMap<Pubkey, CachedDecode>,
+    /// 按访问顺序排列的 `(pubkey, touched)`，仅在限制容量时维护；同一账户可能有多条记录，
+    /// 只有最新的一条有效
+    order: Mutex<VecDeque<(Pubkey, u64)>>,
+}
+
+impl AccountDecodeCache {
+    /// 创建不限容量的缓存，默认关闭
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            capacity: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            tick: AtomicU64::new(0),
+            entries: DashMap::new(),
+            order: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 创建最多缓存 `capacity` 个账户（LRU）的缓存，创建后即开启
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            enabled: AtomicBool::new(true),
+            capacity: Some(capacity),
+            entries: DashMap::with_capacity(capacity),
+            order: Mutex::new(VecDeque::with_capacity(capacity)),
+            ..Self::new()
+        }
+    }
+
+    /// 获取全局实例
+    pub fn global() -> &'static AccountDecodeCache {
+        &ACCOUNT_DECODE_CACHE
+    }
+
+    /// 开启或关闭缓存，关闭时清空已缓存的结果
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.entries.clear();
+            self.order.lock().clear();
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// 账户内容哈希，覆盖账户数据以及事件中携带的 owner、lamports 等字段
+    pub fn content_hash(account: &AccountPretty) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&account.data);
+        hasher.write(account.owner.as_ref());
+        hasher.write_u64(account.lamports);
+        hasher.write_u64(account.rent_epoch);
+        hasher.write_u8(account.executable as u8);
+        hasher.finish()
+    }
+
+    /// 内容哈希与上一次相同时返回上一次的解码结果，元数据按本次更新刷新
+    pub fn get(&self, account: &AccountPretty, hash: u64) -> Option<DexEvent> {
+        let mut cached = self.entries.get_mut(&account.pubkey)?;
+        if cached.hash != hash {
+            return None;
+        }
+        let event = DexEvent::clone(&cached.event);
+        if self.capacity.is_some() {
+            let touched = self.tick.fetch_add(1, Ordering::Relaxed) + 1;
+            cached.touched = touched;
+            drop(cached);
+            let mut order = self.order.lock();
+            order.push_back((account.pubkey, touched));
+            self.compact(&mut order);
+        }
+        Some(refresh_metadata(event, account))
+    }
+
+    /// 记录账户本次的解码结果，限制容量时淘汰最久未访问的账户
+    pub fn insert(&self, pubkey: Pubkey, hash: u64, event: &DexEvent) {
+        let touched = self.tick.fetch_add(1, Ordering::Relaxed) + 1;
+        let event = Arc::new(event.clone());
+        self.entries.insert(pubkey, CachedDecode { hash, event, touched });
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        let mut order = self.order.lock();
+        order.push_back((pubkey, touched));
+        while self.entries.len() > capacity {
+            let Some((pubkey, touched)) = order.pop_front() else {
+                break;
+            };
+            self.entries.remove_if(&pubkey, |_, cached| cached.touched == touched);
+        }
+        self.compact(&mut order);
+    }
+
+    /// 移除账户的缓存（本次更新未解码出事件时调用）
+    pub fn remove(&self, pubkey: &Pubkey) {
+        self.entries.remove(pubkey);
+    }
+
+    /// 频繁访问的账户会在队列中留下大量过期记录，超过一定长度时压缩
+    fn compact(&self, order: &mut VecDeque<(Pubkey, u64)>) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        if order.len() > capacity * 2 {
+            order.retain(|(pubkey, touched)| {
+                self.entries.get(pubkey).is_some_and(|cached| cached.touched == *touched)
+            });
+        }
+    }
+
+    /// 记录一次命中（复用了缓存的解码结果）
+    #[inline]
+    pub fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 记录一次未命中（重新解码）
+    #[inline]
+    pub fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 命中缓存、跳过解码的次数
+    pub fn get_hit_count(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// 未命中缓存、重新解码的次数
+    pub fn get_miss_count(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// 命中率（0.0 ~ 1.0），还没有查询时为 0
+    pub fn hit_rate(&self) -> f64 {
+        let hits = self.get_hit_count();
+        let total = hits + self.get_miss_count();
+        if total == 0 {
+            return 0.0;
+        }
+        hits as f64 / total as f64
+    }
+
+    /// 当前缓存的账户数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 清空缓存和命中计数
+    pub fn clear(&self) {
+        self.entries.clear();
+        self.order.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for AccountDecodeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global account decode cache instance
+static ACCOUNT_DECODE_CACHE: once_cell::sync::Lazy<AccountDecodeCache> =
+    once_cell::sync::Lazy::new(AccountDecodeCache::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn parse_cached(account: AccountPretty, cache: &AccountDecodeCache) -> DexEvent {
+        AccountEventParser::parse_account_event_cached(&[], account, None, cache).unwrap()
+    }
+
+    #[test]
+    fn bounded_cache_evicts_least_recently_used_account() {
+        let cache = AccountDecodeCache::with_capacity(2);
+        let accounts: Vec<_> = (0..3).map(|_| AccountPrettyBuilder::new().build()).collect();
+        parse_cached(accounts[0].clone(), &cache);
+        parse_cached(accounts[1].clone(), &cache);
+        // 访问第一个账户后，最久未访问的是第二个账户
+        parse_cached(accounts[0].clone(), &cache);
+        parse_cached(accounts[2].clone(), &cache);
+
+        assert_eq!(cache.len(), 2);
+        let hash = AccountDecodeCache::content_hash(&accounts[1]);
+        assert!(cache.get(&accounts[1], hash).is_none());
+        assert_eq!(cache.get_hit_count(), 1);
+        assert_eq!(cache.get_miss_count(), 3);
+        assert_eq!(cache.hit_rate(), 0.25);
+    }
+
+    #[test]
+    fn hit_refreshes_metadata_and_changed_data_misses() {
+        let cache = AccountDecodeCache::with_capacity(16);
+        let account = AccountPrettyBuilder::new().slot(10).build();
+        parse_cached(account.clone(), &cache);
+
+        let first_seq = parse_cached(account.clone(), &cache).metadata().seq;
+        let event =
+            parse_cached(AccountPretty { slot: 11, write_version: 5, ..account.clone() }, &cache);
+        assert_eq!(event.slot(), 11);
+        assert_eq!(event.metadata().write_version, Some(5));
+        assert!(event.metadata().seq > first_seq);
+        assert_eq!(cache.get_hit_count(), 2);
+
+        parse_cached(AccountPretty { slot: 12, lamports: 7, ..account }, &cache);
+        assert_eq!(cache.get_miss_count(), 2);
+    }
+
+    #[test]
+    fn stream_cache_does_not_fill_the_global_cache() {
+        let cache = AccountDecodeCache::with_capacity(16);
+        parse_cached(AccountPrettyBuilder::new().build(), &cache);
+        assert_eq!(cache.len(), 1);
+        assert!(AccountDecodeCache::global().is_empty());
+    }
+}
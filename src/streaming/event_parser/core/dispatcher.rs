@@ -8,8 +8,9 @@
 //! - **可测试性**: 每个函数都可以独立测试
 
 use crate::streaming::event_parser::{
-    common::EventMetadata,
+    common::{EventMetadata, EventType},
     core::common_event_parser::{CommonEventParser, COMPUTE_BUDGET_PROGRAM_ID},
+    core::parser_registry::ParserRegistry,
     protocols::{
         bonk::parser as bonk, meteora_damm_v2::parser as meteora_damm_v2, pumpfun::parser as pumpfun,
         pumpswap::parser as pumpswap, raydium_amm_v4::parser as raydium_amm_v4,
@@ -44,6 +45,11 @@ impl EventDispatcher {
         accounts: &[Pubkey],
         mut metadata: EventMetadata,
     ) -> Option<DexEvent> {
+        // 跳过运行时被禁用的协议
+        if !ParserRegistry::global().is_enabled(&protocol) {
+            return None;
+        }
+
         // 根据协议类型设置 metadata.protocol
         use crate::streaming::event_parser::common::ProtocolType;
         metadata.protocol = match protocol {
@@ -119,6 +125,11 @@ impl EventDispatcher {
         inner_instruction_data: &[u8],
         mut metadata: EventMetadata,
     ) -> Option<DexEvent> {
+        // 跳过运行时被禁用的协议
+        if !ParserRegistry::global().is_enabled(&protocol) {
+            return None;
+        }
+
         // 根据协议类型设置 metadata.protocol
         use crate::streaming::event_parser::common::ProtocolType;
         metadata.protocol = match protocol {
@@ -233,6 +244,24 @@ impl EventDispatcher {
         protocols.iter().map(|p| Self::get_program_id(p.clone())).collect()
     }
 
+    /// 判断账户 discriminator 会命中的账户解析器，不解码账户数据
+    ///
+    /// 与 [`EventDispatcher::dispatch_account`] 使用相同的 discriminator 匹配规则，
+    /// 但不检查协议是否在运行时被禁用
+    pub fn identify_account(protocol: Protocol, discriminator: &[u8]) -> Option<EventType> {
+        match protocol {
+            Protocol::PumpFun => pumpfun::identify_pumpfun_account(discriminator),
+            Protocol::PumpSwap => pumpswap::identify_pumpswap_account(discriminator),
+            Protocol::Bonk => bonk::identify_bonk_account(discriminator),
+            Protocol::RaydiumCpmm => raydium_cpmm::identify_raydium_cpmm_account(discriminator),
+            Protocol::RaydiumClmm => raydium_clmm::identify_raydium_clmm_account(discriminator),
+            Protocol::RaydiumAmmV4 => {
+                raydium_amm_v4::identify_raydium_amm_v4_account(discriminator)
+            }
+            Protocol::MeteoraDammV2 => None,
+        }
+    }
+
     /// 解析账户数据
     ///
     /// 根据账户的 discriminator 路由到对应协议的账户解析函数
@@ -246,11 +275,29 @@ impl EventDispatcher {
     /// # 返回
     /// 解析成功返回 `Some(DexEvent)`，否则返回 `None`
     pub fn dispatch_account(
+        protocol: Protocol,
+        discriminator: &[u8],
+        account: &crate::streaming::grpc::AccountPretty,
+        metadata: crate::streaming::event_parser::common::EventMetadata,
+    ) -> Option<DexEvent> {
+        let registry = ParserRegistry::global();
+        Self::dispatch_account_with(registry, protocol, discriminator, account, metadata)
+    }
+
+    /// 与 [`EventDispatcher::dispatch_account`] 相同，协议的启用状态和 Bonk 账户解析器
+    /// 取自 `registry` 而不是全局注册表
+    pub fn dispatch_account_with(
+        registry: &ParserRegistry,
         protocol: Protocol,
         discriminator: &[u8],
         account: &crate::streaming::grpc::AccountPretty,
         mut metadata: crate::streaming::event_parser::common::EventMetadata,
     ) -> Option<DexEvent> {
+        // 跳过运行时被禁用的协议
+        if !registry.is_enabled(&protocol) {
+            return None;
+        }
+
         // 根据协议类型设置 metadata.protocol
         use crate::streaming::event_parser::common::ProtocolType;
         metadata.protocol = match protocol {
@@ -270,7 +317,8 @@ impl EventDispatcher {
             Protocol::PumpSwap => {
                 pumpswap::parse_pumpswap_account_data(discriminator, account, metadata)
             }
-            Protocol::Bonk => bonk::parse_bonk_account_data(discriminator, account, metadata),
+            // Bonk 账户解析器注册在 ParserRegistry 中
+            Protocol::Bonk => registry.parse(account, metadata),
             Protocol::RaydiumCpmm => {
                 raydium_cpmm::parse_raydium_cpmm_account_data(discriminator, account, metadata)
             }
@@ -2,6 +2,7 @@ use crate::streaming::event_parser::{
     DexEvent, Protocol, common::{
         EventMetadata, filter::EventTypeFilter, high_performance_clock::elapsed_micros_since, parse_swap_data_from_next_grpc_instructions, parse_swap_data_from_next_instructions
     }, core::{
+        common_event_parser::ComputeBudgetInfo,
         dispatcher::EventDispatcher,
         global_state::{
             add_bonk_dev_address, add_dev_address, is_bonk_dev_address_in_signature,
@@ -42,10 +43,6 @@ impl EventParser {
         transaction_index: Option<u64>,
         callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
     ) -> anyhow::Result<()> {
-        // 创建适配器回调，将所有权回调转换为引用回调
-        let adapter_callback = Arc::new(move |event: &DexEvent| {
-            callback(event.clone());
-        });
         if let Some(transition) = grpc_tx.transaction {
             if let Some(message) = &transition.message {
                 let mut address_table_lookups: Vec<Vec<u8>> = vec![];
@@ -82,6 +79,12 @@ impl EventParser {
                     .collect();
                 // 解析指令事件
                 let instructions = &message.instructions;
+                let compute_budget =
+                    ComputeBudgetInfo::from_instructions(instructions.iter().filter_map(|ix| {
+                        let program_id = accounts.get(ix.program_id_index as usize)?;
+                        Some((program_id, ix.data.as_slice()))
+                    }));
+                let adapter_callback = Self::adapter_callback(callback, compute_budget);
                 Self::parse_instruction_events_from_grpc_transaction(
                     protocols,
                     event_type_filter,
@@ -122,12 +125,14 @@ impl EventParser {
         transaction_index: Option<u64>,
         callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
     ) -> anyhow::Result<()> {
-        // 创建适配器回调，将所有权回调转换为引用回调
-        let adapter_callback = Arc::new(move |event: &DexEvent| {
-            callback(event.clone());
-        });
         // 获取交易的指令和账户
         let compiled_instructions = transaction.message.instructions();
+        let compute_budget =
+            ComputeBudgetInfo::from_instructions(compiled_instructions.iter().filter_map(|ix| {
+                let program_id = accounts.get(ix.program_id_index as usize)?;
+                Some((program_id, ix.data.as_slice()))
+            }));
+        let adapter_callback = Self::adapter_callback(callback, compute_budget);
         let mut accounts: Vec<Pubkey> = accounts.to_vec();
         // 检查交易中是否包含程序
         let has_program = accounts
@@ -655,6 +660,25 @@ impl EventParser {
     // Helper Functions
     // ================================================================================================
 
+    /// 创建适配器回调，将所有权回调转换为引用回调
+    ///
+    /// 同时为交易事件附带交易级别的信息（Compute Budget 设置的优先费）
+    fn adapter_callback(
+        callback: Arc<dyn Fn(DexEvent) + Send + Sync>,
+        compute_budget: ComputeBudgetInfo,
+    ) -> Arc<dyn for<'a> Fn(&'a DexEvent) + Send + Sync> {
+        Arc::new(move |event: &DexEvent| {
+            let mut event = event.clone();
+            if let DexEvent::BonkTradeEvent(trade_info) = &mut event {
+                trade_info.compute_unit_price = compute_budget.unit_price.unwrap_or(0);
+                trade_info.compute_unit_limit = compute_budget.effective_unit_limit();
+                trade_info.priority_fee_microlamports =
+                    compute_budget.priority_fee_microlamports();
+            }
+            callback(event);
+        })
+    }
+
     /// Check if instruction should be processed based on protocol filter
     ///
     /// Determines whether a program_id matches any of the protocols we're interested in.
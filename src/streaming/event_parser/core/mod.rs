@@ -1,12 +1,22 @@
 pub mod account_event_parser;
 pub mod common_event_parser;
+pub mod coverage;
+pub mod decode_cache;
 pub mod dispatcher;
 pub mod global_state;
 pub mod parser_cache;
+pub mod parser_metrics;
+pub mod parser_registry;
 pub mod traits;
 
-pub use traits::DexEvent;
+pub use traits::{
+    dispatch, AccountEvent, DexEvent, EventHandler, RentStatus, TypedEvent, EVENT_ENCODING_VERSION,
+};
+pub use coverage::{CoverageReport, CoverageSnapshot};
+pub use decode_cache::AccountDecodeCache;
 pub use dispatcher::EventDispatcher;
+pub use parser_metrics::{ParseCounts, ParserMetrics};
+pub use parser_registry::{AccountParser, ParserRegistry};
 
 pub mod event_parser;
 pub mod merger_event;
\ No newline at end of file
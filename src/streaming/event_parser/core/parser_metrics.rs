@@ -0,0 +1,177 @@
+//! 按事件类型统计账户解码结果
+//!
+//! 记录每种账户事件类型的解码尝试次数、成功次数以及失败原因（数据过短、borsh 解码失败、
+//! 往返校验不一致），
+//! 用于监控解码健康度，及时发现协议升级导致的解码失败。
+//! 默认关闭，通过 `ParserMetrics::global().set_enabled(true)` 开启；关闭时只有一次原子读取的开销。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use dashmap::DashMap;
+
+use crate::streaming::event_parser::common::{DecodeError, EventType};
+
+/// 单个事件类型的解码计数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseCounts {
+    /// discriminator 命中、进入解码的次数
+    pub attempted: u64,
+    /// 解码成功的次数
+    pub succeeded: u64,
+    /// 因数据长度不足失败的次数
+    pub too_short: u64,
+    /// 因 borsh 反序列化失败的次数
+    pub borsh_failed: u64,
+    /// 开启往返校验时，重新编码结果与原始数据不一致的次数
+    pub round_trip_mismatch: u64,
+}
+
+impl ParseCounts {
+    /// 失败总数，包含未能区分原因的失败
+    pub fn failed(&self) -> u64 {
+        self.attempted.saturating_sub(self.succeeded)
+    }
+}
+
+#[derive(Default)]
+struct AtomicParseCounts {
+    attempted: AtomicU64,
+    succeeded: AtomicU64,
+    too_short: AtomicU64,
+    borsh_failed: AtomicU64,
+    round_trip_mismatch: AtomicU64,
+}
+
+impl AtomicParseCounts {
+    fn load(&self) -> ParseCounts {
+        ParseCounts {
+            attempted: self.attempted.load(Ordering::Relaxed),
+            succeeded: self.succeeded.load(Ordering::Relaxed),
+            too_short: self.too_short.load(Ordering::Relaxed),
+            borsh_failed: self.borsh_failed.load(Ordering::Relaxed),
+            round_trip_mismatch: self.round_trip_mismatch.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 账户解码指标，由 `AccountEventParser` 和返回 `DecodeError` 的账户解析器更新
+///
+/// `attempted`/`succeeded` 覆盖所有协议账户；失败原因只在解析器能给出 `DecodeError` 时记录，
+/// 其余失败只体现在 [`ParseCounts::failed`] 中
+pub struct ParserMetrics {
+    enabled: AtomicBool,
+    counts: DashMap<EventType, AtomicParseCounts>,
+}
+
+impl ParserMetrics {
+    pub fn new() -> Self {
+        Self { enabled: AtomicBool::new(false), counts: DashMap::new() }
+    }
+
+    /// 获取全局实例
+    pub fn global() -> &'static ParserMetrics {
+        &PARSER_METRICS
+    }
+
+    /// 开启或关闭统计
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn with_counts(&self, event_type: &EventType, f: impl FnOnce(&AtomicParseCounts)) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Some(counts) = self.counts.get(event_type) {
+            f(&counts);
+            return;
+        }
+        f(&self.counts.entry(event_type.clone()).or_default());
+    }
+
+    /// 记录一次解码结果
+    #[inline]
+    pub fn record_attempt(&self, event_type: &EventType, succeeded: bool) {
+        self.with_counts(event_type, |counts| {
+            counts.attempted.fetch_add(1, Ordering::Relaxed);
+            if succeeded {
+                counts.succeeded.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// 记录一次解码失败的原因
+    #[inline]
+    pub fn record_error(&self, event_type: &EventType, error: &DecodeError) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(event_type = event_type.metric_label(), %error, "account decode failed");
+        self.with_counts(event_type, |counts| {
+            let counter = match error {
+                DecodeError::TooShort { .. } => &counts.too_short,
+                DecodeError::Borsh(_) => &counts.borsh_failed,
+                DecodeError::RoundTripMismatch { .. } => &counts.round_trip_mismatch,
+                DecodeError::UnexpectedLength { .. }
+                | DecodeError::DiscriminatorMismatch
+                | DecodeError::NonZeroPadding { .. }
+                | DecodeError::TooManyElements { .. }
+                | DecodeError::UnsupportedVersion { .. }
+                | DecodeError::UnknownVariant { .. }
+                | DecodeError::Bincode(_) => return,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    /// 获取当前统计快照
+    pub fn snapshot(&self) -> HashMap<EventType, ParseCounts> {
+        self.counts.iter().map(|entry| (entry.key().clone(), entry.value().load())).collect()
+    }
+
+    /// 清空统计
+    pub fn reset(&self) {
+        self.counts.clear();
+    }
+}
+
+impl Default for ParserMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global parser metrics instance
+static PARSER_METRICS: once_cell::sync::Lazy<ParserMetrics> =
+    once_cell::sync::Lazy::new(ParserMetrics::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_mismatch_is_counted() {
+        let metrics = ParserMetrics::new();
+        metrics.set_enabled(true);
+        let event_type = EventType::AccountBonkPoolState;
+        metrics.record_attempt(&event_type, false);
+        metrics
+            .record_error(&event_type, &DecodeError::RoundTripMismatch { first_diff_offset: 42 });
+
+        let counts = metrics.snapshot()[&event_type];
+        assert_eq!(counts.round_trip_mismatch, 1);
+        assert_eq!(counts.failed(), 1);
+        assert_eq!(counts.too_short + counts.borsh_failed, 0);
+    }
+
+    #[test]
+    fn disabled_metrics_record_nothing() {
+        let metrics = ParserMetrics::new();
+        metrics.record_error(&EventType::AccountBonkPoolState, &DecodeError::DiscriminatorMismatch);
+        assert!(metrics.snapshot().is_empty());
+    }
+}
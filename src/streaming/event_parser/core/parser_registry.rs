@@ -0,0 +1,187 @@
+//! 协议解析器注册表
+//!
+//! 支持在运行时启用/禁用单个协议的解析（例如协议链上升级导致布局变化时），无需重新部署。
+//! 被禁用的协议在 `EventDispatcher` 中会被跳过。
+//!
+//! 同时维护按 (程序, discriminator) 索引的账户解析器表：内置的 Bonk 账户解析器在创建时注册，
+//! 下游可以通过 [`ParserRegistry::register_account_parser`] 注册自己的账户解析器，无需修改本 crate。
+
+use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::core::traits::DexEvent;
+use crate::streaming::event_parser::protocols::bonk::parser::{
+    BonkGlobalConfigParser, BonkPlatformConfigParser, BonkPoolStateParser,
+};
+use crate::streaming::event_parser::Protocol;
+use crate::streaming::grpc::AccountPretty;
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// 按 (程序, discriminator) 分发的账户解析器
+pub trait AccountParser: Send + Sync {
+    /// 账户数据前 8 字节的 discriminator
+    fn discriminator(&self) -> [u8; 8];
+
+    /// 账户所属程序，只解析该程序拥有的账户
+    ///
+    /// Anchor discriminator 只由账户类型名决定，不同程序的同名账户（如 `PoolState`）
+    /// discriminator 相同，因此解析器必须与程序一起注册
+    fn program_id(&self) -> Pubkey;
+
+    /// 解析账户，`metadata` 中的事件类型由解析器设置
+    fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent>;
+}
+
+/// 协议解析器注册表
+pub struct ParserRegistry {
+    /// 已禁用协议的位掩码，默认全部启用
+    disabled: AtomicU32,
+    /// 按 (程序, discriminator) 索引的账户解析器
+    account_parsers: DashMap<(Pubkey, [u8; 8]), Arc<dyn AccountParser>>,
+}
+
+impl ParserRegistry {
+    pub fn new() -> Self {
+        let registry = Self { disabled: AtomicU32::new(0), account_parsers: DashMap::new() };
+        registry.register_account_parser(BonkPoolStateParser::default());
+        registry.register_account_parser(BonkGlobalConfigParser);
+        registry.register_account_parser(BonkPlatformConfigParser);
+        registry
+    }
+
+    /// 获取全局注册表实例
+    pub fn global() -> &'static ParserRegistry {
+        &PARSER_REGISTRY
+    }
+
+    /// 在运行时启用或禁用指定协议的解析
+    pub fn set_enabled(&self, protocol: Protocol, enabled: bool) {
+        let bit = protocol.bit();
+        if enabled {
+            self.disabled.fetch_and(!bit, Ordering::Release);
+        } else {
+            self.disabled.fetch_or(bit, Ordering::Release);
+        }
+    }
+
+    /// 检查指定协议的解析是否启用
+    #[inline]
+    pub fn is_enabled(&self, protocol: &Protocol) -> bool {
+        self.disabled.load(Ordering::Acquire) & protocol.bit() == 0
+    }
+
+    /// 注册账户解析器，同一程序下相同 discriminator 的已有解析器会被替换
+    pub fn register_account_parser<P: AccountParser + 'static>(&self, parser: P) {
+        self.account_parsers
+            .insert((parser.program_id(), parser.discriminator()), Arc::new(parser));
+    }
+
+    /// 移除指定程序下指定 discriminator 的账户解析器
+    pub fn unregister_account_parser(&self, program_id: &Pubkey, discriminator: &[u8; 8]) {
+        self.account_parsers.remove(&(*program_id, *discriminator));
+    }
+
+    /// 按账户 owner 和数据前 8 字节查找账户解析器并解析，没有匹配的解析器时返回 `None`
+    pub fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+        let discriminator: [u8; 8] = account.data.get(..8)?.try_into().ok()?;
+        let parser = self.account_parsers.get(&(account.owner, discriminator))?.clone();
+        parser.parse(account, metadata)
+    }
+}
+
+impl Default for ParserRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global parser registry instance
+static PARSER_REGISTRY: once_cell::sync::Lazy<ParserRegistry> =
+    once_cell::sync::Lazy::new(ParserRegistry::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::core::account_event_parser::UnknownAccountEvent;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    /// 将账户解析为 `UnknownAccountEvent` 并记录 `data_len` 为 `marker`，用于确认被调用的解析器
+    struct MarkerParser {
+        program_id: Pubkey,
+        discriminator: [u8; 8],
+        marker: usize,
+    }
+
+    impl AccountParser for MarkerParser {
+        fn discriminator(&self) -> [u8; 8] {
+            self.discriminator
+        }
+
+        fn program_id(&self) -> Pubkey {
+            self.program_id
+        }
+
+        fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+            Some(DexEvent::UnknownAccountEvent(UnknownAccountEvent {
+                metadata,
+                pubkey: account.pubkey,
+                data_len: self.marker,
+                ..Default::default()
+            }))
+        }
+    }
+
+    fn marker(event: Option<DexEvent>) -> Option<usize> {
+        match event? {
+            DexEvent::UnknownAccountEvent(event) => Some(event.data_len),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn custom_parser_is_invoked() {
+        let registry = ParserRegistry::new();
+        let program_id = Pubkey::new_unique();
+        let discriminator = [7; 8];
+        registry.register_account_parser(MarkerParser { program_id, discriminator, marker: 1 });
+
+        let account =
+            AccountPrettyBuilder::new().owner(program_id).discriminator(discriminator).build();
+        assert_eq!(marker(registry.parse(&account, EventMetadata::default())), Some(1));
+
+        let other_owner = AccountPrettyBuilder::new().discriminator(discriminator).build();
+        assert_eq!(registry.parse(&other_owner, EventMetadata::default()), None);
+
+        registry.unregister_account_parser(&program_id, &discriminator);
+        assert_eq!(registry.parse(&account, EventMetadata::default()), None);
+    }
+
+    #[test]
+    fn same_discriminator_under_another_program_does_not_replace_builtin() {
+        let registry = ParserRegistry::new();
+        let program_id = Pubkey::new_unique();
+        registry.register_account_parser(MarkerParser {
+            program_id,
+            discriminator: POOL_STATE_DISCRIMINATOR,
+            marker: 2,
+        });
+
+        let bonk_pool = AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(&PoolState::default())
+            .build();
+        let event = registry.parse(&bonk_pool, EventMetadata::default());
+        assert!(matches!(event, Some(DexEvent::BonkPoolStateAccountEvent(_))), "{event:?}");
+
+        let other_pool = AccountPrettyBuilder::new()
+            .owner(program_id)
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .build();
+        assert_eq!(marker(registry.parse(&other_pool, EventMetadata::default())), Some(2));
+    }
+}
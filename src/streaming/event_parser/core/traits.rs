@@ -1,9 +1,9 @@
-use crate::streaming::event_parser::common::EventMetadata;
+use crate::streaming::event_parser::common::{Checkpoint, DecodeError, EventMetadata, EventType};
 use crate::streaming::event_parser::core::account_event_parser::{
-    NonceAccountEvent, TokenAccountEvent, TokenInfoEvent,
+    AccountClosedEvent, NonceAccountEvent, TokenAccountEvent, TokenInfoEvent, UnknownAccountEvent,
 };
 use crate::streaming::event_parser::core::common_event_parser::{
-    SetComputeUnitLimitEvent, SetComputeUnitPriceEvent,
+    HeartbeatEvent, SetComputeUnitLimitEvent, SetComputeUnitPriceEvent,
 };
 use crate::streaming::event_parser::protocols::block::block_meta_event::BlockMetaEvent;
 use crate::streaming::event_parser::protocols::bonk::events::*;
@@ -13,199 +13,898 @@ use crate::streaming::event_parser::protocols::pumpswap::events::*;
 use crate::streaming::event_parser::protocols::raydium_amm_v4::events::*;
 use crate::streaming::event_parser::protocols::raydium_clmm::events::*;
 use crate::streaming::event_parser::protocols::raydium_cpmm::events::*;
+use crate::streaming::event_parser::protocols::raydium_launchpad::events::*;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use solana_program::hash::hashv;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::fmt::{self, Debug};
 
-/// Unified Event Enum - Replaces the trait-based approach with a type-safe enum
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub enum DexEvent {
+/// 由同一份变体列表生成 `DexEvent` 枚举、`metadata()`/`metadata_mut()`、每个变体的
+/// `TypedEvent` 实现以及 `EventHandler` 的回调方法。变体名与其携带的事件类型同名，且事件类型
+/// 都包含 `metadata` 字段，新增事件时只需要在 `define_dex_event!` 的列表中加一行并指定回调名
+macro_rules! define_dex_event {
+    ($($variant:ident => $handler:ident),* $(,)?) => {
+        /// Unified Event Enum - Replaces the trait-based approach with a type-safe enum
+        #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+        pub enum DexEvent {
+            $($variant($variant),)*
+        }
+
+        impl DexEvent {
+            /// 事件元数据
+            pub fn metadata(&self) -> &EventMetadata {
+                match self {
+                    $(DexEvent::$variant(e) => &e.metadata,)*
+                }
+            }
+
+            /// 事件元数据（可变）
+            pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+                match self {
+                    $(DexEvent::$variant(e) => &mut e.metadata,)*
+                }
+            }
+
+            /// 编码为紧凑的二进制帧，用于进程间传输
+            ///
+            /// 帧格式：1 字节格式版本（[`EVENT_ENCODING_VERSION`]）、1 字节变体标签（按变体在
+            /// `define_dex_event!` 中的声明顺序编号），之后是事件结构体的 bincode 编码。
+            /// serde 序列化时跳过的字段（padding、`raw_data` 等）不会写入帧中
+            pub fn encode(&self) -> Vec<u8> {
+                let mut frame = vec![EVENT_ENCODING_VERSION];
+                let result = match self {
+                    $(DexEvent::$variant(e) => {
+                        frame.push(EventTag::$variant as u8);
+                        bincode::serialize_into(&mut frame, e)
+                    })*
+                };
+                result.expect("DexEvent serialization cannot fail");
+                frame
+            }
+
+            /// 解码 [`DexEvent::encode`] 生成的帧
+            ///
+            /// 帧被截断或已损坏时返回错误，不会 panic；版本号不一致时返回
+            /// `DecodeError::UnsupportedVersion`
+            pub fn decode(bytes: &[u8]) -> Result<DexEvent, DecodeError> {
+                let header = DecodeError::take(bytes, 2)?;
+                let (version, tag) = (header[0], header[1]);
+                if version != EVENT_ENCODING_VERSION {
+                    return Err(DecodeError::UnsupportedVersion { version });
+                }
+                let body = &bytes[2..];
+                $(
+                    if tag == EventTag::$variant as u8 {
+                        return Ok(DexEvent::$variant(bincode::deserialize(body)?));
+                    }
+                )*
+                Err(DecodeError::UnknownVariant { tag })
+            }
+        }
+
+        /// 二进制帧中的变体标签
+        #[repr(u8)]
+        #[allow(clippy::enum_variant_names)]
+        enum EventTag {
+            $($variant,)*
+        }
+
+        #[cfg(test)]
+        impl DexEvent {
+            /// 每个变体各一个默认值，按声明顺序排列
+            pub(crate) fn default_variants() -> Vec<DexEvent> {
+                vec![$(DexEvent::$variant($variant::default()),)*]
+            }
+        }
+
+        $(
+            impl TypedEvent for $variant {
+                fn from_dex_event(event: DexEvent) -> Result<Self, Box<DexEvent>> {
+                    match event {
+                        DexEvent::$variant(e) => Ok(e),
+                        other => Err(Box::new(other)),
+                    }
+                }
+            }
+        )*
+
+        /// 按事件类型接收回调，所有方法默认为空，只需实现关心的事件
+        ///
+        /// ```ignore
+        /// struct PoolWatcher;
+        ///
+        /// impl EventHandler for PoolWatcher {
+        ///     fn on_bonk_pool_state_account(&mut self, event: &BonkPoolStateAccountEvent) {
+        ///         println!("{}", event.pubkey);
+        ///     }
+        /// }
+        ///
+        /// dispatch(&event, &mut PoolWatcher);
+        /// ```
+        pub trait EventHandler {
+            $(
+                fn $handler(&mut self, event: &$variant) {
+                    let _ = event;
+                }
+            )*
+        }
+
+        /// 将事件分发到 `handler` 对应的回调方法
+        pub fn dispatch(event: &DexEvent, handler: &mut dyn EventHandler) {
+            match event {
+                $(DexEvent::$variant(e) => handler.$handler(e),)*
+            }
+        }
+    };
+}
+
+/// [`DexEvent::encode`] 的帧格式版本，帧格式、变体标签或事件结构体的字段变化时递增
+///
+/// 变体标签按声明顺序编号，新增事件只能追加在 `define_dex_event!` 列表的末尾。
+///
+/// - 2：`BonkGlobalConfigChangedEvent` 移到 `UnknownAccountEvent` 之后，追加
+///   `BonkImpliedTradeEvent`；`EventMetadata` 新增 `write_version`，`BonkPoolStateAccountEvent`
+///   新增 `decode_warnings`
+pub const EVENT_ENCODING_VERSION: u8 = 2;
+
+define_dex_event!(
     // Bonk events
-    BonkTradeEvent(BonkTradeEvent),
-    BonkPoolCreateEvent(BonkPoolCreateEvent),
-    BonkMigrateToAmmEvent(BonkMigrateToAmmEvent),
-    BonkMigrateToCpswapEvent(BonkMigrateToCpswapEvent),
-    BonkPoolStateAccountEvent(BonkPoolStateAccountEvent),
-    BonkGlobalConfigAccountEvent(BonkGlobalConfigAccountEvent),
-    BonkPlatformConfigAccountEvent(BonkPlatformConfigAccountEvent),
+    BonkTradeEvent => on_bonk_trade,
+    BonkPoolCreateEvent => on_bonk_pool_create,
+    BonkMigrateToAmmEvent => on_bonk_migrate_to_amm,
+    BonkMigrateToCpswapEvent => on_bonk_migrate_to_cpswap,
+    BonkPoolStateAccountEvent => on_bonk_pool_state_account,
+    BonkGlobalConfigAccountEvent => on_bonk_global_config_account,
+    BonkPlatformConfigAccountEvent => on_bonk_platform_config_account,
 
     // PumpFun events
-    PumpFunCreateTokenEvent(PumpFunCreateTokenEvent),
-    PumpFunCreateV2TokenEvent(PumpFunCreateV2TokenEvent),
-    PumpFunTradeEvent(PumpFunTradeEvent),
-    PumpFunMigrateEvent(PumpFunMigrateEvent),
-    PumpFunBondingCurveAccountEvent(PumpFunBondingCurveAccountEvent),
-    PumpFunGlobalAccountEvent(PumpFunGlobalAccountEvent),
+    PumpFunCreateTokenEvent => on_pumpfun_create_token,
+    PumpFunCreateV2TokenEvent => on_pumpfun_create_v2_token,
+    PumpFunTradeEvent => on_pumpfun_trade,
+    PumpFunMigrateEvent => on_pumpfun_migrate,
+    PumpFunBondingCurveAccountEvent => on_pumpfun_bonding_curve_account,
+    PumpFunGlobalAccountEvent => on_pumpfun_global_account,
 
     // PumpSwap events
-    PumpSwapBuyEvent(PumpSwapBuyEvent),
-    PumpSwapSellEvent(PumpSwapSellEvent),
-    PumpSwapCreatePoolEvent(PumpSwapCreatePoolEvent),
-    PumpSwapDepositEvent(PumpSwapDepositEvent),
-    PumpSwapWithdrawEvent(PumpSwapWithdrawEvent),
-    PumpSwapGlobalConfigAccountEvent(PumpSwapGlobalConfigAccountEvent),
-    PumpSwapPoolAccountEvent(PumpSwapPoolAccountEvent),
+    PumpSwapBuyEvent => on_pumpswap_buy,
+    PumpSwapSellEvent => on_pumpswap_sell,
+    PumpSwapCreatePoolEvent => on_pumpswap_create_pool,
+    PumpSwapDepositEvent => on_pumpswap_deposit,
+    PumpSwapWithdrawEvent => on_pumpswap_withdraw,
+    PumpSwapGlobalConfigAccountEvent => on_pumpswap_global_config_account,
+    PumpSwapPoolAccountEvent => on_pumpswap_pool_account,
 
     // Raydium AMM V4 events
-    RaydiumAmmV4SwapEvent(RaydiumAmmV4SwapEvent),
-    RaydiumAmmV4DepositEvent(RaydiumAmmV4DepositEvent),
-    RaydiumAmmV4WithdrawEvent(RaydiumAmmV4WithdrawEvent),
-    RaydiumAmmV4WithdrawPnlEvent(RaydiumAmmV4WithdrawPnlEvent),
-    RaydiumAmmV4Initialize2Event(RaydiumAmmV4Initialize2Event),
-    RaydiumAmmV4AmmInfoAccountEvent(RaydiumAmmV4AmmInfoAccountEvent),
+    RaydiumAmmV4SwapEvent => on_raydium_amm_v4_swap,
+    RaydiumAmmV4DepositEvent => on_raydium_amm_v4_deposit,
+    RaydiumAmmV4WithdrawEvent => on_raydium_amm_v4_withdraw,
+    RaydiumAmmV4WithdrawPnlEvent => on_raydium_amm_v4_withdraw_pnl,
+    RaydiumAmmV4Initialize2Event => on_raydium_amm_v4_initialize2,
+    RaydiumAmmV4AmmInfoAccountEvent => on_raydium_amm_v4_amm_info_account,
 
     // Raydium CLMM events
-    RaydiumClmmSwapEvent(RaydiumClmmSwapEvent),
-    RaydiumClmmSwapV2Event(RaydiumClmmSwapV2Event),
-    RaydiumClmmClosePositionEvent(RaydiumClmmClosePositionEvent),
-    RaydiumClmmIncreaseLiquidityV2Event(RaydiumClmmIncreaseLiquidityV2Event),
-    RaydiumClmmDecreaseLiquidityV2Event(RaydiumClmmDecreaseLiquidityV2Event),
-    RaydiumClmmCreatePoolEvent(RaydiumClmmCreatePoolEvent),
-    RaydiumClmmOpenPositionWithToken22NftEvent(RaydiumClmmOpenPositionWithToken22NftEvent),
-    RaydiumClmmOpenPositionV2Event(RaydiumClmmOpenPositionV2Event),
-    RaydiumClmmAmmConfigAccountEvent(RaydiumClmmAmmConfigAccountEvent),
-    RaydiumClmmPoolStateAccountEvent(RaydiumClmmPoolStateAccountEvent),
-    RaydiumClmmTickArrayStateAccountEvent(RaydiumClmmTickArrayStateAccountEvent),
+    RaydiumClmmSwapEvent => on_raydium_clmm_swap,
+    RaydiumClmmSwapV2Event => on_raydium_clmm_swap_v2,
+    RaydiumClmmClosePositionEvent => on_raydium_clmm_close_position,
+    RaydiumClmmIncreaseLiquidityV2Event => on_raydium_clmm_increase_liquidity_v2,
+    RaydiumClmmDecreaseLiquidityV2Event => on_raydium_clmm_decrease_liquidity_v2,
+    RaydiumClmmCreatePoolEvent => on_raydium_clmm_create_pool,
+    RaydiumClmmOpenPositionWithToken22NftEvent => on_raydium_clmm_open_position_with_token22_nft,
+    RaydiumClmmOpenPositionV2Event => on_raydium_clmm_open_position_v2,
+    RaydiumClmmAmmConfigAccountEvent => on_raydium_clmm_amm_config_account,
+    RaydiumClmmPoolStateAccountEvent => on_raydium_clmm_pool_state_account,
+    RaydiumClmmTickArrayStateAccountEvent => on_raydium_clmm_tick_array_state_account,
 
     // Raydium CPMM events
-    RaydiumCpmmSwapEvent(RaydiumCpmmSwapEvent),
-    RaydiumCpmmDepositEvent(RaydiumCpmmDepositEvent),
-    RaydiumCpmmWithdrawEvent(RaydiumCpmmWithdrawEvent),
-    RaydiumCpmmInitializeEvent(RaydiumCpmmInitializeEvent),
-    RaydiumCpmmAmmConfigAccountEvent(RaydiumCpmmAmmConfigAccountEvent),
-    RaydiumCpmmPoolStateAccountEvent(RaydiumCpmmPoolStateAccountEvent),
+    RaydiumCpmmSwapEvent => on_raydium_cpmm_swap,
+    RaydiumCpmmDepositEvent => on_raydium_cpmm_deposit,
+    RaydiumCpmmWithdrawEvent => on_raydium_cpmm_withdraw,
+    RaydiumCpmmInitializeEvent => on_raydium_cpmm_initialize,
+    RaydiumCpmmAmmConfigAccountEvent => on_raydium_cpmm_amm_config_account,
+    RaydiumCpmmPoolStateAccountEvent => on_raydium_cpmm_pool_state_account,
 
     // Meteora DAMM v2 events
-    MeteoraDammV2SwapEvent(MeteoraDammV2SwapEvent),
-    MeteoraDammV2Swap2Event(MeteoraDammV2Swap2Event),
-    MeteoraDammV2InitializePoolEvent(MeteoraDammV2InitializePoolEvent),
-    MeteoraDammV2InitializeCustomizablePoolEvent(MeteoraDammV2InitializeCustomizablePoolEvent),
-    MeteoraDammV2InitializePoolWithDynamicConfigEvent(MeteoraDammV2InitializePoolWithDynamicConfigEvent),
+    MeteoraDammV2SwapEvent => on_meteora_damm_v2_swap,
+    MeteoraDammV2Swap2Event => on_meteora_damm_v2_swap2,
+    MeteoraDammV2InitializePoolEvent => on_meteora_damm_v2_initialize_pool,
+    MeteoraDammV2InitializeCustomizablePoolEvent => on_meteora_damm_v2_initialize_customizable_pool,
+    MeteoraDammV2InitializePoolWithDynamicConfigEvent => on_meteora_damm_v2_initialize_pool_with_dynamic_config,
 
     // Common events
-    TokenAccountEvent(TokenAccountEvent),
-    NonceAccountEvent(NonceAccountEvent),
-    TokenInfoEvent(TokenInfoEvent),
-    BlockMetaEvent(BlockMetaEvent),
-    SetComputeUnitLimitEvent(SetComputeUnitLimitEvent),
-    SetComputeUnitPriceEvent(SetComputeUnitPriceEvent),
-}
+    TokenAccountEvent => on_token_account,
+    NonceAccountEvent => on_nonce_account,
+    TokenInfoEvent => on_token_info,
+    BlockMetaEvent => on_block_meta,
+    SetComputeUnitLimitEvent => on_set_compute_unit_limit,
+    SetComputeUnitPriceEvent => on_set_compute_unit_price,
+    HeartbeatEvent => on_heartbeat,
+    RaydiumLaunchpadPoolStateAccountEvent => on_raydium_launchpad_pool_state_account,
+    BonkTradingLiveEvent => on_bonk_trading_live,
+    AccountClosedEvent => on_account_closed,
+    UnknownAccountEvent => on_unknown_account,
+    BonkGlobalConfigChangedEvent => on_bonk_global_config_changed,
+    BonkImpliedTradeEvent => on_bonk_implied_trade,
+);
 
 impl DexEvent {
-    pub fn metadata(&self) -> &EventMetadata {
+    /// 事件类型，等同于 `metadata().event_type`
+    pub fn event_type(&self) -> EventType {
+        self.metadata().event_type.clone()
+    }
+
+    /// 事件内容哈希（SHA-256），可作为下游幂等写入的键
+    ///
+    /// 覆盖事件自身的全部字段，以及元数据中除接收/处理耗时（`recv_us`、`handle_us`）、解析序号
+    /// （`seq`）、确认级别（`commitment`）和各节点不同的写入版本号（`write_version`）以外的字段：
+    /// signature、slot、transaction_index、block_time、protocol、event_type、program_id、
+    /// swap_data 和指令位置。账户事件即账户地址、slot 和解码后的结构体。serde 序列化时跳过的
+    /// 字段（padding 等）不参与计算。使用 bincode 序列化，事件结构变化后同一事件的哈希也会变化
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut event = self.clone();
+        let metadata = event.metadata_mut();
+        metadata.recv_us = 0;
+        metadata.handle_us = 0;
+        metadata.seq = 0;
+        metadata.commitment = None;
+        metadata.write_version = None;
+        let bytes = bincode::serialize(&event).expect("DexEvent serialization cannot fail");
+        hashv(&[&bytes]).to_bytes()
+    }
+
+    /// 事件对应的处理进度，见 [`EventMetadata::checkpoint`]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.metadata().checkpoint()
+    }
+
+    /// 账户事件对应的账户地址，非账户事件返回 `None`
+    pub fn account_pubkey(&self) -> Option<Pubkey> {
+        self.as_account_event().map(|e| e.pubkey())
+    }
+
+    /// 以 [`AccountEvent`] 读取账户事件的公共字段，非账户事件返回 `None`
+    ///
+    /// ```ignore
+    /// if let Some(account) = event.as_account_event() {
+    ///     println!("account {} updated at slot {}", account.pubkey(), account.slot());
+    /// }
+    /// ```
+    pub fn as_account_event(&self) -> Option<&dyn AccountEvent> {
         match self {
-            DexEvent::BonkTradeEvent(e) => &e.metadata,
-            DexEvent::BonkPoolCreateEvent(e) => &e.metadata,
-            DexEvent::BonkMigrateToAmmEvent(e) => &e.metadata,
-            DexEvent::BonkMigrateToCpswapEvent(e) => &e.metadata,
-            DexEvent::BonkPoolStateAccountEvent(e) => &e.metadata,
-            DexEvent::BonkGlobalConfigAccountEvent(e) => &e.metadata,
-            DexEvent::BonkPlatformConfigAccountEvent(e) => &e.metadata,
-            DexEvent::PumpFunCreateTokenEvent(e) => &e.metadata,
-            DexEvent::PumpFunCreateV2TokenEvent(e) => &e.metadata,
-            DexEvent::PumpFunTradeEvent(e) => &e.metadata,
-            DexEvent::PumpFunMigrateEvent(e) => &e.metadata,
-            DexEvent::PumpFunBondingCurveAccountEvent(e) => &e.metadata,
-            DexEvent::PumpFunGlobalAccountEvent(e) => &e.metadata,
-            DexEvent::PumpSwapBuyEvent(e) => &e.metadata,
-            DexEvent::PumpSwapSellEvent(e) => &e.metadata,
-            DexEvent::PumpSwapCreatePoolEvent(e) => &e.metadata,
-            DexEvent::PumpSwapDepositEvent(e) => &e.metadata,
-            DexEvent::PumpSwapWithdrawEvent(e) => &e.metadata,
-            DexEvent::PumpSwapGlobalConfigAccountEvent(e) => &e.metadata,
-            DexEvent::PumpSwapPoolAccountEvent(e) => &e.metadata,
-            DexEvent::RaydiumAmmV4SwapEvent(e) => &e.metadata,
-            DexEvent::RaydiumAmmV4DepositEvent(e) => &e.metadata,
-            DexEvent::RaydiumAmmV4WithdrawEvent(e) => &e.metadata,
-            DexEvent::RaydiumAmmV4WithdrawPnlEvent(e) => &e.metadata,
-            DexEvent::RaydiumAmmV4Initialize2Event(e) => &e.metadata,
-            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmSwapEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmSwapV2Event(e) => &e.metadata,
-            DexEvent::RaydiumClmmClosePositionEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmIncreaseLiquidityV2Event(e) => &e.metadata,
-            DexEvent::RaydiumClmmDecreaseLiquidityV2Event(e) => &e.metadata,
-            DexEvent::RaydiumClmmCreatePoolEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmOpenPositionWithToken22NftEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmOpenPositionV2Event(e) => &e.metadata,
-            DexEvent::RaydiumClmmAmmConfigAccountEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => &e.metadata,
-            DexEvent::RaydiumClmmTickArrayStateAccountEvent(e) => &e.metadata,
-            DexEvent::RaydiumCpmmSwapEvent(e) => &e.metadata,
-            DexEvent::RaydiumCpmmDepositEvent(e) => &e.metadata,
-            DexEvent::RaydiumCpmmWithdrawEvent(e) => &e.metadata,
-            DexEvent::RaydiumCpmmInitializeEvent(e) => &e.metadata,
-            DexEvent::RaydiumCpmmAmmConfigAccountEvent(e) => &e.metadata,
-            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => &e.metadata,
-            DexEvent::MeteoraDammV2SwapEvent(e) => &e.metadata,
-            DexEvent::MeteoraDammV2Swap2Event(e) => &e.metadata,
-            DexEvent::MeteoraDammV2InitializePoolEvent(e) => &e.metadata,
-            DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => &e.metadata,
-            DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => &e.metadata,
-            DexEvent::TokenAccountEvent(e) => &e.metadata,
-            DexEvent::NonceAccountEvent(e) => &e.metadata,
-            DexEvent::TokenInfoEvent(e) => &e.metadata,
-            DexEvent::BlockMetaEvent(e) => &e.metadata,
-            DexEvent::SetComputeUnitLimitEvent(e) => &e.metadata,
-            DexEvent::SetComputeUnitPriceEvent(e) => &e.metadata,
-        }
-    }
-
-    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+            DexEvent::BonkPoolStateAccountEvent(e) => Some(e),
+            DexEvent::BonkGlobalConfigAccountEvent(e) => Some(e),
+            DexEvent::BonkPlatformConfigAccountEvent(e) => Some(e),
+            DexEvent::PumpFunBondingCurveAccountEvent(e) => Some(e),
+            DexEvent::PumpFunGlobalAccountEvent(e) => Some(e),
+            DexEvent::PumpSwapGlobalConfigAccountEvent(e) => Some(e),
+            DexEvent::PumpSwapPoolAccountEvent(e) => Some(e),
+            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => Some(e),
+            DexEvent::RaydiumClmmAmmConfigAccountEvent(e) => Some(e),
+            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => Some(e),
+            DexEvent::RaydiumClmmTickArrayStateAccountEvent(e) => Some(e),
+            DexEvent::RaydiumCpmmAmmConfigAccountEvent(e) => Some(e),
+            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => Some(e),
+            DexEvent::RaydiumLaunchpadPoolStateAccountEvent(e) => Some(e),
+            DexEvent::TokenAccountEvent(e) => Some(e),
+            DexEvent::NonceAccountEvent(e) => Some(e),
+            DexEvent::TokenInfoEvent(e) => Some(e),
+            DexEvent::AccountClosedEvent(e) => Some(e),
+            DexEvent::UnknownAccountEvent(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// 事件所在的 slot，等同于 `metadata().slot`
+    pub fn slot(&self) -> u64 {
+        self.metadata().slot
+    }
+
+    /// 区块时间（秒），等同于 `metadata().block_time`
+    pub fn block_time(&self) -> i64 {
+        self.metadata().block_time
+    }
+
+    /// 交易签名，等同于 `metadata().signature`（账户事件为产生该更新的交易签名）
+    pub fn signature(&self) -> &Signature {
+        &self.metadata().signature
+    }
+
+    /// 面向非 Rust 消费者的 JSON 表示
+    ///
+    /// 格式为 `{"type": "<变体名>", "data": {...}}`，所有字段名（包括嵌套字段）转换为 camelCase，
+    /// 例如 `{"type": "BonkPoolStateAccountEvent", "data": {"pubkey": "..", "poolState":
+    /// {"baseMint": ..}, "metadata": {"eventType": ..}}}`。`serde_json::to_value(&event)` 等
+    /// 原有序列化结果不变
+    pub fn to_json_value(&self) -> serde_json::Result<serde_json::Value> {
+        let value = match serde_json::to_value(self)? {
+            // 外部标签：`{"<变体名>": {...}}`
+            serde_json::Value::Object(map) if map.len() == 1 => map,
+            other => return Ok(camel_case_keys(other)),
+        };
+        let (variant, data) = value.into_iter().next().expect("map has one entry");
+        Ok(serde_json::json!({ "type": variant, "data": camel_case_keys(data) }))
+    }
+}
+
+/// 递归地把 JSON 对象的键从 snake_case 转换为 camelCase
+fn camel_case_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (snake_to_camel(&key), camel_case_keys(value)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(camel_case_keys).collect())
+        }
+        other => other,
+    }
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' && !out.is_empty() {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// 单行摘要，用于日志；完整内容使用 `Debug`
+///
+/// 格式为 `<事件类型> <关键字段> slot=<slot>`，例如
+/// `AccountBonkPoolState pool=<pubkey> status=Trade progress=42% slot=<slot>`。
+/// 没有专门摘要的账户事件输出账户地址，其余事件输出交易签名
+impl fmt::Display for DexEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.metadata().event_type)?;
         match self {
-            DexEvent::BonkTradeEvent(e) => &mut e.metadata,
-            DexEvent::BonkPoolCreateEvent(e) => &mut e.metadata,
-            DexEvent::BonkMigrateToAmmEvent(e) => &mut e.metadata,
-            DexEvent::BonkMigrateToCpswapEvent(e) => &mut e.metadata,
-            DexEvent::BonkPoolStateAccountEvent(e) => &mut e.metadata,
-            DexEvent::BonkGlobalConfigAccountEvent(e) => &mut e.metadata,
-            DexEvent::BonkPlatformConfigAccountEvent(e) => &mut e.metadata,
-            DexEvent::PumpFunCreateTokenEvent(e) => &mut e.metadata,
-            DexEvent::PumpFunCreateV2TokenEvent(e) => &mut e.metadata,
-            DexEvent::PumpFunTradeEvent(e) => &mut e.metadata,
-            DexEvent::PumpFunMigrateEvent(e) => &mut e.metadata,
-            DexEvent::PumpFunBondingCurveAccountEvent(e) => &mut e.metadata,
-            DexEvent::PumpFunGlobalAccountEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapBuyEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapSellEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapCreatePoolEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapDepositEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapWithdrawEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapGlobalConfigAccountEvent(e) => &mut e.metadata,
-            DexEvent::PumpSwapPoolAccountEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumAmmV4SwapEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumAmmV4DepositEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumAmmV4WithdrawEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumAmmV4WithdrawPnlEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumAmmV4Initialize2Event(e) => &mut e.metadata,
-            DexEvent::RaydiumAmmV4AmmInfoAccountEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmSwapEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmSwapV2Event(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmClosePositionEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmIncreaseLiquidityV2Event(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmDecreaseLiquidityV2Event(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmCreatePoolEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmOpenPositionWithToken22NftEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmOpenPositionV2Event(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmAmmConfigAccountEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmPoolStateAccountEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumClmmTickArrayStateAccountEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumCpmmSwapEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumCpmmDepositEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumCpmmWithdrawEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumCpmmInitializeEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumCpmmAmmConfigAccountEvent(e) => &mut e.metadata,
-            DexEvent::RaydiumCpmmPoolStateAccountEvent(e) => &mut e.metadata,
-            DexEvent::MeteoraDammV2SwapEvent(e) => &mut e.metadata,
-            DexEvent::MeteoraDammV2Swap2Event(e) => &mut e.metadata,
-            DexEvent::MeteoraDammV2InitializePoolEvent(e) => &mut e.metadata,
-            DexEvent::MeteoraDammV2InitializeCustomizablePoolEvent(e) => &mut e.metadata,
-            DexEvent::MeteoraDammV2InitializePoolWithDynamicConfigEvent(e) => &mut e.metadata,
-            DexEvent::TokenAccountEvent(e) => &mut e.metadata,
-            DexEvent::NonceAccountEvent(e) => &mut e.metadata,
-            DexEvent::TokenInfoEvent(e) => &mut e.metadata,
-            DexEvent::BlockMetaEvent(e) => &mut e.metadata,
-            DexEvent::SetComputeUnitLimitEvent(e) => &mut e.metadata,
-            DexEvent::SetComputeUnitPriceEvent(e) => &mut e.metadata,
+            DexEvent::BonkPoolStateAccountEvent(e) => {
+                let pool_state = &e.pool_state;
+                write!(f, " pool={}", e.base.pubkey)?;
+                match pool_state.status() {
+                    Some(status) => write!(f, " status={status:?}")?,
+                    None => write!(f, " status={}", pool_state.status)?,
+                }
+                write!(f, " progress={:.0}%", pool_state.migration_progress() * 100.0)?;
+            }
+            DexEvent::BonkGlobalConfigAccountEvent(e) => {
+                let config = &e.global_config;
+                write!(f, " config={} index={}", e.base.pubkey, config.index)?;
+                match config.curve_type() {
+                    Some(curve_type) => write!(f, " curve={curve_type:?}")?,
+                    None => write!(f, " curve={}", config.curve_type)?,
+                }
+                write!(f, " quote_mint={}", config.quote_mint)?;
+            }
+            DexEvent::BonkPlatformConfigAccountEvent(e) => {
+                let display = e.platform_config.to_display();
+                write!(
+                    f,
+                    " config={} name={:?} fee_rate={}",
+                    e.base.pubkey, display.name, display.fee_rate
+                )?;
+            }
+            DexEvent::BonkTradeEvent(e) => {
+                write!(f, " pool={} in={} out={}", e.pool_state, e.amount_in, e.amount_out)?;
+            }
+            _ => match self.account_pubkey() {
+                Some(pubkey) => write!(f, " account={pubkey}")?,
+                None => write!(f, " sig={}", self.signature())?,
+            },
+        }
+        write!(f, " slot={}", self.slot())
+    }
+}
+
+/// 可以从 `DexEvent` 中取出的具体事件类型，每个 `DexEvent` 变体对应一个实现
+pub trait TypedEvent: Sized + Send + 'static {
+    /// 取出对应变体中的事件，变体不匹配时原样返回（装箱以免 `Result` 过大）
+    fn from_dex_event(event: DexEvent) -> Result<Self, Box<DexEvent>>;
+}
+
+/// 账户的租金状态，由 `rent_epoch` 转换
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RentStatus {
+    /// `rent_epoch == u64::MAX`，账户免租
+    RentExempt,
+    /// 下一次收取租金的 epoch
+    Epoch(u64),
+}
+
+impl RentStatus {
+    pub fn from_rent_epoch(rent_epoch: u64) -> Self {
+        match rent_epoch {
+            u64::MAX => RentStatus::RentExempt,
+            epoch => RentStatus::Epoch(epoch),
+        }
+    }
+}
+
+/// 账户事件共有的链上账户字段
+pub trait AccountEvent {
+    /// 账户地址
+    fn pubkey(&self) -> Pubkey;
+    /// 账户所属的程序
+    fn owner(&self) -> Pubkey;
+    /// 账户余额
+    fn lamports(&self) -> u64;
+    /// 账户更新所在的 slot
+    fn slot(&self) -> u64;
+    /// 原始的 `rent_epoch`
+    fn rent_epoch(&self) -> u64;
+    /// 账户是否为可执行的程序账户
+    fn executable(&self) -> bool;
+
+    /// 账户的租金状态，`rent_epoch` 为 `u64::MAX` 时为 [`RentStatus::RentExempt`]
+    fn rent_status(&self) -> RentStatus {
+        RentStatus::from_rent_epoch(self.rent_epoch())
+    }
+}
+
+macro_rules! impl_account_event {
+    // 账户字段通过 `base: AccountEventBase` 嵌入的事件
+    (base: $($event:ty),* $(,)?) => {
+        $(impl_account_event!(@impl [.base] $event);)*
+    };
+    (@impl [$($base:tt)*] $event:ty) => {
+        impl AccountEvent for $event {
+            fn pubkey(&self) -> Pubkey {
+                self$($base)*.pubkey
+            }
+
+            fn owner(&self) -> Pubkey {
+                self$($base)*.owner
+            }
+
+            fn lamports(&self) -> u64 {
+                self$($base)*.lamports
+            }
+
+            fn slot(&self) -> u64 {
+                self.metadata.slot
+            }
+
+            fn rent_epoch(&self) -> u64 {
+                self$($base)*.rent_epoch
+            }
+
+            fn executable(&self) -> bool {
+                self$($base)*.executable
+            }
+        }
+    };
+    ($($event:ty),* $(,)?) => {
+        $(impl_account_event!(@impl [] $event);)*
+    };
+}
+
+impl_account_event!(
+    base: BonkPoolStateAccountEvent,
+    BonkGlobalConfigAccountEvent,
+    BonkPlatformConfigAccountEvent,
+);
+
+impl_account_event!(
+    PumpFunBondingCurveAccountEvent,
+    PumpFunGlobalAccountEvent,
+    PumpSwapGlobalConfigAccountEvent,
+    PumpSwapPoolAccountEvent,
+    RaydiumAmmV4AmmInfoAccountEvent,
+    RaydiumClmmAmmConfigAccountEvent,
+    RaydiumClmmPoolStateAccountEvent,
+    RaydiumClmmTickArrayStateAccountEvent,
+    RaydiumCpmmAmmConfigAccountEvent,
+    RaydiumCpmmPoolStateAccountEvent,
+    RaydiumLaunchpadPoolStateAccountEvent,
+    TokenAccountEvent,
+    NonceAccountEvent,
+    TokenInfoEvent,
+    UnknownAccountEvent,
+);
+
+/// 已关闭的账户不再持有 lamports，也不再有 `rent_epoch`（视为 0）且不可执行
+impl AccountEvent for AccountClosedEvent {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn owner(&self) -> Pubkey {
+        self.owner
+    }
+
+    fn lamports(&self) -> u64 {
+        0
+    }
+
+    fn slot(&self) -> u64 {
+        self.metadata.slot
+    }
+
+    fn rent_epoch(&self) -> u64 {
+        0
+    }
+
+    fn executable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_events() -> Vec<DexEvent> {
+        DexEvent::default_variants()
+            .into_iter()
+            .map(|mut event| {
+                let metadata = event.metadata_mut();
+                metadata.slot = 123_456;
+                metadata.signature = Signature::from([7; 64]);
+                metadata.write_version = Some(42);
+                event
+            })
+            .collect()
+    }
+
+    #[test]
+    fn metadata_is_read_back_from_one_event_per_protocol() {
+        use crate::streaming::event_parser::common::ProtocolType;
+
+        let cases = [
+            (ProtocolType::Bonk, EventType::BonkBuyExactIn),
+            (ProtocolType::PumpFun, EventType::PumpFunBuy),
+            (ProtocolType::PumpSwap, EventType::PumpSwapBuy),
+            (ProtocolType::RaydiumAmmV4, EventType::RaydiumAmmV4SwapBaseIn),
+            (ProtocolType::RaydiumClmm, EventType::RaydiumClmmSwap),
+            (ProtocolType::RaydiumCpmm, EventType::RaydiumCpmmSwapBaseInput),
+            (ProtocolType::MeteoraDammV2, EventType::MeteoraDammV2Swap),
+            (ProtocolType::Common, EventType::BlockMeta),
+        ];
+        for ((protocol, event_type), slot) in cases.into_iter().zip(1u64..) {
+            let metadata = EventMetadata { slot, protocol, event_type, ..Default::default() };
+            let mut event = match metadata.protocol {
+                ProtocolType::Bonk => DexEvent::BonkTradeEvent(BonkTradeEvent {
+                    metadata: metadata.clone(),
+                    ..Default::default()
+                }),
+                ProtocolType::PumpFun => DexEvent::PumpFunTradeEvent(PumpFunTradeEvent {
+                    metadata: metadata.clone(),
+                    ..Default::default()
+                }),
+                ProtocolType::PumpSwap => DexEvent::PumpSwapBuyEvent(PumpSwapBuyEvent {
+                    metadata: metadata.clone(),
+                    ..Default::default()
+                }),
+                ProtocolType::RaydiumAmmV4 => {
+                    DexEvent::RaydiumAmmV4SwapEvent(RaydiumAmmV4SwapEvent {
+                        metadata: metadata.clone(),
+                        ..Default::default()
+                    })
+                }
+                ProtocolType::RaydiumClmm => DexEvent::RaydiumClmmSwapEvent(RaydiumClmmSwapEvent {
+                    metadata: metadata.clone(),
+                    ..Default::default()
+                }),
+                ProtocolType::RaydiumCpmm => DexEvent::RaydiumCpmmSwapEvent(RaydiumCpmmSwapEvent {
+                    metadata: metadata.clone(),
+                    ..Default::default()
+                }),
+                ProtocolType::MeteoraDammV2 => {
+                    DexEvent::MeteoraDammV2SwapEvent(MeteoraDammV2SwapEvent {
+                        metadata: metadata.clone(),
+                        ..Default::default()
+                    })
+                }
+                ProtocolType::Common => DexEvent::BlockMetaEvent(BlockMetaEvent {
+                    metadata: metadata.clone(),
+                    ..Default::default()
+                }),
+            };
+            assert_eq!(event.metadata(), &metadata);
+            assert_eq!(event.event_type(), metadata.event_type);
+            assert_eq!(event.slot(), slot);
+
+            event.metadata_mut().slot += 100;
+            assert_eq!(event.slot(), slot + 100);
+        }
+    }
+
+    #[test]
+    fn content_hash_covers_significant_fields_only() {
+        let mut pool = BonkPoolStateAccountEvent::default();
+        pool.metadata.slot = 100;
+        pool.base.pubkey = Pubkey::new_unique();
+        pool.pool_state.virtual_base = 1_000;
+        let event = DexEvent::BonkPoolStateAccountEvent(pool);
+        let hash = event.content_hash();
+        assert_eq!(event.clone().content_hash(), hash);
+
+        // 接收/处理耗时不参与计算
+        let mut replayed = event.clone();
+        replayed.metadata_mut().recv_us = 1_700_000_000_000_000;
+        replayed.metadata_mut().handle_us = 250;
+        assert_eq!(replayed.content_hash(), hash);
+
+        let changes: [fn(&mut BonkPoolStateAccountEvent); 4] = [
+            |e| e.base.pubkey = Pubkey::new_unique(),
+            |e| e.metadata.slot += 1,
+            |e| e.pool_state.virtual_base += 1,
+            |e| e.metadata.signature = Signature::from([1; 64]),
+        ];
+        for (index, change) in changes.into_iter().enumerate() {
+            let mut changed = event.clone();
+            let DexEvent::BonkPoolStateAccountEvent(pool) = &mut changed else { unreachable!() };
+            change(pool);
+            assert_ne!(changed.content_hash(), hash, "change #{index}");
+        }
+    }
+
+    #[test]
+    fn display_summarizes_bonk_events_on_one_line() {
+        let address = Pubkey::new_from_array([1; 32]);
+        let metadata = |event_type| EventMetadata { slot: 77, event_type, ..Default::default() };
+
+        let mut pool = BonkPoolStateAccountEvent {
+            metadata: metadata(EventType::AccountBonkPoolState),
+            ..Default::default()
+        };
+        pool.base.pubkey = address;
+        pool.pool_state.real_quote = 42;
+        pool.pool_state.total_quote_fund_raising = 100;
+        let mut global = BonkGlobalConfigAccountEvent {
+            metadata: metadata(EventType::AccountBonkGlobalConfig),
+            ..Default::default()
+        };
+        global.base.pubkey = address;
+        global.global_config.index = 3;
+        global.global_config.curve_type = 9;
+        let mut platform = BonkPlatformConfigAccountEvent {
+            metadata: metadata(EventType::AccountBonkPlatformConfig),
+            ..Default::default()
+        };
+        platform.base.pubkey = address;
+        platform.platform_config.name[..4].copy_from_slice(b"bonk");
+        platform.platform_config.fee_rate = 100;
+        let trade = BonkTradeEvent {
+            metadata: metadata(EventType::BonkBuyExactIn),
+            pool_state: address,
+            amount_in: 5,
+            amount_out: 6,
+            ..Default::default()
+        };
+        let default = Pubkey::default();
+        let cases = [
+            (
+                DexEvent::BonkPoolStateAccountEvent(pool),
+                format!("AccountBonkPoolState pool={address} status=Fund progress=42% slot=77"),
+            ),
+            (
+                DexEvent::BonkGlobalConfigAccountEvent(global),
+                // 未知的曲线类型输出原始值
+                format!(
+                    "AccountBonkGlobalConfig config={address} index=3 curve=9 \
+                     quote_mint={default} slot=77"
+                ),
+            ),
+            (
+                DexEvent::BonkPlatformConfigAccountEvent(platform),
+                format!(
+                    "AccountBonkPlatformConfig config={address} name=\"bonk\" fee_rate=100 slot=77"
+                ),
+            ),
+            (
+                DexEvent::BonkTradeEvent(trade),
+                format!("BonkBuyExactIn pool={address} in=5 out=6 slot=77"),
+            ),
+        ];
+        for (event, summary) in cases {
+            assert_eq!(event.to_string(), summary);
+        }
+    }
+
+    #[test]
+    fn display_falls_back_to_account_or_signature() {
+        for event in sample_events() {
+            let summary = event.to_string();
+            assert!(!summary.contains('\n'), "{summary}");
+            assert!(summary.starts_with(&event.event_type().to_string()), "{summary}");
+            assert!(summary.ends_with(" slot=123456"), "{summary}");
+            let key = match event.account_pubkey() {
+                Some(pubkey) => format!(" account={pubkey}"),
+                None => format!(" sig={}", event.signature()),
+            };
+            let specialized = matches!(
+                event,
+                DexEvent::BonkPoolStateAccountEvent(_)
+                    | DexEvent::BonkGlobalConfigAccountEvent(_)
+                    | DexEvent::BonkPlatformConfigAccountEvent(_)
+                    | DexEvent::BonkTradeEvent(_)
+            );
+            assert_eq!(summary.contains(&key), !specialized, "{summary}");
+        }
+    }
+
+    #[test]
+    fn json_value_is_type_tagged_with_camel_case_keys() {
+        fn snake_case_keys(value: &serde_json::Value) -> Vec<String> {
+            match value {
+                serde_json::Value::Object(map) => map
+                    .iter()
+                    .flat_map(|(key, value)| {
+                        let nested = snake_case_keys(value);
+                        key.contains('_').then(|| key.clone()).into_iter().chain(nested)
+                    })
+                    .collect(),
+                serde_json::Value::Array(items) => items.iter().flat_map(snake_case_keys).collect(),
+                _ => vec![],
+            }
+        }
+
+        let mut pool = BonkPoolStateAccountEvent::default();
+        pool.metadata.slot = 77;
+        pool.metadata.event_type = EventType::AccountBonkPoolState;
+        pool.base.pubkey = Pubkey::new_from_array([1; 32]);
+        pool.pool_state.virtual_base = 1_000;
+        let event = DexEvent::BonkPoolStateAccountEvent(pool);
+
+        let json = event.to_json_value().unwrap();
+        assert_eq!(json["type"], "BonkPoolStateAccountEvent");
+        let data = &json["data"];
+        assert_eq!(data["metadata"]["slot"], 77);
+        assert_eq!(data["metadata"]["eventType"], "AccountBonkPoolState");
+        assert_eq!(data["poolState"]["virtualBase"], 1_000);
+        assert!(data.get("baseTokenProgram").is_some());
+        assert_eq!(data["pubkey"], serde_json::to_value(Pubkey::new_from_array([1; 32])).unwrap());
+        assert_eq!(snake_case_keys(&json), Vec::<String>::new());
+
+        // 原有的序列化格式不变
+        let plain = serde_json::to_value(&event).unwrap();
+        assert_eq!(plain["BonkPoolStateAccountEvent"]["pool_state"]["virtual_base"], 1_000);
+    }
+
+    #[test]
+    fn account_fields_are_read_through_the_trait() {
+        let (pool, config, closed, owner) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let metadata = |slot| EventMetadata { slot, ..Default::default() };
+
+        let mut bonk = BonkPoolStateAccountEvent { metadata: metadata(10), ..Default::default() };
+        bonk.base.pubkey = pool;
+        bonk.base.owner = owner;
+        bonk.base.lamports = 1_500;
+        let events = [
+            DexEvent::BonkPoolStateAccountEvent(bonk),
+            DexEvent::BonkTradeEvent(BonkTradeEvent {
+                metadata: metadata(11),
+                ..Default::default()
+            }),
+            DexEvent::PumpSwapPoolAccountEvent(PumpSwapPoolAccountEvent {
+                metadata: metadata(12),
+                pubkey: config,
+                owner,
+                lamports: 2_500,
+                ..Default::default()
+            }),
+            DexEvent::AccountClosedEvent(AccountClosedEvent {
+                metadata: metadata(13),
+                pubkey: closed,
+                owner,
+            }),
+        ];
+
+        let fields: Vec<_> = events
+            .iter()
+            .filter_map(DexEvent::as_account_event)
+            .map(|e| (e.pubkey(), e.owner(), e.lamports(), e.slot()))
+            .collect();
+        assert_eq!(
+            fields,
+            [(pool, owner, 1_500, 10), (config, owner, 2_500, 12), (closed, owner, 0, 13)]
+        );
+        assert_eq!(events[1].account_pubkey(), None);
+    }
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        fired: Vec<&'static str>,
+    }
+
+    impl EventHandler for RecordingHandler {
+        fn on_bonk_trade(&mut self, _: &BonkTradeEvent) {
+            self.fired.push("bonk_trade");
         }
+
+        fn on_bonk_pool_state_account(&mut self, _: &BonkPoolStateAccountEvent) {
+            self.fired.push("bonk_pool_state_account");
+        }
+
+        fn on_pumpswap_sell(&mut self, _: &PumpSwapSellEvent) {
+            self.fired.push("pumpswap_sell");
+        }
+    }
+
+    #[test]
+    fn dispatch_routes_each_event_to_its_callback() {
+        let mut handler = RecordingHandler::default();
+        let events = [
+            DexEvent::BonkPoolStateAccountEvent(Default::default()),
+            DexEvent::PumpSwapSellEvent(Default::default()),
+            // 未实现的回调默认为空
+            DexEvent::PumpSwapBuyEvent(Default::default()),
+            DexEvent::BonkTradeEvent(Default::default()),
+        ];
+        for event in &events {
+            dispatch(event, &mut handler);
+        }
+        assert_eq!(handler.fired, ["bonk_pool_state_account", "pumpswap_sell", "bonk_trade"]);
+
+        // 所有变体都能分发，且最多触发一个回调
+        let mut handler = RecordingHandler::default();
+        for event in sample_events() {
+            let before = handler.fired.len();
+            dispatch(&event, &mut handler as &mut dyn EventHandler);
+            assert!(handler.fired.len() - before <= 1, "{event:?}");
+        }
+        assert_eq!(handler.fired.len(), 3);
+    }
+
+    #[test]
+    fn every_variant_round_trips() {
+        for (tag, event) in sample_events().into_iter().enumerate() {
+            let frame = event.encode();
+            assert_eq!(frame[..2], [EVENT_ENCODING_VERSION, tag as u8]);
+            assert_eq!(DexEvent::decode(&frame).unwrap(), event);
+        }
+    }
+
+    #[test]
+    fn existing_variant_tags_are_stable() {
+        let tag = |event: DexEvent| event.encode()[1];
+        assert_eq!(tag(DexEvent::TokenAccountEvent(Default::default())), 48);
+        assert_eq!(tag(DexEvent::AccountClosedEvent(Default::default())), 57);
+        assert_eq!(tag(DexEvent::UnknownAccountEvent(Default::default())), 58);
+        assert_eq!(tag(DexEvent::BonkGlobalConfigChangedEvent(Default::default())), 59);
+        assert_eq!(tag(DexEvent::BonkImpliedTradeEvent(Default::default())), 60);
+    }
+
+    #[test]
+    fn decode_warnings_survive_the_frame() {
+        let mut event = BonkPoolStateAccountEvent::default();
+        event.decode_warnings.push("amm_creator_fee_on: unknown variant 9".to_owned());
+        let event = DexEvent::BonkPoolStateAccountEvent(event);
+        assert_eq!(DexEvent::decode(&event.encode()).unwrap(), event);
+    }
+
+    #[test]
+    fn truncated_frames_fail_cleanly() {
+        for event in sample_events() {
+            let frame = event.encode();
+            for len in 0..frame.len() {
+                assert!(DexEvent::decode(&frame[..len]).is_err(), "{len} of {} bytes", frame.len());
+            }
+        }
+    }
+
+    #[test]
+    fn foreign_version_and_unknown_tag_are_rejected() {
+        let mut frame = DexEvent::AccountClosedEvent(Default::default()).encode();
+        frame[0] = EVENT_ENCODING_VERSION + 1;
+        assert!(matches!(DexEvent::decode(&frame), Err(DecodeError::UnsupportedVersion { .. })));
+
+        frame[0] = EVENT_ENCODING_VERSION;
+        frame[1] = u8::MAX;
+        assert!(matches!(DexEvent::decode(&frame), Err(DecodeError::UnknownVariant { tag: 255 })));
     }
 }
@@ -2,5 +2,7 @@ pub mod common;
 pub mod core;
 pub mod protocols;
 
+pub use common::filter::EventFilter;
+pub use core::account_event_parser::{decode_account_str, identify};
 pub use core::traits::DexEvent;
-pub use protocols::types::Protocol;
\ No newline at end of file
+pub use protocols::types::{Protocol, ProtocolSet};
\ No newline at end of file
@@ -0,0 +1,110 @@
+//! 由多个 data slice 拼装完整的 PoolState
+//!
+//! 使用 data slice 订阅时，每次只收到账户数据的一个窗口。`PoolStateAssembler`
+//! 按账户地址合并这些窗口，记录已知的字节区间，数据齐全后解码出完整的 `PoolState`。
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::event_parser::protocols::bonk::pool_state_offsets::{
+    ACCOUNT_LEN as POOL_STATE_ACCOUNT_LEN, DISCRIMINATOR_LEN,
+};
+use crate::streaming::event_parser::protocols::bonk::{
+    discriminators, pool_state_decode, PoolState,
+};
+
+/// 单个账户的部分数据
+#[derive(Debug, Clone)]
+struct PartialPoolState {
+    data: Vec<u8>,
+    /// 已知字节区间，按起点排序且互不重叠
+    known: Vec<Range<usize>>,
+}
+
+impl PartialPoolState {
+    fn new() -> Self {
+        Self { data: vec![0u8; POOL_STATE_ACCOUNT_LEN], known: Vec::new() }
+    }
+
+    fn insert(&mut self, offset: usize, bytes: &[u8]) {
+        if offset >= POOL_STATE_ACCOUNT_LEN || bytes.is_empty() {
+            return;
+        }
+        let end = (offset + bytes.len()).min(POOL_STATE_ACCOUNT_LEN);
+        self.data[offset..end].copy_from_slice(&bytes[..end - offset]);
+        self.add_range(offset..end);
+    }
+
+    /// 插入区间并合并重叠或相邻的区间
+    fn add_range(&mut self, range: Range<usize>) {
+        let mut merged = range;
+        let mut result = Vec::with_capacity(self.known.len() + 1);
+        for r in self.known.drain(..) {
+            if r.end < merged.start || r.start > merged.end {
+                result.push(r);
+            } else {
+                merged = merged.start.min(r.start)..merged.end.max(r.end);
+            }
+        }
+        result.push(merged);
+        result.sort_by_key(|r| r.start);
+        self.known = result;
+    }
+
+    fn is_complete(&self) -> bool {
+        self.known.len() == 1 && self.known[0] == (0..POOL_STATE_ACCOUNT_LEN)
+    }
+}
+
+/// PoolState 数据分片拼装器
+#[derive(Debug, Default)]
+pub struct PoolStateAssembler {
+    accounts: HashMap<Pubkey, PartialPoolState>,
+}
+
+impl PoolStateAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 合并一个数据分片
+    ///
+    /// `offset` 为分片在账户数据中的起始位置（包含 8 字节 discriminator）。
+    /// 数据齐全且解码成功时返回完整的 `PoolState`，否则返回 `None`
+    pub fn insert_slice(
+        &mut self,
+        pubkey: Pubkey,
+        offset: usize,
+        bytes: &[u8],
+    ) -> Option<PoolState> {
+        let partial = self.accounts.entry(pubkey).or_insert_with(PartialPoolState::new);
+        partial.insert(offset, bytes);
+        if !partial.is_complete()
+            || partial.data[..DISCRIMINATOR_LEN] != discriminators::POOL_STATE_ACCOUNT[..]
+        {
+            return None;
+        }
+        pool_state_decode(&partial.data[DISCRIMINATOR_LEN..]).ok()
+    }
+
+    /// 获取账户已知的字节区间
+    pub fn known_ranges(&self, pubkey: &Pubkey) -> Option<&[Range<usize>]> {
+        self.accounts.get(pubkey).map(|p| p.known.as_slice())
+    }
+
+    /// 检查账户数据是否已齐全
+    pub fn is_complete(&self, pubkey: &Pubkey) -> bool {
+        self.accounts.get(pubkey).is_some_and(|p| p.is_complete())
+    }
+
+    /// 移除账户的缓存数据
+    pub fn remove(&mut self, pubkey: &Pubkey) {
+        self.accounts.remove(pubkey);
+    }
+
+    pub fn clear(&mut self) {
+        self.accounts.clear();
+    }
+}
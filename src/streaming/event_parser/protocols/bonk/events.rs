@@ -1,12 +1,14 @@
 use crate::streaming::event_parser::common::EventMetadata;
 use crate::streaming::event_parser::protocols::bonk::types::{
     CurveParams, MintParams, PoolStatus, TradeDirection, VestingParams,
+    GLOBAL_CONFIG_DISCRIMINATOR, PLATFORM_CONFIG_DISCRIMINATOR, POOL_STATE_DISCRIMINATOR,
 };
 use crate::streaming::event_parser::protocols::bonk::{
-    AmmFeeOn, GlobalConfig, PlatformConfig, PoolState,
+    AmmFeeOn, FieldChange, GlobalConfig, PlatformConfig, PoolState,
 };
+use crate::streaming::grpc::AccountEventBase;
 use borsh::BorshDeserialize;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use solana_sdk::pubkey::Pubkey;
 
 /// Trade event
@@ -69,6 +71,15 @@ pub struct BonkTradeEvent {
     pub platform_associated_account: Pubkey,
     #[borsh(skip)]
     pub creator_associated_account: Pubkey,
+    /// 交易设置的计算单元价格（micro-lamports / CU），未设置时为 0
+    #[borsh(skip)]
+    pub compute_unit_price: u64,
+    /// 交易生效的计算单元上限
+    #[borsh(skip)]
+    pub compute_unit_limit: u32,
+    /// 交易支付的优先费（micro-lamports），`compute_unit_price * compute_unit_limit`
+    #[borsh(skip)]
+    pub priority_fee_microlamports: u64,
 }
 
 pub const BONK_TRADE_EVENT_LOG_SIZE: usize = 32 + 8 * 13 + 1 + 1 + 1;
@@ -229,40 +240,148 @@ pub struct BonkMigrateToCpswapEvent {
 
 /// 池状态
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct BonkPoolStateAccountEvent {
     pub metadata: EventMetadata,
-    pub pubkey: Pubkey,
-    pub executable: bool,
-    pub lamports: u64,
-    pub owner: Pubkey,
-    pub rent_epoch: u64,
+    #[serde(flatten)]
+    pub base: AccountEventBase,
+    /// base mint 所属的 token program，由 `pool_state.token_program_flag` 解析
+    pub base_token_program: Pubkey,
     pub pool_state: PoolState,
+    /// 解码时被替换为默认值的字段，见 `pool_state_decode_tolerant`，正常解码时为空
+    #[serde(default)]
+    pub decode_warnings: Vec<String>,
 }
 
 /// 全局配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct BonkGlobalConfigAccountEvent {
     pub metadata: EventMetadata,
-    pub pubkey: Pubkey,
-    pub executable: bool,
-    pub lamports: u64,
-    pub owner: Pubkey,
-    pub rent_epoch: u64,
+    #[serde(flatten)]
+    pub base: AccountEventBase,
     pub global_config: GlobalConfig,
 }
 
 /// 平台配置
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct BonkPlatformConfigAccountEvent {
     pub metadata: EventMetadata,
-    pub pubkey: Pubkey,
-    pub executable: bool,
-    pub lamports: u64,
-    pub owner: Pubkey,
-    pub rent_epoch: u64,
+    #[serde(flatten)]
+    pub base: AccountEventBase,
     pub platform_config: PlatformConfig,
 }
 
+// `#[serde(flatten)]` 需要按 map 编码，bincode 不支持（事件帧和 `content_hash` 使用 bincode）。
+// 账户事件的派生实现（`remote = "Self"`）只用于 JSON 等可读格式，二进制格式使用下面不展开
+// `base` 的同构结构：bincode 中嵌套结构体按字段依次编码，帧格式与展开前的扁平字段相同
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "BonkPoolStateAccountEvent")]
+struct BonkPoolStateAccountFrame {
+    metadata: EventMetadata,
+    base: AccountEventBase,
+    base_token_program: Pubkey,
+    pool_state: PoolState,
+    #[serde(default)]
+    decode_warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "BonkGlobalConfigAccountEvent")]
+struct BonkGlobalConfigAccountFrame {
+    metadata: EventMetadata,
+    base: AccountEventBase,
+    global_config: GlobalConfig,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "BonkPlatformConfigAccountEvent")]
+struct BonkPlatformConfigAccountFrame {
+    metadata: EventMetadata,
+    base: AccountEventBase,
+    platform_config: PlatformConfig,
+}
+
+/// 可读格式使用 `$event` 派生的实现（展开 `base`），二进制格式使用 `$frame`
+macro_rules! account_event_serde {
+    ($($event:ident => $frame:ident),* $(,)?) => {
+        $(
+            impl Serialize for $event {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    if serializer.is_human_readable() {
+                        $event::serialize(self, serializer)
+                    } else {
+                        $frame::serialize(self, serializer)
+                    }
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $event {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    if deserializer.is_human_readable() {
+                        $event::deserialize(deserializer)
+                    } else {
+                        $frame::deserialize(deserializer)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+account_event_serde! {
+    BonkPoolStateAccountEvent => BonkPoolStateAccountFrame,
+    BonkGlobalConfigAccountEvent => BonkGlobalConfigAccountFrame,
+    BonkPlatformConfigAccountEvent => BonkPlatformConfigAccountFrame,
+}
+
+/// 池子进入 `Trade` 状态（可在 AMM 交易），由 `TradingLiveTracker` 根据池状态变化生成
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BonkTradingLiveEvent {
+    pub metadata: EventMetadata,
+    /// 池子账户地址
+    pub pubkey: Pubkey,
+    /// base mint
+    pub mint: Pubkey,
+    /// 观察到状态变化的 slot
+    pub slot: u64,
+}
+
+/// `GlobalConfig` 的字段发生变化，由 `GlobalConfigChangeTracker` 比较相邻两次快照生成
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BonkGlobalConfigChangedEvent {
+    pub metadata: EventMetadata,
+    /// `GlobalConfig` 账户地址
+    pub pubkey: Pubkey,
+    /// 发生变化的字段，见 [`GlobalConfig::diff`]
+    pub changes: Vec<FieldChange>,
+    /// 观察到变化的 slot
+    pub slot: u64,
+}
+
+/// 由相邻两次池状态的储备变化推断出的交易，由 `PoolTradeTracker` 生成
+///
+/// 两次快照之间的多笔交易会合并为一笔净变化，方向相反的交易相互抵消
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BonkImpliedTradeEvent {
+    pub metadata: EventMetadata,
+    /// 池子账户地址
+    pub pubkey: Pubkey,
+    /// base mint
+    pub base_mint: Pubkey,
+    pub direction: TradeDirection,
+    /// 交易的 base 数量（`real_base` 变化的绝对值），单位为 base 最小单位
+    pub base_amount: u64,
+    /// 交易的 quote 数量（`real_quote` 变化的绝对值），单位为 quote 最小单位
+    pub quote_amount: u64,
+    /// 交易后的定点价格，见 [`PoolState::price_fixed`]
+    pub price_fixed: Option<u128>,
+    /// 观察到变化的 slot
+    pub slot: u64,
+}
+
 /// Event discriminator constants
 pub mod discriminators {
     // Event discriminators
@@ -285,7 +404,57 @@ pub mod discriminators {
     pub const MIGRATE_TO_CP_SWAP: &[u8] = &[136, 92, 200, 103, 28, 218, 144, 140];
 
     // 账户鉴别器
-    pub const POOL_STATE_ACCOUNT: &[u8] = &[247, 237, 227, 245, 215, 195, 222, 70];
-    pub const GLOBAL_CONFIG_ACCOUNT: &[u8] = &[149, 8, 156, 202, 160, 252, 176, 217];
-    pub const PLATFORM_CONFIG_ACCOUNT: &[u8] = &[160, 78, 128, 0, 248, 83, 230, 160];
+    pub const POOL_STATE_ACCOUNT: &[u8] = &super::POOL_STATE_DISCRIMINATOR;
+    pub const GLOBAL_CONFIG_ACCOUNT: &[u8] = &super::GLOBAL_CONFIG_DISCRIMINATOR;
+    pub const PLATFORM_CONFIG_ACCOUNT: &[u8] = &super::PLATFORM_CONFIG_DISCRIMINATOR;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::DexEvent;
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn pool_state_event() -> BonkPoolStateAccountEvent {
+        let account = AccountPrettyBuilder::new().lamports(42).build();
+        BonkPoolStateAccountEvent {
+            base: AccountEventBase { executable: true, rent_epoch: u64::MAX, ..account.base() },
+            base_token_program: Pubkey::new_unique(),
+            decode_warnings: vec!["amm_creator_fee_on: unknown variant 9".to_owned()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn base_fields_are_flat_in_json_and_round_trip() {
+        let event = pool_state_event();
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["pubkey"], serde_json::to_value(event.base.pubkey).unwrap());
+        assert_eq!(json["lamports"], 42);
+        assert_eq!(json["executable"], true);
+        assert_eq!(json["rent_epoch"], u64::MAX);
+        assert!(json.get("base").is_none(), "{json}");
+        assert_eq!(serde_json::from_value::<BonkPoolStateAccountEvent>(json).unwrap(), event);
+    }
+
+    #[test]
+    fn base_fields_round_trip_through_the_binary_frame() {
+        let event = pool_state_event();
+        // 二进制编码与展开前的扁平字段一致
+        let flat = (
+            &event.metadata,
+            event.base.pubkey,
+            event.base.executable,
+            event.base.lamports,
+            event.base.owner,
+            event.base.rent_epoch,
+            event.base_token_program,
+            &event.pool_state,
+            &event.decode_warnings,
+        );
+        assert_eq!(bincode::serialize(&event).unwrap(), bincode::serialize(&flat).unwrap());
+
+        let event = DexEvent::BonkPoolStateAccountEvent(event);
+        assert_eq!(DexEvent::decode(&event.encode()).unwrap(), event);
+    }
 }
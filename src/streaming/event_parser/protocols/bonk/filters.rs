@@ -0,0 +1,84 @@
+//! Bonk 账户订阅的服务端过滤条件
+//!
+//! 生成 gRPC 账户订阅所需的 `AccountFilter`：限定 owner 为 Bonk 程序、discriminator 为
+//! `PoolState`，再按指定字段做 memcmp 匹配，只有符合条件的池子账户会被服务端推送，
+//! 相比客户端过滤可以大幅减少带宽。
+
+use solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_request_filter_accounts_filter::Filter,
+    subscribe_request_filter_accounts_filter_memcmp::Data, SubscribeRequestFilterAccountsFilter,
+    SubscribeRequestFilterAccountsFilterMemcmp,
+};
+
+use super::parser::BONK_PROGRAM_ID;
+use super::types::{pool_state_offsets, POOL_STATE_DISCRIMINATOR};
+use crate::streaming::yellowstone_grpc::AccountFilter;
+
+/// 在账户数据 `offset` 处（含 discriminator）匹配 `bytes` 的 memcmp 过滤条件
+pub fn memcmp_filter(offset: usize, bytes: &[u8]) -> SubscribeRequestFilterAccountsFilter {
+    SubscribeRequestFilterAccountsFilter {
+        filter: Some(Filter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+            offset: offset as u64,
+            data: Some(Data::Bytes(bytes.to_vec())),
+        })),
+    }
+}
+
+/// 只匹配 `PoolState` 账户中 `offset` 处的字段等于 `pubkey` 的过滤器
+fn pool_state_filter_by_pubkey(offset: usize, pubkey: &Pubkey) -> AccountFilter {
+    AccountFilter {
+        account: vec![],
+        owner: vec![BONK_PROGRAM_ID.to_string()],
+        filters: vec![
+            memcmp_filter(0, &POOL_STATE_DISCRIMINATOR),
+            memcmp_filter(offset, pubkey.as_ref()),
+        ],
+    }
+}
+
+/// 只订阅由 `creator` 创建的 `PoolState` 账户
+pub fn pool_state_filter_by_creator(creator: &Pubkey) -> AccountFilter {
+    pool_state_filter_by_pubkey(pool_state_offsets::OFFSET_CREATOR, creator)
+}
+
+/// 只订阅 base mint 为 `base_mint` 的 `PoolState` 账户
+pub fn pool_state_filter_by_base_mint(base_mint: &Pubkey) -> AccountFilter {
+    pool_state_filter_by_pubkey(pool_state_offsets::OFFSET_BASE_MINT, base_mint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const SAMPLE: &str = "So11111111111111111111111111111111111111112";
+
+    fn memcmp(filter: &SubscribeRequestFilterAccountsFilter) -> (u64, &[u8]) {
+        match &filter.filter {
+            Some(Filter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+                offset,
+                data: Some(Data::Bytes(bytes)),
+            })) => (*offset, bytes),
+            other => panic!("not a memcmp filter: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn filters_match_the_field_after_the_discriminator() {
+        let pubkey = Pubkey::from_str(SAMPLE).unwrap();
+        let expected = bs58::decode(SAMPLE).into_vec().unwrap();
+        // 偏移包含 8 字节 discriminator：creator 和 base_mint 在结构体内分别位于 325 和 197
+        let cases = [
+            (pool_state_filter_by_creator(&pubkey), 333),
+            (pool_state_filter_by_base_mint(&pubkey), 205),
+        ];
+        for (filter, offset) in cases {
+            assert_eq!(filter.owner, vec![BONK_PROGRAM_ID.to_string()]);
+            assert!(filter.account.is_empty());
+            assert_eq!(filter.filters.len(), 2);
+            assert_eq!(memcmp(&filter.filters[0]), (0, &POOL_STATE_DISCRIMINATOR[..]));
+            assert_eq!(memcmp(&filter.filters[1]), (offset, &expected[..]));
+        }
+    }
+}
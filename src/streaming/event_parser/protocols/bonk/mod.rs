@@ -1,6 +1,14 @@
+pub mod assembler;
 pub mod events;
+pub mod filters;
 pub mod parser;
 pub mod types;
 
+pub use assembler::PoolStateAssembler;
 pub use events::*;
+pub use filters::{memcmp_filter, pool_state_filter_by_base_mint, pool_state_filter_by_creator};
+pub use parser::{
+    parse_bonk_account, parse_bonk_account_with_owner_check, parse_bonk_instruction,
+    BonkPoolStateParser, BONK_PROGRAM_ID,
+};
 pub use types::*;
@@ -1,20 +1,26 @@
 use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::event_parser::{
-    common::{utils::*, EventMetadata, EventType},
+    common::{utils::*, EventMetadata, EventType, ProtocolType},
+    core::AccountParser,
     protocols::bonk::{
-        bonk_pool_create_event_log_decode, bonk_trade_event_log_decode, discriminators, AmmFeeOn,
+        bonk_pool_create_event_log_decode, bonk_trade_event_log_decode, discriminators,
+        global_config_parser, platform_config_parser, pool_state_parser_with_options, AmmFeeOn,
         BonkMigrateToAmmEvent, BonkMigrateToCpswapEvent, BonkPoolCreateEvent, BonkTradeEvent,
-        ConstantCurve, CurveParams, FixedCurve, LinearCurve, MintParams, TradeDirection,
-        VestingParams,
+        ConstantCurve, CurveParams, FixedCurve, LinearCurve, MintParams, PoolStateParseOptions,
+        TradeDirection, VestingParams, GLOBAL_CONFIG_DISCRIMINATOR, PLATFORM_CONFIG_DISCRIMINATOR,
+        POOL_STATE_DISCRIMINATOR,
     },
     DexEvent,
 };
+use crate::streaming::grpc::AccountPretty;
 
 /// Bonk Program ID
 pub const BONK_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
 
+pub use crate::decode::bonk::BONK_DISCRIMINATOR_SCHEME;
+
 /// 解析 Bonk instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
@@ -56,6 +62,29 @@ pub fn parse_bonk_instruction_data(
     }
 }
 
+/// 解析一条完整的 Bonk 交易 instruction（含 8 字节判别器），只处理 buy/sell
+///
+/// 与 [`parse_bonk_instruction_data`] 相同的解码逻辑，供直接拿到原始 instruction 的调用方使用：
+/// 解码 amount-in / min-amount-out 等参数，并从账户列表中取出 payer、pool_state 等账户，
+/// 其他 instruction 或数据、账户不足时返回 `None`
+pub fn parse_bonk_instruction(
+    ix_data: &[u8],
+    accounts: &[Pubkey],
+    mut metadata: EventMetadata,
+) -> Option<DexEvent> {
+    let (discriminator, data) = ix_data.split_at_checked(8)?;
+    match discriminator {
+        discriminators::BUY_EXACT_IN
+        | discriminators::BUY_EXACT_OUT
+        | discriminators::SELL_EXACT_IN
+        | discriminators::SELL_EXACT_OUT => {
+            metadata.protocol = ProtocolType::Bonk;
+            parse_bonk_instruction_data(discriminator, data, accounts, metadata)
+        }
+        _ => None,
+    }
+}
+
 /// 解析 Bonk inner instruction data
 ///
 /// 根据判别器路由到具体的 inner instruction 解析函数
@@ -97,6 +126,99 @@ pub fn parse_bonk_account_data(
     }
 }
 
+/// 解析任意 Bonk 账户
+///
+/// 先校验 owner 为 Bonk 程序，再按 `account.data[..8]` 一次路由到对应的账户解码器，
+/// owner 不匹配、数据不足 8 字节或 discriminator 未知时返回 `None`
+pub fn parse_bonk_account(account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+    parse_bonk_account_with_owner_check(account, metadata, true)
+}
+
+/// 与 [`parse_bonk_account`] 相同，`verify_owner` 为 `false` 时跳过 owner 校验
+///
+/// 用于 owner 为占位地址的测试数据
+pub fn parse_bonk_account_with_owner_check(
+    account: &AccountPretty,
+    metadata: EventMetadata,
+    verify_owner: bool,
+) -> Option<DexEvent> {
+    if verify_owner && account.owner != BONK_PROGRAM_ID {
+        return None;
+    }
+    let discriminator = account.data.get(..8)?;
+    parse_bonk_account_data(discriminator, account, metadata)
+}
+
+/// 通过 `ParserRegistry` 注册的 `PoolState` 账户解析器
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BonkPoolStateParser {
+    options: PoolStateParseOptions,
+}
+
+impl BonkPoolStateParser {
+    pub fn new(options: PoolStateParseOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl AccountParser for BonkPoolStateParser {
+    fn discriminator(&self) -> [u8; 8] {
+        POOL_STATE_DISCRIMINATOR
+    }
+
+    fn program_id(&self) -> Pubkey {
+        BONK_PROGRAM_ID
+    }
+
+    fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+        pool_state_parser_with_options(account, metadata, &self.options)
+    }
+}
+
+/// 通过 `ParserRegistry` 注册的 `GlobalConfig` 账户解析器
+pub struct BonkGlobalConfigParser;
+
+impl AccountParser for BonkGlobalConfigParser {
+    fn discriminator(&self) -> [u8; 8] {
+        GLOBAL_CONFIG_DISCRIMINATOR
+    }
+
+    fn program_id(&self) -> Pubkey {
+        BONK_PROGRAM_ID
+    }
+
+    fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+        global_config_parser(account, metadata)
+    }
+}
+
+/// 通过 `ParserRegistry` 注册的 `PlatformConfig` 账户解析器
+pub struct BonkPlatformConfigParser;
+
+impl AccountParser for BonkPlatformConfigParser {
+    fn discriminator(&self) -> [u8; 8] {
+        PLATFORM_CONFIG_DISCRIMINATOR
+    }
+
+    fn program_id(&self) -> Pubkey {
+        BONK_PROGRAM_ID
+    }
+
+    fn parse(&self, account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+        platform_config_parser(account, metadata)
+    }
+}
+
+/// 只根据账户 discriminator 判断会命中的账户解析器，不解码账户数据
+pub fn identify_bonk_account(discriminator: &[u8]) -> Option<EventType> {
+    match discriminator {
+        discriminators::POOL_STATE_ACCOUNT => Some(EventType::AccountBonkPoolState),
+        discriminators::GLOBAL_CONFIG_ACCOUNT => Some(EventType::AccountBonkGlobalConfig),
+        discriminators::PLATFORM_CONFIG_ACCOUNT => Some(EventType::AccountBonkPlatformConfig),
+        _ => None,
+    }
+}
+
 /// Parse pool creation event
 fn parse_pool_create_inner_instruction(
     data: &[u8],
@@ -614,3 +736,134 @@ fn parse_migrate_to_cpswap_instruction(
         ..Default::default()
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::protocols::bonk::{
+        GlobalConfig, PlatformConfig, PoolState, GLOBAL_CONFIG_DISCRIMINATOR,
+        PLATFORM_CONFIG_DISCRIMINATOR, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn bonk_account<T: borsh::BorshSerialize>(discriminator: [u8; 8], value: &T) -> AccountPretty {
+        AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(discriminator)
+            .borsh_data(value)
+            .build()
+    }
+
+    /// 交易 instruction 数据：判别器 + amount_in + minimum_amount_out + share_fee_rate
+    fn trade_instruction(discriminator: &[u8], amount_in: u64, minimum_amount_out: u64) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        for value in [amount_in, minimum_amount_out, 25] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn buy_and_sell_instructions_become_trade_events() {
+        let accounts: Vec<_> = (0..18).map(|_| Pubkey::new_unique()).collect();
+        let cases = [
+            (discriminators::BUY_EXACT_IN, EventType::BonkBuyExactIn, TradeDirection::Buy),
+            (discriminators::SELL_EXACT_IN, EventType::BonkSellExactIn, TradeDirection::Sell),
+        ];
+        for (discriminator, event_type, direction) in cases {
+            let data = trade_instruction(discriminator, 1_000_000, 950_000);
+            let event = parse_bonk_instruction(&data, &accounts, EventMetadata::default());
+            let Some(DexEvent::BonkTradeEvent(trade)) = event else {
+                panic!("expected a trade event, got {event:?}");
+            };
+            assert_eq!(trade.metadata.event_type, event_type);
+            assert_eq!(trade.metadata.protocol, ProtocolType::Bonk);
+            assert_eq!(trade.trade_direction, direction);
+            assert_eq!((trade.amount_in, trade.minimum_amount_out), (1_000_000, 950_000));
+            assert_eq!(trade.share_fee_rate, 25);
+            assert_eq!(trade.payer, accounts[0]);
+            assert_eq!(trade.pool_state, accounts[4]);
+            assert_eq!(
+                (trade.base_token_mint, trade.quote_token_mint),
+                (accounts[9], accounts[10])
+            );
+        }
+    }
+
+    #[test]
+    fn other_and_incomplete_instructions_are_not_trades() {
+        let accounts: Vec<_> = (0..18).map(|_| Pubkey::new_unique()).collect();
+        let buy = trade_instruction(discriminators::BUY_EXACT_IN, 1, 1);
+        let metadata = EventMetadata::default;
+
+        let initialize = trade_instruction(discriminators::INITIALIZE, 1, 1);
+        assert!(parse_bonk_instruction(&initialize, &accounts, metadata()).is_none());
+        assert!(parse_bonk_instruction(&buy[..7], &accounts, metadata()).is_none());
+        assert!(parse_bonk_instruction(&buy[..buy.len() - 1], &accounts, metadata()).is_none());
+        assert!(parse_bonk_instruction(&buy, &accounts[..17], metadata()).is_none());
+    }
+
+    #[test]
+    fn each_account_type_is_routed_by_discriminator() {
+        let pool_state = PoolState { virtual_base: 7, ..Default::default() };
+        let global_config = GlobalConfig { index: 3, ..Default::default() };
+        let platform_config = PlatformConfig { fee_rate: 9, ..Default::default() };
+
+        let event = parse_bonk_account(
+            &bonk_account(POOL_STATE_DISCRIMINATOR, &pool_state),
+            EventMetadata::default(),
+        );
+        let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+            panic!("expected a pool state event, got {event:?}");
+        };
+        assert_eq!(event.pool_state, pool_state);
+        assert_eq!(event.metadata.event_type, EventType::AccountBonkPoolState);
+
+        let event = parse_bonk_account(
+            &bonk_account(GLOBAL_CONFIG_DISCRIMINATOR, &global_config),
+            EventMetadata::default(),
+        );
+        let Some(DexEvent::BonkGlobalConfigAccountEvent(event)) = event else {
+            panic!("expected a global config event, got {event:?}");
+        };
+        assert_eq!(event.global_config, global_config);
+
+        let event = parse_bonk_account(
+            &bonk_account(PLATFORM_CONFIG_DISCRIMINATOR, &platform_config),
+            EventMetadata::default(),
+        );
+        let Some(DexEvent::BonkPlatformConfigAccountEvent(event)) = event else {
+            panic!("expected a platform config event, got {event:?}");
+        };
+        assert_eq!(event.platform_config, platform_config);
+    }
+
+    #[test]
+    fn unknown_discriminator_and_short_data_are_not_parsed() {
+        let unknown = bonk_account([1; 8], &PoolState::default());
+        assert!(parse_bonk_account(&unknown, EventMetadata::default()).is_none());
+
+        let short = AccountPrettyBuilder::new().owner(BONK_PROGRAM_ID).data(vec![247; 7]).build();
+        assert!(parse_bonk_account(&short, EventMetadata::default()).is_none());
+    }
+
+    #[test]
+    fn owner_check_is_optional() {
+        let pool_state = PoolState::default();
+        let bonk_owned = bonk_account(POOL_STATE_DISCRIMINATOR, &pool_state);
+        let placeholder = AccountPrettyBuilder::new()
+            .owner(Pubkey::new_unique())
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(&pool_state)
+            .build();
+
+        let parse = |account, verify_owner| {
+            parse_bonk_account_with_owner_check(account, EventMetadata::default(), verify_owner)
+        };
+        assert!(parse(&bonk_owned, true).is_some());
+        assert!(parse(&bonk_owned, false).is_some());
+        assert!(parse(&placeholder, true).is_none());
+        assert!(parse(&placeholder, false).is_some());
+        assert!(parse_bonk_account(&placeholder, EventMetadata::default()).is_none());
+    }
+}
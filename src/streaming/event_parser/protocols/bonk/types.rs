@@ -1,10 +1,18 @@
-use borsh::BorshDeserialize;
+use std::borrow::Cow;
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::{
+    common::{mint_info, MintInfo},
     event_parser::{
-        common::{EventMetadata, EventType},
+        common::{
+            checked_mul_div, try_decode_account_body, verify_discriminator, BorshReader,
+            DecodeError, EventMetadata, EventType, ReserveSnapshot,
+        },
+        core::ParserMetrics,
         protocols::bonk::{
             BonkGlobalConfigAccountEvent, BonkPlatformConfigAccountEvent, BonkPoolStateAccountEvent,
         },
@@ -13,22 +21,44 @@ use crate::streaming::{
     grpc::AccountPretty,
 };
 
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub use crate::decode::bonk::*;
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
 pub enum TradeDirection {
     #[default]
     Buy,
     Sell,
 }
 
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub enum PoolStatus {
-    #[default]
-    Fund,
-    Migrate,
-    Trade,
+impl TradeDirection {
+    /// 由交易者一侧的 base/quote 变化推断交易方向
+    ///
+    /// 交易者 base 增加、quote 减少为 `Buy`，base 减少、quote 增加为 `Sell`；
+    /// 任一变化为 0 或两者同向时无法判断，返回 `None`。
+    /// 池子一侧的变化需要取反后传入
+    pub fn infer(base_delta: i128, quote_delta: i128) -> Option<TradeDirection> {
+        match (base_delta.signum(), quote_delta.signum()) {
+            (1, -1) => Some(TradeDirection::Buy),
+            (-1, 1) => Some(TradeDirection::Sell),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
 pub struct MintParams {
     pub decimals: u8,
     pub name: String,
@@ -36,208 +66,884 @@ pub struct MintParams {
     pub uri: String,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
 pub struct VestingParams {
     pub total_locked_amount: u64,
     pub cliff_period: u64,
     pub unlock_period: u64,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub enum AmmFeeOn {
-    QuoteToken,
-    BothToken,
+impl VestingParams {
+    /// 计算在 `now` 时刻已解锁的数量（`start_time` 为锁仓开始时间，即迁移时间）
+    ///
+    /// 锁仓开始后经过 `cliff_period` 才开始解锁，之后在 `unlock_period` 内线性解锁
+    pub fn unlocked_at(&self, start_time: u64, now: u64) -> u64 {
+        linear_unlocked(
+            self.total_locked_amount,
+            start_time,
+            self.cliff_period,
+            self.unlock_period,
+            now,
+        )
+    }
 }
 
-impl Default for AmmFeeOn {
-    fn default() -> Self {
-        Self::QuoteToken
+fn linear_unlocked(
+    total: u64,
+    start_time: u64,
+    cliff_period: u64,
+    unlock_period: u64,
+    now: u64,
+) -> u64 {
+    if start_time == 0 {
+        return 0;
     }
+    let unlock_start = start_time.saturating_add(cliff_period);
+    if now < unlock_start {
+        return 0;
+    }
+    let elapsed = now - unlock_start;
+    if unlock_period == 0 || elapsed >= unlock_period {
+        return total;
+    }
+    // elapsed < unlock_period，结果不会超过 total
+    checked_mul_div(total, elapsed, unlock_period).unwrap_or(total)
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-#[borsh(use_discriminant = true)]
-#[repr(u8)]
-pub enum AmmCreatorFeeOn {
-    QuoteToken = 0,
-    BothToken = 1,
+/// 费率分母，`GlobalConfig.trade_fee_rate` 等费率以百万分之一为单位
+pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// 每一侧（base / quote）实际收取的费率，单位为基点（bps）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeLegs {
+    pub base_fee_bps: u64,
+    pub quote_fee_bps: u64,
 }
 
-impl Default for AmmCreatorFeeOn {
-    fn default() -> Self {
-        Self::QuoteToken
+/// 一笔交易适用的各项费率，单位与链上一致，为百万分之一（分母 [`FEE_RATE_DENOMINATOR`]）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeFeeRates {
+    /// 协议交易费率，`GlobalConfig.trade_fee_rate`
+    pub trade_fee_rate: u64,
+    /// 平台费率，`PlatformConfig.fee_rate`
+    pub platform_fee_rate: u64,
+    /// 创建者费率，`PlatformConfig.creator_fee_rate`
+    pub creator_fee_rate: u64,
+}
+
+impl TradeFeeRates {
+    pub fn new(global: &GlobalConfig, platform: &PlatformConfig) -> Self {
+        Self {
+            trade_fee_rate: global.trade_fee_rate,
+            platform_fee_rate: platform.fee_rate,
+            creator_fee_rate: platform.creator_fee_rate,
+        }
+    }
+
+    /// 合计费率（百万分之一）
+    pub fn total_rate(&self) -> u64 {
+        self.trade_fee_rate
+            .saturating_add(self.platform_fee_rate)
+            .saturating_add(self.creator_fee_rate)
+    }
+
+    /// 卖出所得的 quote 数量 `gross` 扣除费用后的净额
+    ///
+    /// 协议和平台费总是从 quote 一侧收取；创建者费按 `fee_on` 收取：`QuoteToken` 时同样从
+    /// quote 扣除，`BothToken` 时从输入的 base 一侧收取，不影响 quote 所得。
+    /// 费用 `gross * rate / 1e6` 向上取整（净额偏保守），费率合计超过 100% 时净额为 0
+    pub fn net_amount_after_fees(&self, gross: u64, fee_on: AmmFeeOn) -> u64 {
+        let rate = match fee_on {
+            AmmFeeOn::QuoteToken => self.total_rate(),
+            AmmFeeOn::BothToken => self.trade_fee_rate.saturating_add(self.platform_fee_rate),
+        };
+        let rate = rate.min(FEE_RATE_DENOMINATOR) as u128;
+        let denominator = FEE_RATE_DENOMINATOR as u128;
+        let fee = (gross as u128 * rate).div_ceil(denominator) as u64;
+        gross - fee
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct ConstantCurve {
-    pub supply: u64,
-    pub total_base_sell: u64,
-    pub total_quote_fund_raising: u64,
-    pub migrate_type: u8,
+/// 协议、平台和创建者三项费率之和，单位为基点（bps），不足 1 bps 的部分向下取整
+///
+/// 链上费率以百万分之一为单位，1 bps = 100；需要精确计算时使用 [`TradeFeeRates`]
+pub fn total_fee_bps(global: &GlobalConfig, platform: &PlatformConfig) -> u64 {
+    TradeFeeRates::new(global, platform).total_rate() / (FEE_RATE_DENOMINATOR / 10_000)
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct FixedCurve {
-    pub supply: u64,
-    pub total_quote_fund_raising: u64,
-    pub migrate_type: u8,
+impl VestingSchedule {
+    /// 计算在 `now` 时刻已解锁的数量
+    ///
+    /// `start_time + cliff_period` 之前为 0，之后在 `unlock_period` 内线性解锁，不超过
+    /// `total_locked_amount`；尚未迁移（`start_time` 为 0）时返回 0
+    pub fn unlocked_amount(&self, now: u64) -> u64 {
+        linear_unlocked(
+            self.total_locked_amount,
+            self.start_time,
+            self.cliff_period,
+            self.unlock_period,
+            now,
+        )
+    }
+
+    /// 在 `now` 时刻仍锁定的数量
+    pub fn locked_amount(&self, now: u64) -> u64 {
+        self.total_locked_amount.saturating_sub(self.unlocked_amount(now))
+    }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct LinearCurve {
-    pub supply: u64,
-    pub total_quote_fund_raising: u64,
-    pub migrate_type: u8,
+impl PoolState {
+    /// 当前现货价格（每个 base token 对应的 quote token 数量，已按精度换算）
+    ///
+    /// 使用虚拟储备加实际储备计算：quote 储备为 `virtual_quote + real_quote`，
+    /// base 储备为 `virtual_base - real_base`（`real_base` 为已售出的 base 数量）。
+    /// 尚未发生交易的新池 `real_*` 为 0，价格即为 `virtual_quote / virtual_base`。
+    /// base 储备为 0 或 base/quote mint 相同时返回 `None`
+    pub fn spot_price(&self) -> Option<f64> {
+        if self.has_identical_mints() {
+            return None;
+        }
+        let quote_reserve = self.virtual_quote as f64 + self.real_quote as f64;
+        let base_reserve = self.virtual_base.checked_sub(self.real_base)?;
+        if base_reserve == 0 {
+            return None;
+        }
+        let quote = quote_reserve / 10f64.powi(self.quote_decimals as i32);
+        let base = base_reserve as f64 / 10f64.powi(self.base_decimals as i32);
+        Some(quote / base)
+    }
+
+    /// 当前价格，与 [`PoolState::spot_price`] 相同
+    ///
+    /// 计算 `(virtual_quote + real_quote) / (virtual_base - real_base)` 并按 `base_decimals` /
+    /// `quote_decimals` 换算，无法计算时返回 `None`
+    pub fn current_price(&self) -> Option<f64> {
+        self.spot_price()
+    }
+
+    /// 定点数表示的现货价格：每个完整 base token 对应的 quote 最小单位数量
+    ///
+    /// 即 `spot_price * 10^quote_decimals`，按 `quote_reserve * 10^base_decimals / base_reserve`
+    /// 全程使用整数计算（向下取整），储备取值与 [`PoolState::spot_price`] 相同。
+    /// 例如 quote 为 SOL（9 位精度）时，返回值为每个 token 的 lamports 价格。
+    /// base 储备为 0、base/quote mint 相同或计算溢出时返回 `None`
+    pub fn price_fixed(&self) -> Option<u128> {
+        if self.has_identical_mints() {
+            return None;
+        }
+        let quote_reserve = self.virtual_quote as u128 + self.real_quote as u128;
+        let base_reserve = self.virtual_base.checked_sub(self.real_base)?;
+        if base_reserve == 0 {
+            return None;
+        }
+        let base_unit = 10u128.checked_pow(self.base_decimals as u32)?;
+        quote_reserve.checked_mul(base_unit)?.checked_div(base_reserve as u128)
+    }
+
+    /// 转换为与协议无关的储备快照
+    ///
+    /// 储备与 [`PoolState::spot_price`] 一致：base 为 `virtual_base - real_base`，
+    /// quote 为 `virtual_quote + real_quote`
+    pub fn to_reserve_snapshot(&self) -> ReserveSnapshot {
+        ReserveSnapshot {
+            base_mint: self.base_mint,
+            quote_mint: self.quote_mint,
+            base_reserve: self.virtual_base.saturating_sub(self.real_base),
+            quote_reserve: self.virtual_quote.saturating_add(self.real_quote),
+            base_decimals: self.base_decimals,
+            quote_decimals: self.quote_decimals,
+            price: self.spot_price(),
+        }
+    }
+
+    /// 募资进度（0.0 ~ 1.0），即 `real_quote / total_quote_fund_raising`
+    ///
+    /// 已进入迁移或交易状态的池子返回 1；募资目标为 0 时返回 0；结果不超过 1
+    pub fn migration_progress(&self) -> f64 {
+        if matches!(self.status(), Some(PoolStatus::Migrate | PoolStatus::Trade)) {
+            return 1.0;
+        }
+        if self.total_quote_fund_raising == 0 {
+            return 0.0;
+        }
+        (self.real_quote as f64 / self.total_quote_fund_raising as f64).min(1.0)
+    }
+
+    /// 池子是否处于迁移状态（`status == Migrate`），即募资完成、等待迁移到 AMM/CPSwap
+    pub fn is_migrating(&self) -> bool {
+        self.status() == Some(PoolStatus::Migrate)
+    }
+
+    /// 迁移使用的钱包：`migrate_type` 为 0 时迁移到 Raydium AMM，使用
+    /// `migrate_to_amm_wallet`；为 1 时迁移到 CPSwap，使用 `migrate_to_cpswap_wallet`
+    ///
+    /// 目前只有 0、1 两种取值，其他取值按 CPSwap 处理
+    pub fn migration_target(&self, global: &GlobalConfig) -> Pubkey {
+        match self.migrate_type {
+            0 => global.migrate_to_amm_wallet,
+            _ => global.migrate_to_cpswap_wallet,
+        }
+    }
+
+    /// 检查池子状态是否自洽，返回发现的第一个问题
+    ///
+    /// 用于在异常或解码错误的账户进入价格等计算之前将其拦截
+    pub fn validate(&self) -> Result<(), PoolStateIssue> {
+        if self.has_identical_mints() {
+            return Err(PoolStateIssue::IdenticalMints);
+        }
+        if self.status().is_none() {
+            return Err(PoolStateIssue::UnknownStatus(self.status));
+        }
+        if self.real_base > self.virtual_base {
+            return Err(PoolStateIssue::RealBaseExceedsVirtual);
+        }
+        Ok(())
+    }
+
+    fn has_identical_mints(&self) -> bool {
+        self.base_mint == self.quote_mint
+    }
+
+    /// 池子账户是否已完整初始化：两个 vault 互不相同，mint 和 vault 均不是默认（全 0）地址
+    ///
+    /// 账户创建过程中可能观察到尚未初始化完成的池子，这类池子不应被视为可交易
+    pub fn is_well_formed(&self) -> bool {
+        let keys = [self.base_mint, self.quote_mint, self.base_vault, self.quote_vault];
+        self.base_vault != self.quote_vault && !keys.contains(&Pubkey::default())
+    }
+
+    /// 池子状态，未知取值返回 `None`
+    ///
+    /// 推荐使用此方法读取状态，原始的 `status` 字段仅为保持 borsh 布局而保留
+    pub fn status(&self) -> Option<PoolStatus> {
+        PoolStatus::from_u8(self.status)
+    }
+
+    /// base mint 是否为 Token-2022 代币（`token_program_flag` 非 0）
+    pub fn is_token_2022(&self) -> bool {
+        self.token_program_flag != 0
+    }
+
+    /// base mint 所属的 token program
+    pub fn base_token_program(&self) -> Pubkey {
+        if self.is_token_2022() {
+            spl_token_2022::ID
+        } else {
+            spl_token::ID
+        }
+    }
+
+    /// 按 Token-2022 转账手续费还原的 base 储备（转账前的总量）
+    ///
+    /// 转账手续费从每次转入的数量中扣除，`real_base` 只反映扣费后的数量，还原为
+    /// `real_base * 10_000 / (10_000 - transfer_fee_bps)`，向下取整。`transfer_fee_bps`
+    /// 由调用方从 mint 的 TransferFeeConfig 扩展中读取。非 Token-2022 池子（见
+    /// [`Self::is_token_2022`]）或费率为 0 时返回 `real_base`；费率不小于 10_000 或结果溢出时
+    /// 返回 `u64::MAX`
+    pub fn effective_base_reserve(&self, transfer_fee_bps: u16) -> u64 {
+        if !self.is_token_2022() || transfer_fee_bps == 0 {
+            return self.real_base;
+        }
+        let net_bps = 10_000u64.saturating_sub(u64::from(transfer_fee_bps));
+        checked_mul_div(self.real_base, 10_000, net_bps).unwrap_or(u64::MAX)
+    }
+
+    /// quote mint 是否为原生 SOL（WSOL）
+    pub fn quote_is_native_sol(&self) -> bool {
+        self.quote_mint == spl_token::native_mint::ID
+    }
+
+    /// 距离募资目标（迁移条件）还差的 quote 数量，已达到目标时为 0
+    pub fn remaining_to_migrate(&self) -> u64 {
+        self.total_quote_fund_raising.saturating_sub(self.real_quote)
+    }
+
+    /// 曲线上计划卖出的 base 中已被买走的比例（0.0 ~ 1.0）
+    ///
+    /// `real_base` 即已售出的 base 数量，结果为 `real_base / total_base_sell`，
+    /// 超过 1 时按 1 处理；`total_base_sell` 为 0 时返回 0
+    pub fn supply_sold_pct(&self) -> f64 {
+        if self.total_base_sell == 0 {
+            return 0.0;
+        }
+        (self.real_base as f64 / self.total_base_sell as f64).min(1.0)
+    }
+
+    /// 以 SOL 为单位的 `real_quote`（lamports / 1e9）
+    ///
+    /// 仅 quote 为原生 SOL 时返回，USDC 等其他 quote 的池子返回 `None`，避免误用 SOL 精度
+    pub fn real_quote_sol(&self) -> Option<f64> {
+        self.quote_is_native_sol().then(|| self.real_quote as f64 / 1e9)
+    }
+
+    /// 曲线定价所用的 quote 储备：`real_quote + virtual_quote`，单位为 quote 代币的最小单位
+    /// （例如 lamports），溢出时取 `u64::MAX`
+    pub fn liquidity_quote(&self) -> u64 {
+        self.real_quote.saturating_add(self.virtual_quote)
+    }
+
+    /// 以美元计的流动性估算：[`Self::liquidity_quote`] 按 `quote_decimals` 换算为整币数量后乘以
+    /// `quote_price_usd`（每个整币的美元价格）
+    ///
+    /// 价格由调用方传入，不在这里查询。`quote_decimals` 通常取池子的 `quote_decimals` 字段，
+    /// 例如 SOL 为 9、USDC 为 6
+    pub fn liquidity_usd(&self, quote_price_usd: f64, quote_decimals: u8) -> f64 {
+        let quote = self.liquidity_quote() as f64 / 10f64.powi(i32::from(quote_decimals));
+        quote * quote_price_usd
+    }
+
+    /// 按 `amm_creator_fee_on` 将全局交易费率分配到 base / quote 两侧
+    ///
+    /// `QuoteToken` 只在 quote 一侧收费，`BothToken` 两侧按相同费率收费。
+    /// 费率从百万分之一换算为 bps，不足 1 bps 的部分向下取整
+    pub fn fee_legs(&self, global: &GlobalConfig) -> FeeLegs {
+        let fee_bps = global.trade_fee_rate / (FEE_RATE_DENOMINATOR / 10_000);
+        let leg = |charged: bool| if charged { fee_bps } else { 0 };
+        FeeLegs {
+            base_fee_bps: leg(self.amm_creator_fee_on.charges_base()),
+            quote_fee_bps: leg(self.amm_creator_fee_on.charges_quote()),
+        }
+    }
+
+    /// 检查池子是否满足所有筛选条件
+    pub fn matches(&self, criteria: &SnipeCriteria) -> bool {
+        self.status() == Some(criteria.status)
+            && self.real_quote < criteria.max_real_quote
+            && (criteria.min_base_decimals..=criteria.max_base_decimals)
+                .contains(&self.base_decimals)
+            && (!criteria.require_native_sol_quote || self.quote_is_native_sol())
+    }
+
+    /// 从 `padding` 区域的 `byte_offset` 处读取一个小端序 u64
+    ///
+    /// 程序升级后新增的字段通常占用原有的 padding，在本 crate 正式支持之前可用此方法读取。
+    /// 越界时返回 `None`。`padding` 不参与 serde 序列化，经 serde 反序列化得到的
+    /// `PoolState` 中该区域全为 0，只有从链上账户数据解码的结果才有意义
+    pub fn read_padding_u64(&self, byte_offset: usize) -> Option<u64> {
+        let end = byte_offset.checked_add(8)?;
+        let bytes = self.padding.get(byte_offset..end)?;
+        Some(u64::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// 只比较有经济意义的字段（储备、状态、费用、mint、creator 等），
+    /// 忽略 `epoch`、`auth_bump` 和 `padding`，用于判断账户是否发生了实际变化
+    pub fn semantic_eq(&self, other: &PoolState) -> bool {
+        self.status == other.status
+            && self.base_decimals == other.base_decimals
+            && self.quote_decimals == other.quote_decimals
+            && self.migrate_type == other.migrate_type
+            && self.supply == other.supply
+            && self.total_base_sell == other.total_base_sell
+            && self.virtual_base == other.virtual_base
+            && self.virtual_quote == other.virtual_quote
+            && self.real_base == other.real_base
+            && self.real_quote == other.real_quote
+            && self.total_quote_fund_raising == other.total_quote_fund_raising
+            && self.quote_protocol_fee == other.quote_protocol_fee
+            && self.platform_fee == other.platform_fee
+            && self.migrate_fee == other.migrate_fee
+            && self.vesting_schedule == other.vesting_schedule
+            && self.global_config == other.global_config
+            && self.platform_config == other.platform_config
+            && self.base_mint == other.base_mint
+            && self.quote_mint == other.quote_mint
+            && self.base_vault == other.base_vault
+            && self.quote_vault == other.quote_vault
+            && self.creator == other.creator
+            && self.token_program_flag == other.token_program_flag
+            && self.amm_creator_fee_on == other.amm_creator_fee_on
+            && self.platform_vesting_share == other.platform_vesting_share
+    }
+
+    /// 与同一池子的上一次状态 `prev` 比较，得到储备变化、推断的交易方向和状态变化
+    ///
+    /// `prev` 为默认（全 0）状态时视为首次出现，`initial` 为 `true`，
+    /// 储备变化为当前值，不推断交易方向和状态变化
+    pub fn diff(&self, prev: &PoolState) -> PoolStateDelta {
+        let delta = |current: u64, previous: u64| current as i128 - previous as i128;
+        if prev.semantic_eq(&PoolState::default()) {
+            return PoolStateDelta {
+                initial: true,
+                real_base: self.real_base as i128,
+                real_quote: self.real_quote as i128,
+                virtual_base: self.virtual_base as i128,
+                virtual_quote: self.virtual_quote as i128,
+                direction: None,
+                status_change: None,
+            };
+        }
+
+        let real_base = delta(self.real_base, prev.real_base);
+        let real_quote = delta(self.real_quote, prev.real_quote);
+        // real_base 为已售出的 base 数量，增加量即交易者收到的 base；
+        // real_quote 为池子收到的 quote，交易者一侧取反
+        let direction = TradeDirection::infer(real_base, -real_quote);
+        PoolStateDelta {
+            initial: false,
+            real_base,
+            real_quote,
+            virtual_base: delta(self.virtual_base, prev.virtual_base),
+            virtual_quote: delta(self.virtual_quote, prev.virtual_quote),
+            direction,
+            status_change: (self.status != prev.status).then_some((prev.status, self.status)),
+        }
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub enum CurveParams {
-    Constant { data: ConstantCurve },
-    Fixed { data: FixedCurve },
-    Linear { data: LinearCurve },
+/// 同一池子相邻两次 `PoolState` 之间的变化，由 [`PoolState::diff`] 生成
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStateDelta {
+    /// 上一次状态为空，本次为首次出现
+    pub initial: bool,
+    pub real_base: i128,
+    pub real_quote: i128,
+    pub virtual_base: i128,
+    pub virtual_quote: i128,
+    /// 由储备变化推断的交易方向，储备未按买入或卖出的方式变化时为 `None`
+    pub direction: Option<TradeDirection>,
+    /// 状态变化 `(上一次, 本次)`，原始取值可用 `PoolStatus::from_u8` 转换
+    pub status_change: Option<(u8, u8)>,
 }
 
-impl Default for CurveParams {
-    fn default() -> Self {
-        Self::Constant { data: ConstantCurve::default() }
+impl PoolStateDelta {
+    /// 储备和状态都没有变化
+    pub fn is_unchanged(&self) -> bool {
+        !self.initial
+            && self.real_base == 0
+            && self.real_quote == 0
+            && self.virtual_base == 0
+            && self.virtual_quote == 0
+            && self.status_change.is_none()
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct VestingSchedule {
-    pub total_locked_amount: u64,
-    pub cliff_period: u64,
-    pub unlock_period: u64,
-    pub start_time: u64,
-    pub allocated_share_amount: u64,
+/// `PoolState::validate` 发现的问题
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolStateIssue {
+    /// base mint 与 quote mint 相同
+    IdenticalMints,
+    /// 未知的池子状态值
+    UnknownStatus(u8),
+    /// 已售出的 base 数量超过虚拟 base 储备
+    RealBaseExceedsVirtual,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct PoolState {
-    pub epoch: u64,
-    pub auth_bump: u8,
-    pub status: u8,
-    pub base_decimals: u8,
-    pub quote_decimals: u8,
-    pub migrate_type: u8,
-    pub supply: u64,
-    pub total_base_sell: u64,
-    pub virtual_base: u64,
-    pub virtual_quote: u64,
-    pub real_base: u64,
-    pub real_quote: u64,
-    pub total_quote_fund_raising: u64,
-    pub quote_protocol_fee: u64,
-    pub platform_fee: u64,
-    pub migrate_fee: u64,
-    pub vesting_schedule: VestingSchedule,
-    pub global_config: Pubkey,
-    pub platform_config: Pubkey,
-    pub base_mint: Pubkey,
-    pub quote_mint: Pubkey,
-    pub base_vault: Pubkey,
-    pub quote_vault: Pubkey,
-    pub creator: Pubkey,
-    pub token_program_flag: u8,
-    pub amm_creator_fee_on: AmmCreatorFeeOn,
-    pub platform_vesting_share: u64,
-    #[serde(with = "serde_big_array::BigArray")]
-    pub padding: [u8; 54],
-}
-
-impl Default for PoolState {
+impl std::fmt::Display for PoolStateIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PoolStateIssue::IdenticalMints => write!(f, "base mint and quote mint are identical"),
+            PoolStateIssue::UnknownStatus(status) => write!(f, "unknown pool status {status}"),
+            PoolStateIssue::RealBaseExceedsVirtual => write!(f, "real_base exceeds virtual_base"),
+        }
+    }
+}
+
+impl std::error::Error for PoolStateIssue {}
+
+/// `PoolState::matches` 的筛选条件
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnipeCriteria {
+    /// 要求的池子状态（默认 `Fund`）
+    pub status: PoolStatus,
+    /// `real_quote` 上限（不含），即池子已募集的 quote 数量（默认不限制）
+    pub max_real_quote: u64,
+    /// base 精度下限（默认 0）
+    pub min_base_decimals: u8,
+    /// base 精度上限（默认 9）
+    pub max_base_decimals: u8,
+    /// 是否要求 quote 为原生 SOL（默认 true）
+    pub require_native_sol_quote: bool,
+}
+
+impl Default for SnipeCriteria {
     fn default() -> Self {
         Self {
-            epoch: 0,
-            auth_bump: 0,
-            status: 0,
-            base_decimals: 0,
-            quote_decimals: 0,
-            migrate_type: 0,
-            supply: 0,
-            total_base_sell: 0,
-            virtual_base: 0,
-            virtual_quote: 0,
-            real_base: 0,
-            real_quote: 0,
-            total_quote_fund_raising: 0,
-            quote_protocol_fee: 0,
-            platform_fee: 0,
-            migrate_fee: 0,
-            vesting_schedule: VestingSchedule::default(),
-            global_config: Pubkey::default(),
-            platform_config: Pubkey::default(),
-            base_mint: Pubkey::default(),
-            quote_mint: Pubkey::default(),
-            base_vault: Pubkey::default(),
-            quote_vault: Pubkey::default(),
-            creator: Pubkey::default(),
-            token_program_flag: 0,
-            amm_creator_fee_on: AmmCreatorFeeOn::default(),
-            platform_vesting_share: 0,
-            padding: [0u8; 54],
-        }
-    }
-}
-
-pub const POOL_STATE_SIZE: usize = 8 + 1 * 5 + 8 * 10 + 32 * 7 + 8 * 8 + 8 * 5 + 1 + 1 + 8 + 54;
-
-pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
-    if data.len() < POOL_STATE_SIZE {
-        return None;
+            status: PoolStatus::Fund,
+            max_real_quote: u64::MAX,
+            min_base_decimals: 0,
+            max_base_decimals: 9,
+            require_native_sol_quote: true,
+        }
     }
-    borsh::from_slice::<PoolState>(&data[..POOL_STATE_SIZE]).ok()
 }
 
-pub fn pool_state_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
+impl From<&PoolState> for ReserveSnapshot {
+    fn from(pool_state: &PoolState) -> Self {
+        pool_state.to_reserve_snapshot()
+    }
+}
+
+/// `PoolState` 的字段数量（不含末尾的 padding）
+pub const POOL_STATE_FIELD_COUNT: usize = 27;
+
+/// 宽松解码得到的 `PoolState`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LenientPoolState {
+    /// 已解码的字段，缺失的字段为默认值
+    pub state: PoolState,
+    /// 按声明顺序成功解码的字段数量，等于 `POOL_STATE_FIELD_COUNT` 时数据完整
+    pub fields_present: usize,
+}
+
+impl LenientPoolState {
+    pub fn is_complete(&self) -> bool {
+        self.fields_present == POOL_STATE_FIELD_COUNT
+    }
+}
+
+/// 宽松解码 `PoolState`（不含 discriminator），仅用于诊断和跨版本兼容
+///
+/// 按声明顺序逐个解码字段，数据不足或某个字段取值非法时停止，其余字段保持默认值。
+/// 一个字段都无法解码时返回 `None`
+pub fn pool_state_decode_lenient(data: &[u8]) -> Option<LenientPoolState> {
+    let mut reader = BorshReader::new(data);
+    let mut state = PoolState::default();
+    let mut fields_present = 0;
+
+    macro_rules! read_fields {
+        ($($field:ident),* $(,)?) => {
+            $(
+                match reader.read() {
+                    Some(value) => state.$field = value,
+                    None => {
+                        return (fields_present > 0)
+                            .then_some(LenientPoolState { state, fields_present });
+                    }
+                }
+                fields_present += 1;
+            )*
+        };
+    }
+
+    read_fields!(
+        epoch,
+        auth_bump,
+        status,
+        base_decimals,
+        quote_decimals,
+        migrate_type,
+        supply,
+        total_base_sell,
+        virtual_base,
+        virtual_quote,
+        real_base,
+        real_quote,
+        total_quote_fund_raising,
+        quote_protocol_fee,
+        platform_fee,
+        migrate_fee,
+        vesting_schedule,
+        global_config,
+        platform_config,
+        base_mint,
+        quote_mint,
+        base_vault,
+        quote_vault,
+        creator,
+        token_program_flag,
+        amm_creator_fee_on,
+        platform_vesting_share,
+    );
+    Some(LenientPoolState { state, fields_present })
+}
+
+/// 按 Bonk 的前缀格式解码账户，失败原因记入 `ParserMetrics`
+fn decode_account<T: BorshDeserialize>(
+    data: &[u8],
+    event_type: &EventType,
+    size: usize,
+) -> Option<T> {
+    try_decode_account_body(BONK_DISCRIMINATOR_SCHEME, data, size)
+        .map_err(|e| ParserMetrics::global().record_error(event_type, &e))
+        .ok()
+}
+
+/// `pool_state_parser_with_options` 的解析选项，默认全部关闭
+///
+/// 选项属于单个解析器，不影响其他解析器；通过 `ParserRegistry` 解析时注册带选项的
+/// `BonkPoolStateParser` 替换内置解析器：
+///
+/// ```ignore
+/// let options = PoolStateParseOptions { skip_ill_formed: true, ..Default::default() };
+/// registry.register_account_parser(BonkPoolStateParser::new(options));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStateParseOptions {
+    /// 跳过 [`PoolState::is_well_formed`] 不成立的池子，用于过滤账户创建过程中观察到的中间状态
+    pub skip_ill_formed: bool,
+    /// 使用 [`pool_state_decode_verified`] 解码，重新编码后与原始数据不一致时记入
+    /// `ParserMetrics` 并跳过该账户，未知的枚举取值也会被视为不一致而不再回退为默认值。
+    /// 解码开销大约翻倍，适合在生产环境中抽样开启以发现布局变化
+    pub verify_round_trip: bool,
+}
+
+pub fn pool_state_parser(account: &AccountPretty, metadata: EventMetadata) -> Option<DexEvent> {
+    pool_state_parser_with_options(account, metadata, &PoolStateParseOptions::default())
+}
+
+/// 按 `options` 解析 `PoolState` 账户，`pool_state_parser_strict` 不受这些选项影响
+pub fn pool_state_parser_with_options(
+    account: &AccountPretty,
+    mut metadata: EventMetadata,
+    options: &PoolStateParseOptions,
+) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountBonkPoolState;
 
-    if account.data.len() < POOL_STATE_SIZE + 8 {
+    if !verify_discriminator(&account.data, &POOL_STATE_DISCRIMINATOR) {
         return None;
     }
-    if let Some(pool_state) = pool_state_decode(&account.data[8..POOL_STATE_SIZE + 8]) {
-        Some(DexEvent::BonkPoolStateAccountEvent(BonkPoolStateAccountEvent {
-            metadata,
-            pubkey: account.pubkey,
-            executable: account.executable,
-            lamports: account.lamports,
-            owner: account.owner,
-            rent_epoch: account.rent_epoch,
-            pool_state,
-        }))
+
+    let body = account.data.get(pool_state_offsets::DISCRIMINATOR_LEN..).unwrap_or_default();
+    let decoded = if options.verify_round_trip {
+        pool_state_decode_verified(body).map(|pool_state| (pool_state, Vec::new()))
     } else {
-        None
+        pool_state_decode_tolerant(body)
+    };
+    let (pool_state, decode_warnings) =
+        decoded.map_err(|e| ParserMetrics::global().record_error(&metadata.event_type, &e)).ok()?;
+    if options.skip_ill_formed && !pool_state.is_well_formed() {
+        return None;
     }
+    Some(pool_state_event(account, metadata, pool_state, decode_warnings))
 }
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct GlobalConfig {
-    pub epoch: u64,
-    pub curve_type: u8,
-    pub index: u16,
-    pub migrate_fee: u64,
-    pub trade_fee_rate: u64,
-    pub max_share_fee_rate: u64,
-    pub min_base_supply: u64,
-    pub max_lock_rate: u64,
-    pub min_base_sell_rate: u64,
-    pub min_base_migrate_rate: u64,
-    pub min_quote_fund_raising: u64,
-    pub quote_mint: Pubkey,
-    pub protocol_fee_owner: Pubkey,
-    pub migrate_fee_owner: Pubkey,
-    pub migrate_to_amm_wallet: Pubkey,
-    pub migrate_to_cpswap_wallet: Pubkey,
-    pub padding: [u64; 16],
-}
-
-pub const GLOBAL_CONFIG_SIZE: usize = 8 + 1 + 2 + 8 * 8 + 32 * 5 + 8 * 16;
-
-pub fn global_config_decode(data: &[u8]) -> Option<GlobalConfig> {
-    if data.len() < GLOBAL_CONFIG_SIZE {
+
+/// 严格模式的 `pool_state_parser`，用于及时发现账户布局变化
+///
+/// `POOL_STATE_SIZE` 已包含结构体末尾的预留空间，账户数据长度超过
+/// `pool_state_offsets::ACCOUNT_LEN`（含 discriminator）时返回 `UnexpectedLength`，
+/// 而不是像 `pool_state_parser` 那样忽略多出的字节；预留空间出现非零字节时返回 `NonZeroPadding`
+pub fn pool_state_parser_strict(
+    account: &AccountPretty,
+    mut metadata: EventMetadata,
+) -> Result<DexEvent, DecodeError> {
+    metadata.event_type = EventType::AccountBonkPoolState;
+
+    if !verify_discriminator(&account.data, &POOL_STATE_DISCRIMINATOR) {
+        return Err(DecodeError::DiscriminatorMismatch);
+    }
+    let max_len = pool_state_offsets::ACCOUNT_LEN;
+    if account.data.len() > max_len {
+        return Err(DecodeError::UnexpectedLength {
+            expected: max_len,
+            actual: account.data.len(),
+        });
+    }
+
+    let body = account.data.get(pool_state_offsets::DISCRIMINATOR_LEN..).unwrap_or_default();
+    let pool_state = pool_state_decode_strict(body)?;
+    Ok(pool_state_event(account, metadata, pool_state, Vec::new()))
+}
+
+fn pool_state_event(
+    account: &AccountPretty,
+    metadata: EventMetadata,
+    pool_state: PoolState,
+    decode_warnings: Vec<String>,
+) -> DexEvent {
+    DexEvent::BonkPoolStateAccountEvent(BonkPoolStateAccountEvent {
+        metadata,
+        base: account.base(),
+        base_token_program: pool_state.base_token_program(),
+        pool_state,
+        decode_warnings,
+    })
+}
+
+/// 直接从账户数据中按固定偏移读取 `PoolState` 字段的只读视图，不做 borsh 解码
+///
+/// 适合只关心少数字段（状态、储备、mint 等）的高吞吐场景；需要完整结构体时使用
+/// [`PoolStateView::to_owned_state`] 或 `pool_state_parser`
+#[derive(Clone, Copy, Debug)]
+pub struct PoolStateView<'a> {
+    /// 完整的账户数据，长度至少为 `pool_state_offsets::ACCOUNT_LEN`
+    data: &'a [u8],
+}
+
+/// 从完整的账户数据（含 discriminator）构造 `PoolStateView`
+///
+/// discriminator 不是 `PoolState` 或数据长度不足时返回 `None`
+pub fn pool_state_view(data: &[u8]) -> Option<PoolStateView<'_>> {
+    if !verify_discriminator(data, &POOL_STATE_DISCRIMINATOR) {
+        return None;
+    }
+    if data.len() < pool_state_offsets::ACCOUNT_LEN {
         return None;
     }
-    borsh::from_slice::<GlobalConfig>(&data[..GLOBAL_CONFIG_SIZE]).ok()
+    Some(PoolStateView { data })
+}
+
+impl PoolStateView<'_> {
+    #[inline]
+    fn read_u8(&self, offset: usize) -> u8 {
+        self.data[offset]
+    }
+
+    #[inline]
+    fn read_u64(&self, offset: usize) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn read_pubkey(&self, offset: usize) -> Pubkey {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&self.data[offset..offset + 32]);
+        Pubkey::new_from_array(bytes)
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_EPOCH)
+    }
+
+    /// 池子状态，与 [`PoolState::status`] 相同
+    pub fn status(&self) -> Option<PoolStatus> {
+        PoolStatus::from_u8(self.read_u8(pool_state_offsets::OFFSET_STATUS))
+    }
+
+    pub fn base_decimals(&self) -> u8 {
+        self.read_u8(pool_state_offsets::OFFSET_BASE_DECIMALS)
+    }
+
+    pub fn quote_decimals(&self) -> u8 {
+        self.read_u8(pool_state_offsets::OFFSET_QUOTE_DECIMALS)
+    }
+
+    pub fn migrate_type(&self) -> u8 {
+        self.read_u8(pool_state_offsets::OFFSET_MIGRATE_TYPE)
+    }
+
+    pub fn supply(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_SUPPLY)
+    }
+
+    pub fn total_base_sell(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_TOTAL_BASE_SELL)
+    }
+
+    pub fn virtual_base(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_VIRTUAL_BASE)
+    }
+
+    pub fn virtual_quote(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_VIRTUAL_QUOTE)
+    }
+
+    pub fn real_base(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_REAL_BASE)
+    }
+
+    pub fn real_quote(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_REAL_QUOTE)
+    }
+
+    pub fn total_quote_fund_raising(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_TOTAL_QUOTE_FUND_RAISING)
+    }
+
+    pub fn global_config(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_GLOBAL_CONFIG)
+    }
+
+    pub fn platform_config(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_PLATFORM_CONFIG)
+    }
+
+    pub fn base_mint(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_BASE_MINT)
+    }
+
+    pub fn quote_mint(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_QUOTE_MINT)
+    }
+
+    pub fn base_vault(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_BASE_VAULT)
+    }
+
+    pub fn quote_vault(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_QUOTE_VAULT)
+    }
+
+    pub fn creator(&self) -> Pubkey {
+        self.read_pubkey(pool_state_offsets::OFFSET_CREATOR)
+    }
+
+    pub fn token_program_flag(&self) -> u8 {
+        self.read_u8(pool_state_offsets::OFFSET_TOKEN_PROGRAM_FLAG)
+    }
+
+    pub fn platform_vesting_share(&self) -> u64 {
+        self.read_u64(pool_state_offsets::OFFSET_PLATFORM_VESTING_SHARE)
+    }
+
+    /// 完整解码为 `PoolState`
+    pub fn to_owned_state(&self) -> Option<PoolState> {
+        pool_state_decode(&self.data[pool_state_offsets::DISCRIMINATOR_LEN..]).ok()
+    }
+}
+
+impl GlobalConfig {
+    /// 曲线类型，`curve_type` 取值未知时返回 `None`
+    pub fn curve_type(&self) -> Option<CurveType> {
+        CurveType::from_u8(self.curve_type)
+    }
+
+    /// quote mint 的符号和精度，见 [`mint_info`]，未注册的 mint 返回 `None`
+    pub fn quote_mint_info(&self) -> Option<MintInfo> {
+        mint_info(&self.quote_mint)
+    }
+
+    /// 与上一次的配置 `prev` 相比发生变化的字段
+    ///
+    /// 比较全部费率、数量限制和地址字段；`epoch` 每次写入都会刷新，`padding` 不含数据，
+    /// 两者不参与比较
+    pub fn diff(&self, prev: &GlobalConfig) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        macro_rules! compare {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if self.$field != prev.$field {
+                        changes.push(FieldChange {
+                            field: Cow::Borrowed(stringify!($field)),
+                            old: prev.$field.to_string(),
+                            new: self.$field.to_string(),
+                        });
+                    }
+                )*
+            };
+        }
+        compare!(
+            curve_type,
+            index,
+            migrate_fee,
+            trade_fee_rate,
+            max_share_fee_rate,
+            min_base_supply,
+            max_lock_rate,
+            min_base_sell_rate,
+            min_base_migrate_rate,
+            min_quote_fund_raising,
+            quote_mint,
+            protocol_fee_owner,
+            migrate_fee_owner,
+            migrate_to_amm_wallet,
+            migrate_to_cpswap_wallet,
+        );
+        changes
+    }
+}
+
+/// 配置中单个字段的变化，数值和地址都格式化为字符串
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// 字段名，与结构体字段同名
+    pub field: Cow<'static, str>,
+    pub old: String,
+    pub new: String,
 }
 
 pub fn global_config_parser(
@@ -246,60 +952,150 @@ pub fn global_config_parser(
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountBonkGlobalConfig;
 
-    if account.data.len() < GLOBAL_CONFIG_SIZE + 8 {
+    if !verify_discriminator(&account.data, &GLOBAL_CONFIG_DISCRIMINATOR) {
         return None;
     }
-    if let Some(global_config) = global_config_decode(&account.data[8..GLOBAL_CONFIG_SIZE + 8]) {
-        Some(DexEvent::BonkGlobalConfigAccountEvent(BonkGlobalConfigAccountEvent {
-            metadata,
-            pubkey: account.pubkey,
-            executable: account.executable,
-            lamports: account.lamports,
-            owner: account.owner,
-            rent_epoch: account.rent_epoch,
-            global_config,
-        }))
-    } else {
-        None
+
+    decode_account::<GlobalConfig>(&account.data, &metadata.event_type, GLOBAL_CONFIG_SIZE).map(
+        |global_config| {
+            DexEvent::BonkGlobalConfigAccountEvent(BonkGlobalConfigAccountEvent {
+                metadata,
+                base: account.base(),
+                global_config,
+            })
+        },
+    )
+}
+
+/// 曲线类型，对应 `GlobalConfig.curve_type`，与 `CurveParams` 的变体一一对应
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum CurveType {
+    Constant = 0,
+    Fixed = 1,
+    Linear = 2,
+}
+
+impl CurveType {
+    /// 从链上的原始取值转换：0 = Constant，1 = Fixed，2 = Linear，其他取值返回 `None`
+    pub fn from_u8(value: u8) -> Option<CurveType> {
+        match value {
+            0 => Some(CurveType::Constant),
+            1 => Some(CurveType::Fixed),
+            2 => Some(CurveType::Linear),
+            _ => None,
+        }
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct BondingCurveParam {
-    pub migrate_type: u8,
-    pub migrate_cpmm_fee_on: u8,
+/// 归一化后的曲线信息，不同曲线类型使用相同的字段
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurveInfo {
+    /// 曲线类型，`GlobalConfig.curve_type` 取值未知时为 `None`
+    pub kind: Option<CurveType>,
+    /// 代币总供应量
     pub supply: u64,
+    /// 曲线上计划卖出的代币数量
     pub total_base_sell: u64,
+    /// 募资目标（quote）
     pub total_quote_fund_raising: u64,
-    pub total_locked_amount: u64,
-    pub cliff_period: u64,
-    pub unlock_period: u64,
+    pub migrate_type: u8,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct PlatformCurveParam {
-    pub epoch: u64,
-    pub index: u8,
-    pub global_config: Pubkey,
-    pub bonding_curve_param: BondingCurveParam,
-    #[serde(with = "serde_big_array::BigArray")]
-    pub padding: [u64; 50],
+/// 根据池子状态和其全局配置生成曲线信息
+///
+/// 曲线类型来自 `GlobalConfig`，供应量和目标值来自 `PoolState`
+pub fn curve_info(pool: &PoolState, global: &GlobalConfig) -> CurveInfo {
+    CurveInfo {
+        kind: global.curve_type(),
+        supply: pool.supply,
+        total_base_sell: pool.total_base_sell,
+        total_quote_fund_raising: pool.total_quote_fund_raising,
+        migrate_type: pool.migrate_type,
+    }
 }
 
-impl Default for PlatformCurveParam {
-    fn default() -> Self {
-        Self {
-            epoch: 0,
-            index: 0,
-            global_config: Pubkey::default(),
-            bonding_curve_param: BondingCurveParam::default(),
-            padding: [0u64; 50],
+/// 按当前 quote 流入速度估算距离迁移（毕业）的时间
+///
+/// `velocity_quote_per_sec` 与 `real_quote` 同单位（未按精度换算），可取自
+/// `MomentumTracker::velocity`。速度不为正数或结果无法表示时返回 `None`
+pub fn graduation_eta(pool: &PoolState, velocity_quote_per_sec: f64) -> Option<Duration> {
+    if velocity_quote_per_sec.is_nan() || velocity_quote_per_sec <= 0.0 {
+        return None;
+    }
+    Duration::try_from_secs_f64(pool.remaining_to_migrate() as f64 / velocity_quote_per_sec).ok()
+}
+
+/// 创建者的代币分配
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatorAllocation {
+    /// 迁移时立即可领取的数量（锁仓没有 cliff 和解锁期时全部立即可领取）
+    pub immediate: u64,
+    /// 按 `schedule` 线性解锁的数量
+    pub vested: u64,
+    /// 池子的锁仓计划，`start_time` 为 0 表示尚未迁移、解锁尚未开始
+    pub schedule: VestingSchedule,
+}
+
+impl CreatorAllocation {
+    /// 创建者分配的总量
+    pub fn total(&self) -> u64 {
+        self.immediate.saturating_add(self.vested)
+    }
+}
+
+/// 汇总创建者的代币分配
+///
+/// 锁仓总量 `vesting_schedule.total_locked_amount` 中扣除平台分成后归创建者所有。
+/// 平台分成优先使用池子记录的 `platform_vesting_share`；为 0 时按平台配置的
+/// `platform_vesting_scale`（百万分之一）推算
+pub fn creator_allocation(pool: &PoolState, platform: &PlatformConfig) -> CreatorAllocation {
+    let schedule = pool.vesting_schedule.clone();
+    let platform_share = if pool.platform_vesting_share > 0 {
+        pool.platform_vesting_share
+    } else {
+        checked_mul_div(
+            schedule.total_locked_amount,
+            platform.platform_vesting_scale,
+            FEE_RATE_DENOMINATOR,
+        )
+        .unwrap_or(0)
+    };
+    let creator_share = schedule.total_locked_amount.saturating_sub(platform_share);
+    let unlocks_immediately = schedule.cliff_period == 0 && schedule.unlock_period == 0;
+    let (immediate, vested) =
+        if unlocks_immediately { (creator_share, 0) } else { (0, creator_share) };
+    CreatorAllocation { immediate, vested, schedule }
+}
+
+impl PlatformConfig {
+    /// 转换为适合 JSON 输出的视图，`name`/`web`/`img` 解码为字符串
+    pub fn to_display(&self) -> PlatformConfigDisplay {
+        PlatformConfigDisplay {
+            epoch: self.epoch,
+            platform_fee_wallet: self.platform_fee_wallet,
+            platform_nft_wallet: self.platform_nft_wallet,
+            platform_scale: self.platform_scale,
+            creator_scale: self.creator_scale,
+            burn_scale: self.burn_scale,
+            fee_rate: self.fee_rate,
+            name: fixed_bytes_to_string(&self.name),
+            web: fixed_bytes_to_string(&self.web),
+            img: fixed_bytes_to_string(&self.img),
+            cpswap_config: self.cpswap_config,
+            creator_fee_rate: self.creator_fee_rate,
+            transfer_fee_extension_auth: self.transfer_fee_extension_auth,
+            platform_vesting_wallet: self.platform_vesting_wallet,
+            platform_vesting_scale: self.platform_vesting_scale,
+            platform_cp_creator: self.platform_cp_creator,
+            curve_params: self.curve_params.clone(),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
-pub struct PlatformConfig {
+/// `PlatformConfig` 的展示视图，定长字节数组解码为字符串，与 `MintParams` 一样以 `String` 表示文本
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformConfigDisplay {
     pub epoch: u64,
     pub platform_fee_wallet: Pubkey,
     pub platform_nft_wallet: Pubkey,
@@ -307,55 +1103,172 @@ pub struct PlatformConfig {
     pub creator_scale: u64,
     pub burn_scale: u64,
     pub fee_rate: u64,
-    #[serde(with = "serde_big_array::BigArray")]
-    pub name: [u8; 64],
-    #[serde(with = "serde_big_array::BigArray")]
-    pub web: [u8; 256],
-    #[serde(with = "serde_big_array::BigArray")]
-    pub img: [u8; 256],
+    pub name: String,
+    pub web: String,
+    pub img: String,
     pub cpswap_config: Pubkey,
     pub creator_fee_rate: u64,
     pub transfer_fee_extension_auth: Pubkey,
     pub platform_vesting_wallet: Pubkey,
     pub platform_vesting_scale: u64,
     pub platform_cp_creator: Pubkey,
-    #[serde(with = "serde_big_array::BigArray")]
-    pub padding: [u8; 108],
     pub curve_params: Vec<PlatformCurveParam>,
 }
 
-impl Default for PlatformConfig {
-    fn default() -> Self {
-        Self {
-            epoch: 0,
-            platform_fee_wallet: Pubkey::default(),
-            platform_nft_wallet: Pubkey::default(),
-            platform_scale: 0,
-            creator_scale: 0,
-            burn_scale: 0,
-            fee_rate: 0,
-            name: [0u8; 64],
-            web: [0u8; 256],
-            img: [0u8; 256],
-            cpswap_config: Pubkey::default(),
-            creator_fee_rate: 0,
-            transfer_fee_extension_auth: Pubkey::default(),
-            platform_vesting_wallet: Pubkey::default(),
-            platform_vesting_scale: 0,
-            platform_cp_creator: Pubkey::default(),
-            padding: [0u8; 108],
-            curve_params: Vec::new(),
-        }
+/// 将以 0 填充的定长字节数组解码为字符串，去掉末尾的 0，非 UTF-8 字节按替换字符处理
+fn fixed_bytes_to_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// 在 `Debug` 输出中以 `[..; N]` 代替 padding 的内容
+struct ElidedPadding(usize);
+
+impl std::fmt::Debug for ElidedPadding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[..; {}]", self.0)
     }
 }
 
-pub const PLATFORM_CONFIG_SIZE: usize = 8 + 32 * 2 + 8 * 4 + 64 + 256 + 256 + 32 + 8 + 32 + 32 + 8 + 32 + 108;
+/// `PoolState` 的精简 `Debug` 输出，由 [`PoolState::debug_compact`] 返回
+pub struct PoolStateCompact<'a>(&'a PoolState);
 
-pub fn platform_config_decode(data: &[u8]) -> Option<PlatformConfig> {
-    if data.len() < PLATFORM_CONFIG_SIZE {
-        return None;
+impl std::fmt::Debug for PoolStateCompact<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = self.0;
+        f.debug_struct("PoolState")
+            .field("epoch", &s.epoch)
+            .field("auth_bump", &s.auth_bump)
+            .field("status", &s.status)
+            .field("base_decimals", &s.base_decimals)
+            .field("quote_decimals", &s.quote_decimals)
+            .field("migrate_type", &s.migrate_type)
+            .field("supply", &s.supply)
+            .field("total_base_sell", &s.total_base_sell)
+            .field("virtual_base", &s.virtual_base)
+            .field("virtual_quote", &s.virtual_quote)
+            .field("real_base", &s.real_base)
+            .field("real_quote", &s.real_quote)
+            .field("total_quote_fund_raising", &s.total_quote_fund_raising)
+            .field("quote_protocol_fee", &s.quote_protocol_fee)
+            .field("platform_fee", &s.platform_fee)
+            .field("migrate_fee", &s.migrate_fee)
+            .field("vesting_schedule", &s.vesting_schedule)
+            .field("global_config", &s.global_config)
+            .field("platform_config", &s.platform_config)
+            .field("base_mint", &s.base_mint)
+            .field("quote_mint", &s.quote_mint)
+            .field("base_vault", &s.base_vault)
+            .field("quote_vault", &s.quote_vault)
+            .field("creator", &s.creator)
+            .field("token_program_flag", &s.token_program_flag)
+            .field("amm_creator_fee_on", &s.amm_creator_fee_on)
+            .field("platform_vesting_share", &s.platform_vesting_share)
+            .field("padding", &ElidedPadding(s.padding.len()))
+            .finish()
+    }
+}
+
+impl PoolState {
+    /// 省略 padding 内容的 `Debug` 输出，派生的 `Debug` 仍输出完整字节
+    pub fn debug_compact(&self) -> PoolStateCompact<'_> {
+        PoolStateCompact(self)
+    }
+}
+
+/// `PlatformCurveParam` 的精简 `Debug` 输出
+struct PlatformCurveParamCompact<'a>(&'a PlatformCurveParam);
+
+impl std::fmt::Debug for PlatformCurveParamCompact<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let p = self.0;
+        f.debug_struct("PlatformCurveParam")
+            .field("epoch", &p.epoch)
+            .field("index", &p.index)
+            .field("global_config", &p.global_config)
+            .field("bonding_curve_param", &p.bonding_curve_param)
+            .field("padding", &ElidedPadding(p.padding.len()))
+            .finish()
+    }
+}
+
+/// `PlatformConfig` 的精简 `Debug` 输出，由 [`PlatformConfig::debug_compact`] 返回
+pub struct PlatformConfigCompact<'a>(&'a PlatformConfig);
+
+impl std::fmt::Debug for PlatformConfigCompact<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = self.0;
+        let curve_params: Vec<_> = c.curve_params.iter().map(PlatformCurveParamCompact).collect();
+        f.debug_struct("PlatformConfig")
+            .field("epoch", &c.epoch)
+            .field("platform_fee_wallet", &c.platform_fee_wallet)
+            .field("platform_nft_wallet", &c.platform_nft_wallet)
+            .field("platform_scale", &c.platform_scale)
+            .field("creator_scale", &c.creator_scale)
+            .field("burn_scale", &c.burn_scale)
+            .field("fee_rate", &c.fee_rate)
+            .field("name", &fixed_bytes_to_string(&c.name))
+            .field("web", &fixed_bytes_to_string(&c.web))
+            .field("img", &fixed_bytes_to_string(&c.img))
+            .field("cpswap_config", &c.cpswap_config)
+            .field("creator_fee_rate", &c.creator_fee_rate)
+            .field("transfer_fee_extension_auth", &c.transfer_fee_extension_auth)
+            .field("platform_vesting_wallet", &c.platform_vesting_wallet)
+            .field("platform_vesting_scale", &c.platform_vesting_scale)
+            .field("platform_cp_creator", &c.platform_cp_creator)
+            .field("padding", &ElidedPadding(c.padding.len()))
+            .field("curve_params", &curve_params)
+            .finish()
     }
-    borsh::from_slice::<PlatformConfig>(&data[..PLATFORM_CONFIG_SIZE]).ok()
+}
+
+impl PlatformConfig {
+    /// 省略 padding 内容、`name`/`web`/`img` 解码为字符串的 `Debug` 输出，
+    /// 派生的 `Debug` 仍输出完整字节
+    pub fn debug_compact(&self) -> PlatformConfigCompact<'_> {
+        PlatformConfigCompact(self)
+    }
+}
+
+/// `PlatformConfig` 中的费用相关字段
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformConfigFees {
+    pub epoch: u64,
+    pub platform_fee_wallet: Pubkey,
+    pub platform_nft_wallet: Pubkey,
+    pub platform_scale: u64,
+    pub creator_scale: u64,
+    pub burn_scale: u64,
+    pub fee_rate: u64,
+    pub creator_fee_rate: u64,
+}
+
+/// 只解码 `PlatformConfig` 的费用字段（不含 discriminator）
+///
+/// 逐字段读取，跳过 `name`/`web`/`img`/`cpswap_config`，读到 `creator_fee_rate` 后停止，
+/// 不解码其后的字段
+pub fn platform_config_fees_decode(data: &[u8]) -> Option<PlatformConfigFees> {
+    let mut reader = BorshReader::new(data);
+    let epoch = reader.read()?;
+    let platform_fee_wallet = reader.read()?;
+    let platform_nft_wallet = reader.read()?;
+    let platform_scale = reader.read()?;
+    let creator_scale = reader.read()?;
+    let burn_scale = reader.read()?;
+    let fee_rate = reader.read()?;
+    // name + web + img + cpswap_config
+    reader.skip(64 + 256 + 256 + 32)?;
+    let creator_fee_rate = reader.read()?;
+    Some(PlatformConfigFees {
+        epoch,
+        platform_fee_wallet,
+        platform_nft_wallet,
+        platform_scale,
+        creator_scale,
+        burn_scale,
+        fee_rate,
+        creator_fee_rate,
+    })
 }
 
 pub fn platform_config_parser(
@@ -364,22 +1277,1115 @@ pub fn platform_config_parser(
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountBonkPlatformConfig;
 
-    if account.data.len() < PLATFORM_CONFIG_SIZE + 8 {
+    if !verify_discriminator(&account.data, &PLATFORM_CONFIG_DISCRIMINATOR) {
         return None;
     }
-    if let Some(platform_config) =
-        platform_config_decode(&account.data[8..PLATFORM_CONFIG_SIZE + 8])
-    {
-        Some(DexEvent::BonkPlatformConfigAccountEvent(BonkPlatformConfigAccountEvent {
-            metadata,
-            pubkey: account.pubkey,
-            executable: account.executable,
-            lamports: account.lamports,
-            owner: account.owner,
-            rent_epoch: account.rent_epoch,
-            platform_config,
-        }))
-    } else {
-        None
+
+    let body = account.data.get(BONK_DISCRIMINATOR_SCHEME.prefix_len()..).unwrap_or_default();
+    if let Err(e) = check_curve_params_len(body) {
+        ParserMetrics::global().record_error(&metadata.event_type, &e);
+        return None;
+    }
+
+    // curve_params 为变长字段，会读取到 PLATFORM_CONFIG_SIZE 之后
+    decode_account::<PlatformConfig>(&account.data, &metadata.event_type, PLATFORM_CONFIG_SIZE).map(
+        |platform_config| {
+            DexEvent::BonkPlatformConfigAccountEvent(BonkPlatformConfigAccountEvent {
+                metadata,
+                base: account.base(),
+                platform_config,
+            })
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::protocols::bonk::BONK_PROGRAM_ID;
+    use crate::streaming::grpc::{run_account_fixture, AccountPrettyBuilder};
+
+    #[test]
+    fn pool_status_from_raw_byte() {
+        let cases = [
+            (0, Some(PoolStatus::Fund)),
+            (1, Some(PoolStatus::Migrate)),
+            (2, Some(PoolStatus::Trade)),
+            (3, None),
+            (u8::MAX, None),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(PoolStatus::from_u8(raw), expected, "raw status {raw}");
+            let pool_state = PoolState { status: raw, ..Default::default() };
+            assert_eq!(pool_state.status(), expected, "raw status {raw}");
+        }
+    }
+
+    #[test]
+    fn fee_on_enums_agree_on_byte_meaning() {
+        for (byte, fee_on) in [(0, AmmFeeOn::QuoteToken), (1, AmmFeeOn::BothToken)] {
+            assert_eq!(borsh::from_slice::<AmmFeeOn>(&[byte]).unwrap(), fee_on);
+            assert_eq!(borsh::to_vec(&fee_on).unwrap(), [byte]);
+            assert_eq!(AmmFeeOn::from_u8(byte), Some(fee_on.clone()));
+            assert_eq!(fee_on.as_u8(), byte);
+        }
+        for (byte, fee_on) in [(0, AmmCreatorFeeOn::QuoteToken), (1, AmmCreatorFeeOn::BothToken)] {
+            assert_eq!(borsh::from_slice::<AmmCreatorFeeOn>(&[byte]).unwrap(), fee_on);
+            assert_eq!(borsh::to_vec(&fee_on).unwrap(), [byte]);
+            assert_eq!(AmmCreatorFeeOn::from_u8(byte), Some(fee_on.clone()));
+            assert_eq!(fee_on.as_u8(), byte);
+        }
+        // 未知取值两者都拒绝
+        assert_eq!(AmmFeeOn::from_u8(2), None);
+        assert_eq!(AmmCreatorFeeOn::from_u8(2), None);
+        assert!(borsh::from_slice::<AmmFeeOn>(&[2]).is_err());
+        assert!(borsh::from_slice::<AmmCreatorFeeOn>(&[2]).is_err());
+    }
+
+    #[test]
+    fn curve_params_accessors_read_every_variant() {
+        let constant = CurveParams::Constant {
+            data: ConstantCurve {
+                supply: 1_000,
+                total_base_sell: 800,
+                total_quote_fund_raising: 85,
+                migrate_type: 0,
+            },
+        };
+        let fixed = CurveParams::Fixed {
+            data: FixedCurve { supply: 2_000, total_quote_fund_raising: 90, migrate_type: 1 },
+        };
+        let linear = CurveParams::Linear {
+            data: LinearCurve { supply: 3_000, total_quote_fund_raising: 95, migrate_type: 1 },
+        };
+        let cases = [
+            (constant, 1_000, 85, 0, Some(800)),
+            (fixed, 2_000, 90, 1, None),
+            (linear, 3_000, 95, 1, None),
+        ];
+        for (curve, supply, fund_raising, migrate_type, base_sell) in cases {
+            assert_eq!(curve.supply(), supply, "{curve:?}");
+            assert_eq!(curve.total_quote_fund_raising(), fund_raising, "{curve:?}");
+            assert_eq!(curve.migrate_type(), migrate_type, "{curve:?}");
+            // 只有 Constant 曲线带 total_base_sell
+            assert_eq!(curve.total_base_sell(), base_sell, "{curve:?}");
+        }
+    }
+
+    fn encoded_len<T: BorshSerialize>(value: &T) -> usize {
+        borsh::to_vec(value).unwrap().len()
+    }
+
+    #[test]
+    fn size_constants_match_borsh_encoding() {
+        assert_eq!(encoded_len(&PoolState::default()), POOL_STATE_SIZE);
+        assert_eq!(encoded_len(&GlobalConfig::default()), GLOBAL_CONFIG_SIZE);
+        assert_eq!(encoded_len(&PlatformCurveParam::default()), PLATFORM_CURVE_PARAM_SIZE);
+        // 定长部分加上空 `curve_params` 的 4 字节长度前缀
+        assert_eq!(encoded_len(&PlatformConfig::default()), PLATFORM_CONFIG_SIZE + 4);
+    }
+
+    #[test]
+    fn quote_mint_info_resolves_wsol_only_when_known() {
+        let wsol = GlobalConfig { quote_mint: spl_token::native_mint::ID, ..Default::default() };
+        let info = wsol.quote_mint_info().unwrap();
+        assert_eq!((info.symbol.as_str(), info.decimals), ("WSOL", 9));
+
+        let unknown = GlobalConfig { quote_mint: Pubkey::new_unique(), ..Default::default() };
+        assert_eq!(unknown.quote_mint_info(), None);
+    }
+
+    #[test]
+    fn global_config_diff_lists_exactly_the_changed_fields() {
+        let prev = GlobalConfig { epoch: 800, trade_fee_rate: 2_500, ..Default::default() };
+        let owner = Pubkey::new_unique();
+        // epoch 不参与比较
+        let next = GlobalConfig {
+            epoch: 801,
+            trade_fee_rate: 3_000,
+            protocol_fee_owner: owner,
+            ..prev.clone()
+        };
+        assert_eq!(
+            next.diff(&prev),
+            vec![
+                FieldChange {
+                    field: "trade_fee_rate".into(),
+                    old: "2500".to_owned(),
+                    new: "3000".to_owned(),
+                },
+                FieldChange {
+                    field: "protocol_fee_owner".into(),
+                    old: Pubkey::default().to_string(),
+                    new: owner.to_string(),
+                },
+            ]
+        );
+        assert!(next.diff(&GlobalConfig { epoch: 900, ..next.clone() }).is_empty());
+    }
+
+    #[test]
+    fn debug_compact_elides_padding_contents() {
+        let pool = PoolState { real_quote: 10_000, padding: [0xAB; 54], ..Default::default() };
+        let compact = format!("{:?}", pool.debug_compact());
+        assert!(compact.starts_with("PoolState { epoch: 0,"), "{compact}");
+        assert!(compact.contains("real_quote: 10000,"), "{compact}");
+        assert!(compact.ends_with("padding: [..; 54] }"), "{compact}");
+        assert!(!compact.contains("171"), "{compact}");
+        // 派生的 Debug 仍输出完整字节
+        assert!(format!("{pool:?}").contains("171, 171"));
+
+        let mut config = PlatformConfig {
+            fee_rate: 25,
+            padding: [0xAB; 108],
+            curve_params: vec![PlatformCurveParam { padding: [0xAB; 50], ..Default::default() }],
+            ..Default::default()
+        };
+        config.name[..4].copy_from_slice(b"Bonk");
+        config.web[..16].copy_from_slice(b"https://bonk.fun");
+        let compact = format!("{:?}", config.debug_compact());
+        assert!(
+            compact.contains(r#"name: "Bonk", web: "https://bonk.fun", img: "","#),
+            "{compact}"
+        );
+        assert!(compact.contains("fee_rate: 25,"), "{compact}");
+        assert!(compact.contains("padding: [..; 108]"), "{compact}");
+        assert!(compact.contains("padding: [..; 50]"), "{compact}");
+        assert!(!compact.contains("171"), "{compact}");
+    }
+
+    #[test]
+    fn global_config_curve_type() {
+        let cases = [
+            (0, Some(CurveType::Constant)),
+            (1, Some(CurveType::Fixed)),
+            (2, Some(CurveType::Linear)),
+            (3, None),
+            (255, None),
+        ];
+        for (raw, expected) in cases {
+            let config = GlobalConfig { curve_type: raw, ..Default::default() };
+            assert_eq!(config.curve_type(), expected, "raw curve type {raw}");
+        }
+    }
+
+    fn vesting(unlock_period: u64) -> VestingSchedule {
+        VestingSchedule {
+            total_locked_amount: 1_000,
+            cliff_period: 100,
+            unlock_period,
+            start_time: 10_000,
+            allocated_share_amount: 0,
+        }
+    }
+
+    #[test]
+    fn vesting_schedule_unlocks_linearly_after_cliff() {
+        let schedule = vesting(1_000);
+        assert_eq!(schedule.unlocked_amount(9_000), 0);
+        assert_eq!(schedule.unlocked_amount(10_099), 0);
+        assert_eq!(schedule.unlocked_amount(10_100), 0);
+        assert_eq!(schedule.unlocked_amount(10_600), 500);
+        assert_eq!(schedule.locked_amount(10_600), 500);
+        assert_eq!(schedule.unlocked_amount(11_100), 1_000);
+        assert_eq!(schedule.unlocked_amount(u64::MAX), 1_000);
+        assert_eq!(schedule.locked_amount(u64::MAX), 0);
+    }
+
+    #[test]
+    fn vesting_schedule_edge_cases() {
+        // unlock_period 为 0：悬崖期结束后一次性解锁
+        let schedule = vesting(0);
+        assert_eq!(schedule.unlocked_amount(10_099), 0);
+        assert_eq!(schedule.unlocked_amount(10_100), 1_000);
+
+        // 尚未迁移（start_time 为 0）时不解锁
+        let not_started = VestingSchedule { start_time: 0, ..vesting(1_000) };
+        assert_eq!(not_started.unlocked_amount(u64::MAX), 0);
+
+        // 异常的链上取值不会溢出
+        let bogus = VestingSchedule {
+            total_locked_amount: u64::MAX,
+            cliff_period: u64::MAX,
+            unlock_period: u64::MAX,
+            start_time: u64::MAX,
+            allocated_share_amount: u64::MAX,
+        };
+        assert_eq!(bogus.unlocked_amount(u64::MAX), 0);
+        let huge = VestingSchedule { total_locked_amount: u64::MAX, ..vesting(1_000) };
+        assert_eq!(huge.unlocked_amount(10_600), u64::MAX / 2);
+    }
+
+    #[test]
+    fn net_amount_matches_hand_calculation() {
+        // 0.25% + 1% + 0.5%
+        let global = GlobalConfig { trade_fee_rate: 2_500, ..Default::default() };
+        let platform =
+            PlatformConfig { fee_rate: 10_000, creator_fee_rate: 5_000, ..Default::default() };
+        assert_eq!(total_fee_bps(&global, &platform), 175);
+
+        let rates = TradeFeeRates::new(&global, &platform);
+        assert_eq!(rates.total_rate(), 17_500);
+        assert_eq!(rates.net_amount_after_fees(1_000_000_000, AmmFeeOn::QuoteToken), 982_500_000);
+        // 创建者费从 base 一侧收取
+        assert_eq!(rates.net_amount_after_fees(1_000_000_000, AmmFeeOn::BothToken), 987_500_000);
+        // 101 * 1.75% = 1.7675，费用向上取整为 2
+        assert_eq!(rates.net_amount_after_fees(101, AmmFeeOn::QuoteToken), 99);
+        assert_eq!(rates.net_amount_after_fees(0, AmmFeeOn::QuoteToken), 0);
+
+        let excessive = TradeFeeRates {
+            trade_fee_rate: 600_000,
+            platform_fee_rate: 500_000,
+            creator_fee_rate: 0,
+        };
+        assert_eq!(excessive.net_amount_after_fees(u64::MAX, AmmFeeOn::BothToken), 0);
+    }
+
+    fn funding_pool() -> PoolState {
+        PoolState {
+            status: 0,
+            base_decimals: 6,
+            quote_decimals: 9,
+            virtual_base: 1_073_000_000_000,
+            real_base: 73_000_000_000,
+            virtual_quote: 30_000_000_000,
+            real_quote: 10_000_000_000,
+            total_quote_fund_raising: 80_000_000_000,
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn trade_direction_is_inferred_from_trader_side_deltas() {
+        use TradeDirection::{Buy, Sell};
+
+        let cases = [
+            (100, -40, Some(Buy)),
+            (-100, 40, Some(Sell)),
+            (i128::MAX, i128::MIN, Some(Buy)),
+            // 同向变化无法判断
+            (100, 40, None),
+            (-100, -40, None),
+            (0, -40, None),
+            (100, 0, None),
+            (0, 0, None),
+        ];
+        for (base_delta, quote_delta, expected) in cases {
+            assert_eq!(
+                TradeDirection::infer(base_delta, quote_delta),
+                expected,
+                "base {base_delta}, quote {quote_delta}"
+            );
+        }
+    }
+
+    #[test]
+    fn diff_infers_a_buy_from_rising_reserves() {
+        let prev = funding_pool();
+        let next = PoolState {
+            real_base: prev.real_base + 500,
+            real_quote: prev.real_quote + 20,
+            ..prev.clone()
+        };
+        let delta = next.diff(&prev);
+        assert!(!delta.initial);
+        assert_eq!((delta.real_base, delta.real_quote), (500, 20));
+        assert_eq!((delta.virtual_base, delta.virtual_quote), (0, 0));
+        assert_eq!(delta.direction, Some(TradeDirection::Buy));
+        assert_eq!(delta.status_change, None);
+        assert!(!delta.is_unchanged());
+        assert!(prev.diff(&prev).is_unchanged());
+    }
+
+    #[test]
+    fn diff_infers_a_sell_from_falling_reserves() {
+        let prev = funding_pool();
+        let next = PoolState {
+            real_base: prev.real_base - 500,
+            real_quote: prev.real_quote - 20,
+            ..prev.clone()
+        };
+        let delta = next.diff(&prev);
+        assert_eq!((delta.real_base, delta.real_quote), (-500, -20));
+        assert_eq!(delta.direction, Some(TradeDirection::Sell));
+    }
+
+    #[test]
+    fn diff_reports_the_fund_to_migrate_transition() {
+        let prev = funding_pool();
+        let next = PoolState {
+            status: PoolStatus::Migrate as u8,
+            real_base: prev.real_base + 500,
+            real_quote: prev.total_quote_fund_raising,
+            ..prev.clone()
+        };
+        let delta = next.diff(&prev);
+        assert_eq!(delta.status_change, Some((PoolStatus::Fund as u8, PoolStatus::Migrate as u8)));
+        assert_eq!(delta.direction, Some(TradeDirection::Buy));
+    }
+
+    #[test]
+    fn diff_against_the_default_state_is_initial() {
+        let pool = funding_pool();
+        let delta = pool.diff(&PoolState::default());
+        assert!(delta.initial);
+        assert_eq!(delta.real_base, pool.real_base as i128);
+        assert_eq!(delta.virtual_quote, pool.virtual_quote as i128);
+        // 首次出现时不推断方向和状态变化
+        assert_eq!((delta.direction, delta.status_change), (None, None));
+        assert!(!delta.is_unchanged());
+    }
+
+    #[test]
+    fn price_fixed_matches_hand_computed_values() {
+        // quote 储备 40 SOL，base 储备 1_000_000_000_000 个最小单位
+        let pool = funding_pool();
+        let cases = [
+            // 6 位精度：100 万个 token，每个 0.00004 SOL
+            (6, 40_000),
+            // 9 位精度：1000 个 token，每个 0.04 SOL
+            (9, 40_000_000),
+            // 0 位精度：每个 token 不足 1 lamport，向下取整
+            (0, 0),
+            // 40e9 * 10^27 仍在 u128 范围内
+            (27, 40_000 * 10u128.pow(21)),
+        ];
+        for (base_decimals, expected) in cases {
+            let pool = PoolState { base_decimals, ..pool.clone() };
+            assert_eq!(pool.price_fixed(), Some(expected), "base_decimals {base_decimals}");
+        }
+
+        // 除不尽时向下取整，不经过 f64
+        let third = PoolState {
+            virtual_base: 3,
+            real_base: 0,
+            virtual_quote: 1,
+            real_quote: 0,
+            ..pool.clone()
+        };
+        assert_eq!(third.price_fixed(), Some(333_333));
+
+        // 乘积或 10^base_decimals 超出 u128
+        for base_decimals in [28, 39] {
+            let overflow = PoolState { base_decimals, ..pool.clone() };
+            assert_eq!(overflow.price_fixed(), None, "base_decimals {base_decimals}");
+        }
+        let sold_out = PoolState { real_base: pool.virtual_base, ..pool.clone() };
+        assert_eq!(sold_out.price_fixed(), None);
+        let identical = PoolState { quote_mint: pool.base_mint, ..pool };
+        assert_eq!(identical.price_fixed(), None);
+    }
+
+    #[test]
+    fn is_migrating_only_in_migrate_status() {
+        for (status, migrating) in [(0, false), (1, true), (2, false), (9, false)] {
+            let pool = PoolState { status, ..funding_pool() };
+            assert_eq!(pool.is_migrating(), migrating, "status {status}");
+        }
+    }
+
+    #[test]
+    fn migration_target_follows_migrate_type() {
+        let global = GlobalConfig {
+            migrate_to_amm_wallet: Pubkey::new_unique(),
+            migrate_to_cpswap_wallet: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let cases = [
+            (0, global.migrate_to_amm_wallet),
+            (1, global.migrate_to_cpswap_wallet),
+            // 未知取值按 CPSwap 处理
+            (7, global.migrate_to_cpswap_wallet),
+        ];
+        for (migrate_type, wallet) in cases {
+            let pool = PoolState { migrate_type, ..funding_pool() };
+            assert_eq!(pool.migration_target(&global), wallet, "migrate_type {migrate_type}");
+        }
+    }
+
+    #[test]
+    fn liquidity_usd_scales_the_quote_reserve_by_price() {
+        // 10 SOL 实际储备 + 30 SOL 虚拟储备
+        let pool = funding_pool();
+        assert_eq!(pool.liquidity_quote(), 40_000_000_000);
+        assert_eq!(pool.liquidity_usd(150.0, 9), 6_000.0);
+        // 同样的最小单位数量按 USDC 的 6 位精度是 40_000 个整币
+        assert_eq!(pool.liquidity_usd(1.0, 6), 40_000.0);
+
+        let saturated = PoolState { real_quote: u64::MAX, ..pool };
+        assert_eq!(saturated.liquidity_quote(), u64::MAX);
+    }
+
+    #[test]
+    fn effective_base_reserve_grosses_up_token_2022_transfer_fees() {
+        let pool = PoolState { token_program_flag: 1, ..funding_pool() };
+        assert!(pool.is_token_2022());
+        assert_eq!(pool.effective_base_reserve(0), 73_000_000_000);
+        // 73e9 * 10_000 / 9_900，向下取整
+        assert_eq!(pool.effective_base_reserve(100), 73_737_373_737);
+        assert_eq!(pool.effective_base_reserve(10_000), u64::MAX);
+
+        // 非 Token-2022 池子没有转账手续费
+        let spl = PoolState { token_program_flag: 0, ..pool };
+        assert!(!spl.is_token_2022());
+        assert_eq!(spl.effective_base_reserve(100), 73_000_000_000);
+    }
+
+    #[test]
+    fn reserve_snapshot_mirrors_the_pool_state() {
+        let pool = funding_pool();
+        let snapshot = ReserveSnapshot::from(&pool);
+        assert_eq!(snapshot, pool.to_reserve_snapshot());
+        assert_eq!((snapshot.base_mint, snapshot.quote_mint), (pool.base_mint, pool.quote_mint));
+        assert_eq!(snapshot.base_reserve, 1_000_000_000_000);
+        assert_eq!(snapshot.quote_reserve, 40_000_000_000);
+        assert_eq!((snapshot.base_decimals, snapshot.quote_decimals), (6, 9));
+        assert_eq!(snapshot.price, pool.spot_price());
+
+        // 由快照中的储备和精度重新计算价格，应与池子价格一致
+        let price = (snapshot.quote_reserve as f64 / 1e9) / (snapshot.base_reserve as f64 / 1e6);
+        assert!((snapshot.price.unwrap() - price).abs() < 1e-15, "{snapshot:?}");
+    }
+
+    #[test]
+    fn current_price_uses_virtual_plus_real_reserves() {
+        // (30 + 10) SOL / (1_073_000 - 73_000) 个代币
+        let pool = funding_pool();
+        let price = pool.current_price().unwrap();
+        assert!((price - 0.000_04).abs() < 1e-15, "{price}");
+        assert_eq!(pool.current_price(), pool.spot_price());
+
+        let sold_out = PoolState { real_base: pool.virtual_base, ..pool.clone() };
+        assert_eq!(sold_out.current_price(), None);
+        let oversold = PoolState { real_base: pool.virtual_base + 1, ..pool };
+        assert_eq!(oversold.current_price(), None);
+    }
+
+    #[test]
+    fn helpers_do_not_panic_on_near_max_reserves() {
+        let pool = PoolState {
+            base_decimals: u8::MAX,
+            quote_decimals: u8::MAX,
+            supply: u64::MAX,
+            total_base_sell: 1,
+            virtual_base: u64::MAX,
+            real_base: u64::MAX - 1,
+            virtual_quote: u64::MAX,
+            real_quote: u64::MAX,
+            total_quote_fund_raising: 1,
+            token_program_flag: 1,
+            ..funding_pool()
+        };
+        assert!(pool.spot_price().is_some());
+        // 10^255 超出 u128
+        assert_eq!(pool.price_fixed(), None);
+        let snapshot = pool.to_reserve_snapshot();
+        assert_eq!((snapshot.base_reserve, snapshot.quote_reserve), (1, u64::MAX));
+        assert_eq!(pool.liquidity_quote(), u64::MAX);
+        assert!(pool.liquidity_usd(f64::MAX, 0).is_infinite());
+        assert_eq!(pool.migration_progress(), 1.0);
+        assert_eq!(pool.remaining_to_migrate(), 0);
+        assert_eq!(pool.supply_sold_pct(), 1.0);
+        assert_eq!(pool.effective_base_reserve(9_999), u64::MAX);
+        assert_eq!(pool.effective_base_reserve(u16::MAX), u64::MAX);
+
+        let decimals_9 = PoolState { base_decimals: 9, quote_decimals: 9, ..pool.clone() };
+        assert_eq!(decimals_9.price_fixed(), Some(2 * u64::MAX as u128 * 1_000_000_000));
+        let global = GlobalConfig { trade_fee_rate: u64::MAX, ..Default::default() };
+        assert_eq!(pool.fee_legs(&global).quote_fee_bps, u64::MAX / 100);
+        let rates = TradeFeeRates {
+            trade_fee_rate: u64::MAX,
+            platform_fee_rate: u64::MAX,
+            creator_fee_rate: u64::MAX,
+        };
+        assert_eq!(rates.total_rate(), u64::MAX);
+        assert_eq!(rates.net_amount_after_fees(u64::MAX, AmmFeeOn::QuoteToken), 0);
+    }
+
+    #[test]
+    fn identical_mints_are_rejected_instead_of_priced() {
+        let pool = funding_pool();
+        assert_eq!(pool.validate(), Ok(()));
+
+        let identical = PoolState { quote_mint: pool.base_mint, ..pool };
+        assert_eq!(identical.validate(), Err(PoolStateIssue::IdenticalMints));
+        assert_eq!(identical.spot_price(), None);
+        assert_eq!(identical.price_fixed(), None);
+        assert_eq!(identical.to_reserve_snapshot().price, None);
+    }
+
+    #[test]
+    fn untraded_pool_is_priced_from_virtual_reserves() {
+        let pool = PoolState { real_base: 0, real_quote: 0, ..funding_pool() };
+        // 30 SOL / 1_073_000 个代币
+        let expected = (30_000_000_000.0 / 1e9) / (1_073_000_000_000.0 / 1e6);
+        let price = pool.spot_price().unwrap();
+        assert!((price - expected).abs() < 1e-15, "{price}");
+    }
+
+    #[test]
+    fn migration_progress_is_clamped_to_unit_range() {
+        let pool = funding_pool();
+        assert_eq!(pool.migration_progress(), 0.125);
+
+        let overfunded = PoolState { real_quote: 90_000_000_000, ..pool.clone() };
+        assert_eq!(overfunded.migration_progress(), 1.0);
+        let no_target = PoolState { total_quote_fund_raising: 0, ..pool.clone() };
+        assert_eq!(no_target.migration_progress(), 0.0);
+        let migrating = PoolState { status: 1, real_quote: 0, ..pool };
+        assert_eq!(migrating.migration_progress(), 1.0);
+    }
+
+    fn sample_pool_state() -> PoolState {
+        PoolState {
+            epoch: 700,
+            auth_bump: 254,
+            status: 2,
+            base_decimals: 6,
+            quote_decimals: 9,
+            migrate_type: 1,
+            supply: 1_000_000_000_000_000,
+            total_base_sell: 793_100_000_000_000,
+            virtual_base: 1_073_025_605_596_382,
+            virtual_quote: 30_000_852_951,
+            real_base: 12_345_678_901,
+            real_quote: 85_000_000_000,
+            total_quote_fund_raising: 85_000_000_000,
+            quote_protocol_fee: 11,
+            platform_fee: 12,
+            migrate_fee: 13,
+            global_config: Pubkey::new_unique(),
+            platform_config: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_vault: Pubkey::new_unique(),
+            quote_vault: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            token_program_flag: 1,
+            platform_vesting_share: 42,
+            ..Default::default()
+        }
+    }
+
+    fn pool_state_account_data(pool_state: &PoolState) -> Vec<u8> {
+        let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(pool_state).unwrap());
+        data
+    }
+
+    #[test]
+    fn lenient_decode_keeps_the_fields_before_the_cut() {
+        use pool_state_offsets::*;
+
+        let pool_state = sample_pool_state();
+        let body = borsh::to_vec(&pool_state).unwrap();
+        let decode = |end: usize| pool_state_decode_lenient(&body[..end - DISCRIMINATOR_LEN]);
+        assert_eq!(pool_state_decode_lenient(&[]), None);
+
+        let epoch_only = decode(OFFSET_AUTH_BUMP).unwrap();
+        assert_eq!(epoch_only.fields_present, 1);
+        assert_eq!(epoch_only.state, PoolState { epoch: pool_state.epoch, ..Default::default() });
+
+        // 截断在 supply 中间，supply 及之后的字段保持默认值
+        let mid_supply = decode(OFFSET_SUPPLY + 3).unwrap();
+        assert_eq!(mid_supply.fields_present, 6);
+        assert_eq!(mid_supply.state.migrate_type, pool_state.migrate_type);
+        assert_eq!(mid_supply.state.supply, 0);
+
+        let through_vesting = decode(OFFSET_GLOBAL_CONFIG).unwrap();
+        assert_eq!(through_vesting.fields_present, 17);
+        assert_eq!(through_vesting.state.vesting_schedule, pool_state.vesting_schedule);
+        assert_eq!(through_vesting.state.global_config, Pubkey::default());
+
+        let through_creator = decode(OFFSET_TOKEN_PROGRAM_FLAG).unwrap();
+        assert_eq!(through_creator.fields_present, 24);
+        assert_eq!(through_creator.state.creator, pool_state.creator);
+        assert_eq!(through_creator.state.token_program_flag, 0);
+        assert!(!through_creator.is_complete());
+
+        let full = pool_state_decode_lenient(&body).unwrap();
+        assert!(full.is_complete());
+        assert_eq!(full.state, pool_state);
+    }
+
+    #[test]
+    fn non_zero_padding_fails_strict_decoding_only() {
+        use pool_state_offsets::{DISCRIMINATOR_LEN, OFFSET_PADDING};
+
+        let pool_state = sample_pool_state();
+        let mut body = borsh::to_vec(&pool_state).unwrap();
+        assert_eq!(pool_state_decode_strict(&body).unwrap(), pool_state);
+        body[OFFSET_PADDING - DISCRIMINATOR_LEN + 5] = 1;
+        // 默认解码忽略 padding 的内容
+        assert!(pool_state_decode(&body).unwrap().semantic_eq(&pool_state));
+        assert!(matches!(
+            pool_state_decode_strict(&body),
+            Err(DecodeError::NonZeroPadding { offset }) if offset == OFFSET_PADDING + 5
+        ));
+
+        let mut data = pool_state_account_data(&pool_state);
+        data[OFFSET_PADDING] = 1;
+        let account = AccountPrettyBuilder::new().owner(BONK_PROGRAM_ID).data(data).build();
+        assert!(pool_state_parser(&account, EventMetadata::default()).is_some());
+        assert!(matches!(
+            pool_state_parser_strict(&account, EventMetadata::default()),
+            Err(DecodeError::NonZeroPadding { offset: OFFSET_PADDING })
+        ));
+
+        let global_config = GlobalConfig { index: 2, ..Default::default() };
+        let mut body = borsh::to_vec(&global_config).unwrap();
+        assert_eq!(global_config_decode_strict(&body).unwrap(), global_config);
+        *body.last_mut().unwrap() = 0xff;
+        assert_eq!(global_config_decode(&body).unwrap().index, 2);
+        assert!(matches!(
+            global_config_decode_strict(&body),
+            Err(DecodeError::NonZeroPadding { offset }) if offset == GLOBAL_CONFIG_SIZE + 7
+        ));
+    }
+
+    #[test]
+    fn pool_state_view_matches_decoded_struct() {
+        let pool_state = sample_pool_state();
+        let data = pool_state_account_data(&pool_state);
+        let view = pool_state_view(&data).unwrap();
+
+        assert_eq!(view.epoch(), pool_state.epoch);
+        assert_eq!(view.status(), pool_state.status());
+        assert_eq!(view.base_decimals(), pool_state.base_decimals);
+        assert_eq!(view.quote_decimals(), pool_state.quote_decimals);
+        assert_eq!(view.migrate_type(), pool_state.migrate_type);
+        assert_eq!(view.supply(), pool_state.supply);
+        assert_eq!(view.total_base_sell(), pool_state.total_base_sell);
+        assert_eq!(view.virtual_base(), pool_state.virtual_base);
+        assert_eq!(view.virtual_quote(), pool_state.virtual_quote);
+        assert_eq!(view.real_base(), pool_state.real_base);
+        assert_eq!(view.real_quote(), pool_state.real_quote);
+        assert_eq!(view.total_quote_fund_raising(), pool_state.total_quote_fund_raising);
+        assert_eq!(view.global_config(), pool_state.global_config);
+        assert_eq!(view.platform_config(), pool_state.platform_config);
+        assert_eq!(view.base_mint(), pool_state.base_mint);
+        assert_eq!(view.quote_mint(), pool_state.quote_mint);
+        assert_eq!(view.base_vault(), pool_state.base_vault);
+        assert_eq!(view.quote_vault(), pool_state.quote_vault);
+        assert_eq!(view.creator(), pool_state.creator);
+        assert_eq!(view.token_program_flag(), pool_state.token_program_flag);
+        assert_eq!(view.platform_vesting_share(), pool_state.platform_vesting_share);
+        assert_eq!(view.to_owned_state(), Some(pool_state));
+    }
+
+    #[test]
+    fn pool_state_view_rejects_short_or_foreign_data() {
+        let data = pool_state_account_data(&sample_pool_state());
+        assert!(pool_state_view(&data[..data.len() - 1]).is_none());
+
+        let mut foreign = data.clone();
+        foreign[0] ^= 0xFF;
+        assert!(pool_state_view(&foreign).is_none());
+    }
+
+    #[test]
+    fn verified_decode_accepts_correct_buffer() {
+        let pool_state = sample_pool_state();
+        let data = pool_state_account_data(&pool_state);
+        let body = &data[pool_state_offsets::DISCRIMINATOR_LEN..];
+        assert_eq!(pool_state_decode_verified(body).unwrap(), pool_state);
+    }
+
+    #[test]
+    fn round_trip_check_reports_first_corrupted_byte() {
+        let pool_state = sample_pool_state();
+        let data = pool_state_account_data(&pool_state);
+        let mut body = data[pool_state_offsets::DISCRIMINATOR_LEN..].to_vec();
+        let corrupted = pool_state_offsets::OFFSET_BASE_MINT + 3;
+        body[corrupted - pool_state_offsets::DISCRIMINATOR_LEN] ^= 0xFF;
+        body[pool_state_offsets::OFFSET_CREATOR - pool_state_offsets::DISCRIMINATOR_LEN] ^= 0xFF;
+
+        // 模拟布局漂移：按旧布局解码出的结构体重新编码后与账户数据不一致
+        let err = check_pool_state_round_trip(&body, &pool_state).unwrap_err();
+        assert!(matches!(
+            err,
+            DecodeError::RoundTripMismatch { first_diff_offset } if first_diff_offset == corrupted
+        ));
+    }
+
+    #[test]
+    fn round_trip_verification_is_a_per_parser_option() {
+        let intact = pool_state_account(&sample_pool_state());
+        let mut unknown_fee_on = intact.clone();
+        unknown_fee_on.data[pool_state_offsets::OFFSET_AMM_CREATOR_FEE_ON] = 7;
+        let verifying = PoolStateParseOptions { verify_round_trip: true, ..Default::default() };
+        let parse = |account: &AccountPretty, options: &PoolStateParseOptions| {
+            pool_state_parser_with_options(account, EventMetadata::default(), options)
+        };
+
+        // 默认回退为 AmmCreatorFeeOn 的默认值，校验模式下不再回退
+        assert!(parse(&unknown_fee_on, &PoolStateParseOptions::default()).is_some());
+        assert!(parse(&unknown_fee_on, &verifying).is_none());
+        assert!(parse(&intact, &verifying).is_some());
+    }
+
+    fn curve_param(index: u8, supply: u64) -> PlatformCurveParam {
+        PlatformCurveParam {
+            epoch: 700,
+            index,
+            global_config: Pubkey::new_unique(),
+            bonding_curve_param: BondingCurveParam {
+                migrate_type: 1,
+                supply,
+                total_base_sell: supply / 2,
+                total_quote_fund_raising: 85_000_000_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn sample_platform_config() -> PlatformConfig {
+        PlatformConfig {
+            epoch: 700,
+            platform_fee_wallet: Pubkey::new_unique(),
+            fee_rate: 2_500,
+            creator_fee_rate: 500,
+            curve_params: vec![curve_param(0, 1_000_000), curve_param(1, 2_000_000)],
+            ..Default::default()
+        }
+    }
+
+    fn platform_config_account(config: &PlatformConfig, reserved: usize) -> AccountPretty {
+        let mut data = borsh::to_vec(config).unwrap();
+        // 账户在 curve_params 之后还有预留空间
+        data.resize(data.len() + reserved, 0);
+        AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(PLATFORM_CONFIG_DISCRIMINATOR)
+            .data(data)
+            .build()
+    }
+
+    #[test]
+    fn platform_config_curve_params_reach_the_event() {
+        let config = sample_platform_config();
+        for reserved in [0, PLATFORM_CURVE_PARAM_SIZE * 3 + 17] {
+            let account = platform_config_account(&config, reserved);
+            let event = platform_config_parser(&account, EventMetadata::default());
+            let Some(DexEvent::BonkPlatformConfigAccountEvent(event)) = event else {
+                panic!("expected a platform config event, got {event:?}");
+            };
+            assert_eq!(event.platform_config.curve_params.len(), 2);
+            assert_eq!(event.platform_config, config);
+            assert_eq!(event.base.pubkey, account.pubkey);
+        }
+    }
+
+    #[test]
+    fn correctly_sized_data_with_another_discriminator_is_rejected() {
+        let pool_state = borsh::to_vec(&sample_pool_state()).unwrap();
+        let global_config = borsh::to_vec(&GlobalConfig::default()).unwrap();
+        let platform_config = borsh::to_vec(&sample_platform_config()).unwrap();
+        let with_discriminator = |discriminator, body: &Vec<u8>| {
+            AccountPrettyBuilder::new()
+                .owner(BONK_PROGRAM_ID)
+                .discriminator(discriminator)
+                .data(body.clone())
+                .build()
+        };
+        let metadata = EventMetadata::default;
+
+        for discriminator in [GLOBAL_CONFIG_DISCRIMINATOR, PLATFORM_CONFIG_DISCRIMINATOR] {
+            let account = with_discriminator(discriminator, &pool_state);
+            assert!(pool_state_parser(&account, metadata()).is_none());
+            assert!(matches!(
+                pool_state_parser_strict(&account, metadata()),
+                Err(DecodeError::DiscriminatorMismatch)
+            ));
+        }
+        for discriminator in [POOL_STATE_DISCRIMINATOR, PLATFORM_CONFIG_DISCRIMINATOR] {
+            let account = with_discriminator(discriminator, &global_config);
+            assert!(global_config_parser(&account, metadata()).is_none());
+        }
+        for discriminator in [POOL_STATE_DISCRIMINATOR, GLOBAL_CONFIG_DISCRIMINATOR] {
+            let account = with_discriminator(discriminator, &platform_config);
+            assert!(platform_config_parser(&account, metadata()).is_none());
+        }
+
+        // 对照：同样的数据配上正确的 discriminator 可以解析
+        let account = with_discriminator(POOL_STATE_DISCRIMINATOR, &pool_state);
+        assert!(pool_state_parser(&account, metadata()).is_some());
+    }
+
+    /// 固定种子的 xorshift64，测试结果可复现
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next_u64() % bound as u64) as usize
+        }
+
+        fn bytes(&mut self, len: usize) -> Vec<u8> {
+            (0..len).map(|_| self.next_u64() as u8).collect()
+        }
+    }
+
+    #[test]
+    fn random_buffers_never_panic_the_parsers() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+        let discriminators =
+            [POOL_STATE_DISCRIMINATOR, GLOBAL_CONFIG_DISCRIMINATOR, PLATFORM_CONFIG_DISCRIMINATOR];
+        let max_len = PLATFORM_CONFIG_SIZE + 8 + 4 + PLATFORM_CURVE_PARAM_SIZE * 2;
+
+        for _ in 0..2_000 {
+            // 长度集中在各结构体大小附近，覆盖刚好不足和刚好足够的边界
+            let len = match rng.below(4) {
+                0 => rng.below(max_len),
+                1 => POOL_STATE_SIZE + 8 - 2 + rng.below(4),
+                2 => GLOBAL_CONFIG_SIZE + 8 - 2 + rng.below(4),
+                _ => PLATFORM_CONFIG_SIZE + 8 + rng.below(8 + PLATFORM_CURVE_PARAM_SIZE),
+            };
+            let mut data = rng.bytes(len);
+            if len >= 8 && rng.below(4) != 0 {
+                data[..8].copy_from_slice(&discriminators[rng.below(discriminators.len())]);
+            }
+            // 随机的长度前缀几乎总是过大，一半的情况改为较小的值以进入 Vec 解码
+            let prefix = PLATFORM_CONFIG_SIZE + 8;
+            if data.len() >= prefix + 4 && rng.below(2) == 0 {
+                let count = rng.below(3) as u32;
+                data[prefix..prefix + 4].copy_from_slice(&count.to_le_bytes());
+            }
+            let account = AccountPrettyBuilder::new().owner(BONK_PROGRAM_ID).data(data).build();
+            let body = account.data.get(8..).unwrap_or_default();
+
+            let _ = pool_state_parser(&account, EventMetadata::default());
+            let _ = pool_state_parser_strict(&account, EventMetadata::default());
+            let _ = global_config_parser(&account, EventMetadata::default());
+            let _ = platform_config_parser(&account, EventMetadata::default());
+            let _ = pool_state_view(&account.data).map(|view| view.to_owned_state());
+            let _ = pool_state_decode_lenient(body);
+            let _ = pool_state_decode_verified(body);
+            let _ = platform_config_fees_decode(body);
+        }
+    }
+
+    #[test]
+    fn forged_curve_params_length_is_rejected_before_decoding() {
+        let body_with_count = |count: u32, entries: usize| {
+            let mut body = borsh::to_vec(&PlatformConfig::default()).unwrap();
+            body.truncate(PLATFORM_CONFIG_SIZE);
+            body.extend_from_slice(&count.to_le_bytes());
+            body.resize(body.len() + entries * PLATFORM_CURVE_PARAM_SIZE, 0);
+            body
+        };
+
+        // 长度前缀声称的元素数量超过剩余数据能容纳的数量
+        let forged = body_with_count(u32::MAX, 1);
+        assert!(matches!(
+            platform_config_decode(&forged),
+            Err(DecodeError::TooManyElements { count, max: 1 }) if count == u32::MAX as usize
+        ));
+        // 数据足够，但超过上限
+        let over_cap = MAX_CURVE_PARAMS + 1;
+        let forged = body_with_count(over_cap as u32, over_cap);
+        assert!(matches!(
+            check_curve_params_len(&forged),
+            Err(DecodeError::TooManyElements { count, max: MAX_CURVE_PARAMS }) if count == over_cap
+        ));
+        let account = AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(PLATFORM_CONFIG_DISCRIMINATOR)
+            .data(forged)
+            .build();
+        assert!(platform_config_parser(&account, EventMetadata::default()).is_none());
+
+        let at_cap = body_with_count(MAX_CURVE_PARAMS as u32, MAX_CURVE_PARAMS);
+        let config = platform_config_decode(&at_cap).unwrap();
+        assert_eq!(config.curve_params.len(), MAX_CURVE_PARAMS);
+    }
+
+    #[test]
+    fn well_formed_pools_have_distinct_non_default_vaults_and_mints() {
+        let pool_state = sample_pool_state();
+        assert!(pool_state.is_well_formed());
+
+        let equal_vaults = PoolState { quote_vault: pool_state.base_vault, ..pool_state.clone() };
+        assert!(!equal_vaults.is_well_formed());
+
+        let default_base_mint = PoolState { base_mint: Pubkey::default(), ..pool_state.clone() };
+        assert!(!default_base_mint.is_well_formed());
+        let default_quote_mint = PoolState { quote_mint: Pubkey::default(), ..pool_state.clone() };
+        assert!(!default_quote_mint.is_well_formed());
+        let default_vault = PoolState { base_vault: Pubkey::default(), ..pool_state };
+        assert!(!default_vault.is_well_formed());
+
+        assert!(!PoolState::default().is_well_formed());
+    }
+
+    fn pool_state_account(pool_state: &PoolState) -> AccountPretty {
+        AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(pool_state)
+            .build()
+    }
+
+    #[test]
+    fn token_program_flag_selects_the_base_token_program() {
+        for (flag, is_token_2022, program) in [
+            (0, false, spl_token::ID),
+            (1, true, spl_token_2022::ID),
+            (2, true, spl_token_2022::ID),
+        ] {
+            let pool_state = PoolState { token_program_flag: flag, ..sample_pool_state() };
+            assert_eq!(pool_state.is_token_2022(), is_token_2022, "flag {flag}");
+            assert_eq!(pool_state.base_token_program(), program, "flag {flag}");
+
+            let account = pool_state_account(&pool_state);
+            let Some(DexEvent::BonkPoolStateAccountEvent(event)) =
+                pool_state_parser(&account, EventMetadata::default())
+            else {
+                panic!("flag {flag}: expected a pool state event");
+            };
+            assert_eq!(event.base_token_program, program, "flag {flag}");
+        }
+    }
+
+    #[test]
+    fn skipping_ill_formed_pools_is_a_per_parser_option() {
+        let skipping = PoolStateParseOptions { skip_ill_formed: true, ..Default::default() };
+        let pool_state = sample_pool_state();
+        let well_formed = pool_state_account(&pool_state);
+        let equal_vaults =
+            pool_state_account(&PoolState { quote_vault: pool_state.base_vault, ..pool_state });
+
+        let parse = |account, options| {
+            pool_state_parser_with_options(account, EventMetadata::default(), options)
+        };
+        assert!(parse(&well_formed, &skipping).is_some());
+        assert!(parse(&equal_vaults, &skipping).is_none());
+        // 默认选项的解析器不受影响
+        assert!(parse(&equal_vaults, &PoolStateParseOptions::default()).is_some());
+        assert!(pool_state_parser(&equal_vaults, EventMetadata::default()).is_some());
+    }
+
+    #[test]
+    fn unknown_amm_creator_fee_on_falls_back_to_default_with_a_warning() {
+        let pool_state = sample_pool_state();
+        let mut account = pool_state_account(&pool_state);
+        account.data[pool_state_offsets::OFFSET_AMM_CREATOR_FEE_ON] = 9;
+        let body = &account.data[pool_state_offsets::DISCRIMINATOR_LEN..];
+        assert!(pool_state_decode(body).is_err());
+
+        // 其余字段原样解码，只有未知的枚举取值被替换为默认值
+        let (decoded, warnings) = pool_state_decode_tolerant(body).unwrap();
+        let fallback = AmmCreatorFeeOn::default();
+        assert_eq!(decoded, PoolState { amm_creator_fee_on: fallback.clone(), ..pool_state });
+        assert_eq!(
+            warnings,
+            vec![format!("amm_creator_fee_on: unknown discriminant 9, using {fallback:?}")]
+        );
+
+        let event = pool_state_parser(&account, EventMetadata::default());
+        let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+            panic!("expected a pool state event, got {event:?}");
+        };
+        assert_eq!(event.pool_state, decoded);
+        assert_eq!(event.decode_warnings, warnings);
+    }
+
+    /// 随机字节经 borsh 解码得到的任意取值，`AmmCreatorFeeOn` 取随机的合法变体
+    fn arbitrary_pool_state(rng: &mut XorShift) -> PoolState {
+        let mut body = rng.bytes(POOL_STATE_SIZE);
+        let offset =
+            pool_state_offsets::OFFSET_AMM_CREATOR_FEE_ON - pool_state_offsets::DISCRIMINATOR_LEN;
+        body[offset] = rng.below(2) as u8;
+        borsh::from_slice(&body).unwrap()
+    }
+
+    fn arbitrary_global_config(rng: &mut XorShift) -> GlobalConfig {
+        borsh::from_slice(&rng.bytes(GLOBAL_CONFIG_SIZE)).unwrap()
+    }
+
+    /// `curve_params` 最多 3 个元素
+    fn arbitrary_platform_config(rng: &mut XorShift) -> PlatformConfig {
+        let count = rng.below(4);
+        let mut body = rng.bytes(PLATFORM_CONFIG_SIZE);
+        body.extend_from_slice(&(count as u32).to_le_bytes());
+        body.extend(rng.bytes(count * PLATFORM_CURVE_PARAM_SIZE));
+        borsh::from_slice(&body).unwrap()
+    }
+
+    fn encoded_account<T: BorshSerialize>(discriminator: [u8; 8], value: &T) -> AccountPretty {
+        AccountPrettyBuilder::new()
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(discriminator)
+            .borsh_data(value)
+            .build()
+    }
+
+    #[test]
+    fn arbitrary_accounts_round_trip_through_the_parsers() {
+        let mut rng = XorShift(0xD1B5_4A32_D192_ED03);
+        for _ in 0..200 {
+            let pool_state = arbitrary_pool_state(&mut rng);
+            let account = encoded_account(POOL_STATE_DISCRIMINATOR, &pool_state);
+            assert_eq!(pool_state_decode(&account.data[8..]).unwrap(), pool_state);
+            let event = pool_state_parser(&account, EventMetadata::default());
+            let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+                panic!("expected a pool state event, got {event:?}");
+            };
+            assert_eq!(event.pool_state, pool_state);
+            assert!(event.decode_warnings.is_empty());
+
+            let global_config = arbitrary_global_config(&mut rng);
+            let account = encoded_account(GLOBAL_CONFIG_DISCRIMINATOR, &global_config);
+            assert_eq!(global_config_decode(&account.data[8..]).unwrap(), global_config);
+            let event = global_config_parser(&account, EventMetadata::default());
+            let Some(DexEvent::BonkGlobalConfigAccountEvent(event)) = event else {
+                panic!("expected a global config event, got {event:?}");
+            };
+            assert_eq!(event.global_config, global_config);
+
+            let platform_config = arbitrary_platform_config(&mut rng);
+            let account = encoded_account(PLATFORM_CONFIG_DISCRIMINATOR, &platform_config);
+            assert_eq!(platform_config_decode(&account.data[8..]).unwrap(), platform_config);
+            let event = platform_config_parser(&account, EventMetadata::default());
+            let Some(DexEvent::BonkPlatformConfigAccountEvent(event)) = event else {
+                panic!("expected a platform config event, got {event:?}");
+            };
+            assert_eq!(event.platform_config, platform_config);
+        }
+    }
+
+    macro_rules! fixture {
+        ($name:literal) => {
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/bonk/", $name))
+        };
+    }
+
+    #[test]
+    fn account_fixtures_decode_to_expected_json() {
+        let fixtures = [
+            (POOL_STATE_DISCRIMINATOR, fixture!("pool_state.b64"), fixture!("pool_state.json")),
+            (
+                GLOBAL_CONFIG_DISCRIMINATOR,
+                fixture!("global_config.b64"),
+                fixture!("global_config.json"),
+            ),
+            (
+                PLATFORM_CONFIG_DISCRIMINATOR,
+                fixture!("platform_config.b64"),
+                fixture!("platform_config.json"),
+            ),
+        ];
+        for (discriminator, data, expected) in fixtures {
+            run_account_fixture(&BONK_PROGRAM_ID, discriminator, data, expected);
+        }
     }
 }
@@ -1,5 +1,5 @@
 use crate::streaming::event_parser::{
-    common::{EventMetadata, EventType},
+    common::{DiscriminatorScheme, EventMetadata, EventType},
     protocols::meteora_damm_v2::{
         discriminators, meteora_damm_v2_initialize_pool_event_decode,
         meteora_damm_v2_swap_event_decode, MeteoraDammV2InitializeCustomizablePoolEvent,
@@ -14,6 +14,9 @@ use solana_sdk::pubkey::Pubkey;
 pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
 
+/// Meteora DAMM v2 账户数据的前缀格式，账户解析器按此提取数据主体
+pub const METEORA_DAMM_V2_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::Anchor8;
+
 /// 解析 Meteora DAMM v2 instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
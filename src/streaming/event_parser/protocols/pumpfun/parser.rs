@@ -1,5 +1,5 @@
 use crate::streaming::event_parser::{
-    common::{EventMetadata, EventType},
+    common::{DiscriminatorScheme, EventMetadata, EventType},
     protocols::pumpfun::{
         discriminators, pumpfun_create_v2_token_event_log_decode, pumpfun_migrate_event_log_decode,
         pumpfun_trade_event_log_decode, PumpFunCreateTokenEvent, PumpFunCreateV2TokenEvent,
@@ -13,6 +13,9 @@ use solana_sdk::pubkey::Pubkey;
 pub const PUMPFUN_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 
+/// PumpFun 账户数据的前缀格式，账户解析器按此提取数据主体
+pub const PUMPFUN_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::Anchor8;
+
 /// 解析 PumpFun instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
@@ -76,6 +79,15 @@ pub fn parse_pumpfun_account_data(
     }
 }
 
+/// 只根据账户 discriminator 判断会命中的账户解析器，不解码账户数据
+pub fn identify_pumpfun_account(discriminator: &[u8]) -> Option<EventType> {
+    match discriminator {
+        discriminators::BONDING_CURVE_ACCOUNT => Some(EventType::AccountPumpFunBondingCurve),
+        discriminators::GLOBAL_ACCOUNT => Some(EventType::AccountPumpFunGlobal),
+        _ => None,
+    }
+}
+
 /// 解析迁移事件
 fn parse_migrate_inner_instruction(data: &[u8], mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::PumpFunMigrate;
@@ -4,8 +4,11 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::{
     event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::pumpfun::{PumpFunBondingCurveAccountEvent, PumpFunGlobalAccountEvent},
+        common::{extract_account_body, EventMetadata, EventType},
+        protocols::pumpfun::{
+            parser::PUMPFUN_DISCRIMINATOR_SCHEME, PumpFunBondingCurveAccountEvent,
+            PumpFunGlobalAccountEvent,
+        },
         DexEvent,
     },
     grpc::AccountPretty,
@@ -39,11 +42,10 @@ pub fn bonding_curve_parser(
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpFunBondingCurve;
 
-    if account.data.len() < BONDING_CURVE_SIZE + 8 {
-        return None;
-    }
-    if let Some(bonding_curve) = bonding_curve_decode(&account.data[8..BONDING_CURVE_SIZE + 8]) {
-        Some(DexEvent::PumpFunBondingCurveAccountEvent(PumpFunBondingCurveAccountEvent {
+    let body =
+        extract_account_body(PUMPFUN_DISCRIMINATOR_SCHEME, &account.data, BONDING_CURVE_SIZE)?;
+    bonding_curve_decode(body).map(|bonding_curve| {
+        DexEvent::PumpFunBondingCurveAccountEvent(PumpFunBondingCurveAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -51,10 +53,8 @@ pub fn bonding_curve_parser(
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             bonding_curve,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
@@ -94,11 +94,9 @@ pub fn global_decode(data: &[u8]) -> Option<Global> {
 pub fn global_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpFunGlobal;
 
-    if account.data.len() < GLOBAL_SIZE + 8 {
-        return None;
-    }
-    if let Some(global) = global_decode(&account.data[8..GLOBAL_SIZE + 8]) {
-        Some(DexEvent::PumpFunGlobalAccountEvent(PumpFunGlobalAccountEvent {
+    let body = extract_account_body(PUMPFUN_DISCRIMINATOR_SCHEME, &account.data, GLOBAL_SIZE)?;
+    global_decode(body).map(|global| {
+        DexEvent::PumpFunGlobalAccountEvent(PumpFunGlobalAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -106,8 +104,6 @@ pub fn global_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Op
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             global,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
@@ -1,5 +1,5 @@
 use crate::streaming::event_parser::{
-    common::{read_u64_le, EventMetadata, EventType},
+    common::{read_u64_le, DiscriminatorScheme, EventMetadata, EventType},
     protocols::pumpswap::{
         discriminators, pump_swap_buy_event_log_decode, pump_swap_create_pool_event_log_decode,
         pump_swap_deposit_event_log_decode, pump_swap_sell_event_log_decode,
@@ -14,6 +14,9 @@ use solana_sdk::pubkey::Pubkey;
 pub const PUMPSWAP_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
 
+/// PumpSwap 账户数据的前缀格式，账户解析器按此提取数据主体
+pub const PUMPSWAP_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::Anchor8;
+
 /// 解析 PumpSwap instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
@@ -76,6 +79,15 @@ pub fn parse_pumpswap_account_data(
     }
 }
 
+/// 只根据账户 discriminator 判断会命中的账户解析器，不解码账户数据
+pub fn identify_pumpswap_account(discriminator: &[u8]) -> Option<EventType> {
+    match discriminator {
+        discriminators::GLOBAL_CONFIG_ACCOUNT => Some(EventType::AccountPumpSwapGlobalConfig),
+        discriminators::POOL_ACCOUNT => Some(EventType::AccountPumpSwapPool),
+        _ => None,
+    }
+}
+
 /// 解析买入日志事件
 fn parse_buy_inner_instruction(data: &[u8], metadata: EventMetadata) -> Option<DexEvent> {
     // Note: event_type will be set by instruction parser
@@ -4,8 +4,11 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::{
     event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::pumpswap::{PumpSwapGlobalConfigAccountEvent, PumpSwapPoolAccountEvent},
+        common::{extract_account_body, EventMetadata, EventType},
+        protocols::pumpswap::{
+            parser::PUMPSWAP_DISCRIMINATOR_SCHEME, PumpSwapGlobalConfigAccountEvent,
+            PumpSwapPoolAccountEvent,
+        },
         DexEvent,
     },
     grpc::AccountPretty,
@@ -41,11 +44,10 @@ pub fn global_config_parser(
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpSwapGlobalConfig;
 
-    if account.data.len() < GLOBAL_CONFIG_SIZE + 8 {
-        return None;
-    }
-    if let Some(config) = global_config_decode(&account.data[8..GLOBAL_CONFIG_SIZE + 8]) {
-        Some(DexEvent::PumpSwapGlobalConfigAccountEvent(PumpSwapGlobalConfigAccountEvent {
+    let body =
+        extract_account_body(PUMPSWAP_DISCRIMINATOR_SCHEME, &account.data, GLOBAL_CONFIG_SIZE)?;
+    global_config_decode(body).map(|config| {
+        DexEvent::PumpSwapGlobalConfigAccountEvent(PumpSwapGlobalConfigAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -53,10 +55,8 @@ pub fn global_config_parser(
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             global_config: config,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
@@ -86,11 +86,9 @@ pub fn pool_decode(data: &[u8]) -> Option<Pool> {
 pub fn pool_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountPumpSwapPool;
 
-    if account.data.len() < POOL_SIZE + 8 {
-        return None;
-    }
-    if let Some(pool) = pool_decode(&account.data[8..POOL_SIZE + 8]) {
-        Some(DexEvent::PumpSwapPoolAccountEvent(PumpSwapPoolAccountEvent {
+    let body = extract_account_body(PUMPSWAP_DISCRIMINATOR_SCHEME, &account.data, POOL_SIZE)?;
+    pool_decode(body).map(|pool| {
+        DexEvent::PumpSwapPoolAccountEvent(PumpSwapPoolAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -98,8 +96,6 @@ pub fn pool_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Opti
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             pool: pool,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
@@ -1,5 +1,5 @@
 use crate::streaming::event_parser::{
-    common::{read_u64_le, EventMetadata, EventType},
+    common::{read_u64_le, DiscriminatorScheme, EventMetadata, EventType},
     protocols::raydium_amm_v4::{
         discriminators, RaydiumAmmV4DepositEvent, RaydiumAmmV4Initialize2Event,
         RaydiumAmmV4SwapEvent, RaydiumAmmV4WithdrawEvent, RaydiumAmmV4WithdrawPnlEvent,
@@ -12,6 +12,9 @@ use solana_sdk::pubkey::Pubkey;
 pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
 
+/// Raydium AMM V4 账户数据的前缀格式：不是 Anchor 程序，账户数据没有 discriminator
+pub const RAYDIUM_AMM_V4_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::None;
+
 /// 解析 Raydium AMM V4 instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
@@ -66,6 +69,14 @@ pub fn parse_raydium_amm_v4_account_data(
     }
 }
 
+/// 只根据账户 discriminator 判断会命中的账户解析器，不解码账户数据
+pub fn identify_raydium_amm_v4_account(discriminator: &[u8]) -> Option<EventType> {
+    match discriminator {
+        discriminators::AMM_INFO => Some(EventType::AccountRaydiumAmmV4AmmInfo),
+        _ => None,
+    }
+}
+
 
 /// 解析提现指令事件
 fn parse_withdraw_pnl_instruction(
@@ -4,8 +4,10 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::{
     event_parser::{
-        common::{EventMetadata, EventType},
-        protocols::raydium_amm_v4::RaydiumAmmV4AmmInfoAccountEvent,
+        common::{extract_account_body, EventMetadata, EventType},
+        protocols::raydium_amm_v4::{
+            parser::RAYDIUM_AMM_V4_DISCRIMINATOR_SCHEME, RaydiumAmmV4AmmInfoAccountEvent,
+        },
         DexEvent,
     },
     grpc::AccountPretty,
@@ -90,11 +92,10 @@ pub fn amm_info_decode(data: &[u8]) -> Option<AmmInfo> {
 pub fn amm_info_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumAmmV4AmmInfo;
 
-    if account.data.len() < AMM_INFO_SIZE {
-        return None;
-    }
-    if let Some(amm_info) = amm_info_decode(&account.data[..AMM_INFO_SIZE]) {
-        Some(DexEvent::RaydiumAmmV4AmmInfoAccountEvent(RaydiumAmmV4AmmInfoAccountEvent {
+    let body =
+        extract_account_body(RAYDIUM_AMM_V4_DISCRIMINATOR_SCHEME, &account.data, AMM_INFO_SIZE)?;
+    amm_info_decode(body).map(|amm_info| {
+        DexEvent::RaydiumAmmV4AmmInfoAccountEvent(RaydiumAmmV4AmmInfoAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -102,10 +103,8 @@ pub fn amm_info_parser(account: &AccountPretty, mut metadata: EventMetadata) ->
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             amm_info: amm_info,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
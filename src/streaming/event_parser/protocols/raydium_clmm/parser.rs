@@ -1,7 +1,7 @@
 use crate::streaming::event_parser::{
     common::{
-        read_i32_le, read_option_bool, read_u128_le, read_u64_le, read_u8_le, EventMetadata,
-        EventType,
+        read_i32_le, read_option_bool, read_u128_le, read_u64_le, read_u8_le, DiscriminatorScheme,
+        EventMetadata, EventType,
     },
     protocols::raydium_clmm::{
         discriminators, RaydiumClmmClosePositionEvent, RaydiumClmmCreatePoolEvent,
@@ -17,6 +17,9 @@ use solana_sdk::pubkey::Pubkey;
 pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
 
+/// Raydium CLMM 账户数据的前缀格式，账户解析器按此提取数据主体
+pub const RAYDIUM_CLMM_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::Anchor8;
+
 /// 解析 Raydium CLMM instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
@@ -83,6 +86,16 @@ pub fn parse_raydium_clmm_account_data(
     }
 }
 
+/// 只根据账户 discriminator 判断会命中的账户解析器，不解码账户数据
+pub fn identify_raydium_clmm_account(discriminator: &[u8]) -> Option<EventType> {
+    match discriminator {
+        discriminators::AMM_CONFIG => Some(EventType::AccountRaydiumClmmAmmConfig),
+        discriminators::POOL_STATE => Some(EventType::AccountRaydiumClmmPoolState),
+        discriminators::TICK_ARRAY_STATE => Some(EventType::AccountRaydiumClmmTickArrayState),
+        _ => None,
+    }
+}
+
 /// 解析打开仓位V2指令事件
 fn parse_open_position_v2_instruction(
     data: &[u8],
@@ -4,10 +4,10 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::{
     event_parser::{
-        common::{EventMetadata, EventType},
+        common::{extract_account_body, EventMetadata, EventType},
         protocols::raydium_clmm::{
-            RaydiumClmmAmmConfigAccountEvent, RaydiumClmmPoolStateAccountEvent,
-            RaydiumClmmTickArrayStateAccountEvent,
+            parser::RAYDIUM_CLMM_DISCRIMINATOR_SCHEME, RaydiumClmmAmmConfigAccountEvent,
+            RaydiumClmmPoolStateAccountEvent, RaydiumClmmTickArrayStateAccountEvent,
         },
         DexEvent,
     },
@@ -40,11 +40,10 @@ pub fn amm_config_decode(data: &[u8]) -> Option<AmmConfig> {
 pub fn amm_config_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmAmmConfig;
 
-    if account.data.len() < AMM_CONFIG_SIZE + 8 {
-        return None;
-    }
-    if let Some(amm_config) = amm_config_decode(&account.data[8..AMM_CONFIG_SIZE + 8]) {
-        Some(DexEvent::RaydiumClmmAmmConfigAccountEvent(RaydiumClmmAmmConfigAccountEvent {
+    let body =
+        extract_account_body(RAYDIUM_CLMM_DISCRIMINATOR_SCHEME, &account.data, AMM_CONFIG_SIZE)?;
+    amm_config_decode(body).map(|amm_config| {
+        DexEvent::RaydiumClmmAmmConfigAccountEvent(RaydiumClmmAmmConfigAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -52,10 +51,8 @@ pub fn amm_config_parser(account: &AccountPretty, mut metadata: EventMetadata) -
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             amm_config: amm_config,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
@@ -127,11 +124,10 @@ pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
 pub fn pool_state_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmPoolState;
 
-    if account.data.len() < POOL_STATE_SIZE + 8 {
-        return None;
-    }
-    if let Some(pool_state) = pool_state_decode(&account.data[8..POOL_STATE_SIZE + 8]) {
-        Some(DexEvent::RaydiumClmmPoolStateAccountEvent(RaydiumClmmPoolStateAccountEvent {
+    let body =
+        extract_account_body(RAYDIUM_CLMM_DISCRIMINATOR_SCHEME, &account.data, POOL_STATE_SIZE)?;
+    pool_state_decode(body).map(|pool_state| {
+        DexEvent::RaydiumClmmPoolStateAccountEvent(RaydiumClmmPoolStateAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -139,10 +135,8 @@ pub fn pool_state_parser(account: &AccountPretty, mut metadata: EventMetadata) -
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             pool_state: pool_state,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
@@ -210,24 +204,20 @@ pub fn tick_array_state_parser(
 ) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumClmmTickArrayState;
 
-    if account.data.len() < TICK_ARRAY_STATE_SIZE + 8 {
-        return None;
-    }
-    if let Some(tick_array_state) =
-        tick_array_state_decode(&account.data[8..TICK_ARRAY_STATE_SIZE + 8])
-    {
-        Some(DexEvent::RaydiumClmmTickArrayStateAccountEvent(
-            RaydiumClmmTickArrayStateAccountEvent {
-                metadata,
-                pubkey: account.pubkey,
-                executable: account.executable,
-                lamports: account.lamports,
-                owner: account.owner,
-                rent_epoch: account.rent_epoch,
-                tick_array_state: tick_array_state,
-            },
-        ))
-    } else {
-        None
-    }
+    let body = extract_account_body(
+        RAYDIUM_CLMM_DISCRIMINATOR_SCHEME,
+        &account.data,
+        TICK_ARRAY_STATE_SIZE,
+    )?;
+    tick_array_state_decode(body).map(|tick_array_state| {
+        DexEvent::RaydiumClmmTickArrayStateAccountEvent(RaydiumClmmTickArrayStateAccountEvent {
+            metadata,
+            pubkey: account.pubkey,
+            executable: account.executable,
+            lamports: account.lamports,
+            owner: account.owner,
+            rent_epoch: account.rent_epoch,
+            tick_array_state,
+        })
+    })
 }
@@ -1,7 +1,7 @@
 use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::event_parser::{
-    common::{read_u64_le, EventMetadata, EventType},
+    common::{read_u64_le, DiscriminatorScheme, EventMetadata, EventType},
     protocols::raydium_cpmm::{
         discriminators, RaydiumCpmmDepositEvent, RaydiumCpmmInitializeEvent, RaydiumCpmmSwapEvent,
         RaydiumCpmmWithdrawEvent,
@@ -13,6 +13,9 @@ use crate::streaming::event_parser::{
 pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
 
+/// Raydium CPMM 账户数据的前缀格式，账户解析器按此提取数据主体
+pub const RAYDIUM_CPMM_DISCRIMINATOR_SCHEME: DiscriminatorScheme = DiscriminatorScheme::Anchor8;
+
 /// 解析 Raydium CPMM instruction data
 ///
 /// 根据判别器路由到具体的 instruction 解析函数
@@ -67,6 +70,15 @@ pub fn parse_raydium_cpmm_account_data(
     }
 }
 
+/// 只根据账户 discriminator 判断会命中的账户解析器，不解码账户数据
+pub fn identify_raydium_cpmm_account(discriminator: &[u8]) -> Option<EventType> {
+    match discriminator {
+        discriminators::AMM_CONFIG => Some(EventType::AccountRaydiumCpmmAmmConfig),
+        discriminators::POOL_STATE => Some(EventType::AccountRaydiumCpmmPoolState),
+        _ => None,
+    }
+}
+
 
 /// 解析提款指令事件
 fn parse_withdraw_instruction(
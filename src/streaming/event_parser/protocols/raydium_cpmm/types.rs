@@ -4,9 +4,10 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::streaming::{
     event_parser::{
-        common::{EventMetadata, EventType},
+        common::{extract_account_body, EventMetadata, EventType},
         protocols::raydium_cpmm::{
-            RaydiumCpmmAmmConfigAccountEvent, RaydiumCpmmPoolStateAccountEvent,
+            parser::RAYDIUM_CPMM_DISCRIMINATOR_SCHEME, RaydiumCpmmAmmConfigAccountEvent,
+            RaydiumCpmmPoolStateAccountEvent,
         },
         DexEvent,
     },
@@ -40,11 +41,10 @@ pub fn amm_config_decode(data: &[u8]) -> Option<AmmConfig> {
 pub fn amm_config_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumCpmmAmmConfig;
 
-    if account.data.len() < AMM_CONFIG_SIZE + 8 {
-        return None;
-    }
-    if let Some(amm_config) = amm_config_decode(&account.data[8..AMM_CONFIG_SIZE + 8]) {
-        Some(DexEvent::RaydiumCpmmAmmConfigAccountEvent(RaydiumCpmmAmmConfigAccountEvent {
+    let body =
+        extract_account_body(RAYDIUM_CPMM_DISCRIMINATOR_SCHEME, &account.data, AMM_CONFIG_SIZE)?;
+    amm_config_decode(body).map(|amm_config| {
+        DexEvent::RaydiumCpmmAmmConfigAccountEvent(RaydiumCpmmAmmConfigAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -52,10 +52,8 @@ pub fn amm_config_parser(account: &AccountPretty, mut metadata: EventMetadata) -
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             amm_config: amm_config,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
@@ -102,11 +100,10 @@ pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
 pub fn pool_state_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
     metadata.event_type = EventType::AccountRaydiumCpmmPoolState;
 
-    if account.data.len() < POOL_STATE_SIZE + 8 {
-        return None;
-    }
-    if let Some(pool_state) = pool_state_decode(&account.data[8..POOL_STATE_SIZE + 8]) {
-        Some(DexEvent::RaydiumCpmmPoolStateAccountEvent(RaydiumCpmmPoolStateAccountEvent {
+    let body =
+        extract_account_body(RAYDIUM_CPMM_DISCRIMINATOR_SCHEME, &account.data, POOL_STATE_SIZE)?;
+    pool_state_decode(body).map(|pool_state| {
+        DexEvent::RaydiumCpmmPoolStateAccountEvent(RaydiumCpmmPoolStateAccountEvent {
             metadata,
             pubkey: account.pubkey,
             executable: account.executable,
@@ -114,8 +111,6 @@ pub fn pool_state_parser(account: &AccountPretty, mut metadata: EventMetadata) -
             owner: account.owner,
             rent_epoch: account.rent_epoch,
             pool_state: pool_state,
-        }))
-    } else {
-        None
-    }
+        })
+    })
 }
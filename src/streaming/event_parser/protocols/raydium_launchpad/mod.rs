@@ -0,0 +1,22 @@
+//! Raydium LaunchLab（Launchpad）池子账户
+//!
+//! Bonk 运行在 LaunchLab 程序之上，`bonk` 模块按当前 Bonk 使用的布局解码 `PoolState`。
+//! 本模块提供 LaunchLab 原始布局，两者总长度与前缀字段完全相同，区别仅在 `creator`
+//! 之后的 64 字节：
+//!
+//! | 偏移（不含 discriminator） | Bonk 布局 | LaunchLab 原始布局 |
+//! |---|---|---|
+//! | 357..358 | `token_program_flag: u8` | `padding[0]` 的一部分 |
+//! | 358..359 | `amm_creator_fee_on: AmmCreatorFeeOn` | `padding[0]` 的一部分 |
+//! | 359..367 | `platform_vesting_share: u64` | `padding[0..2]` 的一部分 |
+//! | 367..421 | `padding: [u8; 54]` | `padding: [u64; 8]` 的其余部分 |
+//!
+//! 两种布局使用相同的程序 ID 和账户 discriminator，因此 `DexEvent` 流默认按 Bonk 布局解析。
+//! 对于尚未启用上述字段的 LaunchLab 平台的池子，可直接调用
+//! [`types::pool_state_parser`] 得到不包含这些字段的 `RaydiumLaunchpadPoolStateAccountEvent`。
+
+pub mod events;
+pub mod types;
+
+pub use events::*;
+pub use types::*;
@@ -0,0 +1,85 @@
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::streaming::{
+    event_parser::{
+        common::{extract_account_body, EventMetadata, EventType},
+        protocols::{
+            bonk::{parser::BONK_DISCRIMINATOR_SCHEME, PoolStatus, VestingSchedule},
+            raydium_launchpad::RaydiumLaunchpadPoolStateAccountEvent,
+        },
+        DexEvent,
+    },
+    grpc::AccountPretty,
+};
+
+/// LaunchLab 原始布局的池状态，与 Bonk 布局的差异见模块文档
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshDeserialize)]
+pub struct PoolState {
+    pub epoch: u64,
+    pub auth_bump: u8,
+    pub status: u8,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub migrate_type: u8,
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base: u64,
+    pub real_quote: u64,
+    pub total_quote_fund_raising: u64,
+    pub quote_protocol_fee: u64,
+    pub platform_fee: u64,
+    pub migrate_fee: u64,
+    pub vesting_schedule: VestingSchedule,
+    pub global_config: Pubkey,
+    pub platform_config: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub creator: Pubkey,
+    #[serde(skip)]
+    pub padding: [u64; 8],
+}
+
+impl PoolState {
+    /// 池子状态，未知取值返回 `None`（与 Bonk 共用 `PoolStatus`）
+    pub fn pool_status(&self) -> Option<PoolStatus> {
+        match self.status {
+            0 => Some(PoolStatus::Fund),
+            1 => Some(PoolStatus::Migrate),
+            2 => Some(PoolStatus::Trade),
+            _ => None,
+        }
+    }
+}
+
+/// 不含 8 字节 discriminator，与 Bonk 布局的 `PoolState` 结构体长度相同
+pub const POOL_STATE_SIZE: usize = 8 + 5 + 8 * 10 + 8 * 5 + 32 * 7 + 8 * 8;
+
+pub fn pool_state_decode(data: &[u8]) -> Option<PoolState> {
+    if data.len() < POOL_STATE_SIZE {
+        return None;
+    }
+    borsh::from_slice::<PoolState>(&data[..POOL_STATE_SIZE]).ok()
+}
+
+pub fn pool_state_parser(account: &AccountPretty, mut metadata: EventMetadata) -> Option<DexEvent> {
+    metadata.event_type = EventType::AccountRaydiumLaunchpadPoolState;
+
+    let body = extract_account_body(BONK_DISCRIMINATOR_SCHEME, &account.data, POOL_STATE_SIZE)?;
+    pool_state_decode(body).map(|pool_state| {
+        DexEvent::RaydiumLaunchpadPoolStateAccountEvent(RaydiumLaunchpadPoolStateAccountEvent {
+            metadata,
+            pubkey: account.pubkey,
+            executable: account.executable,
+            lamports: account.lamports,
+            owner: account.owner,
+            rent_epoch: account.rent_epoch,
+            pool_state,
+        })
+    })
+}
@@ -1,8 +1,12 @@
+use crate::streaming::event_parser::common::{DiscriminatorScheme, ProtocolType};
 use crate::streaming::event_parser::protocols::{
-    bonk::parser::BONK_PROGRAM_ID, meteora_damm_v2::parser::METEORA_DAMM_V2_PROGRAM_ID,
-    pumpfun::parser::PUMPFUN_PROGRAM_ID, pumpswap::parser::PUMPSWAP_PROGRAM_ID,
-    raydium_amm_v4::parser::RAYDIUM_AMM_V4_PROGRAM_ID, raydium_clmm::parser::RAYDIUM_CLMM_PROGRAM_ID,
-    raydium_cpmm::parser::RAYDIUM_CPMM_PROGRAM_ID,
+    bonk::parser::{BONK_DISCRIMINATOR_SCHEME, BONK_PROGRAM_ID},
+    meteora_damm_v2::parser::{METEORA_DAMM_V2_DISCRIMINATOR_SCHEME, METEORA_DAMM_V2_PROGRAM_ID},
+    pumpfun::parser::{PUMPFUN_DISCRIMINATOR_SCHEME, PUMPFUN_PROGRAM_ID},
+    pumpswap::parser::{PUMPSWAP_DISCRIMINATOR_SCHEME, PUMPSWAP_PROGRAM_ID},
+    raydium_amm_v4::parser::{RAYDIUM_AMM_V4_DISCRIMINATOR_SCHEME, RAYDIUM_AMM_V4_PROGRAM_ID},
+    raydium_clmm::parser::{RAYDIUM_CLMM_DISCRIMINATOR_SCHEME, RAYDIUM_CLMM_PROGRAM_ID},
+    raydium_cpmm::parser::{RAYDIUM_CPMM_DISCRIMINATOR_SCHEME, RAYDIUM_CPMM_PROGRAM_ID},
 };
 use anyhow::{anyhow, Result};
 use solana_sdk::pubkey::Pubkey;
@@ -20,6 +24,17 @@ pub enum Protocol {
 }
 
 impl Protocol {
+    /// 所有支持的协议
+    pub const ALL: [Protocol; 7] = [
+        Protocol::PumpSwap,
+        Protocol::PumpFun,
+        Protocol::Bonk,
+        Protocol::RaydiumCpmm,
+        Protocol::RaydiumClmm,
+        Protocol::RaydiumAmmV4,
+        Protocol::MeteoraDammV2,
+    ];
+
     pub fn get_program_id(&self) -> Vec<Pubkey> {
         match self {
             Protocol::PumpSwap => vec![PUMPSWAP_PROGRAM_ID],
@@ -31,6 +46,48 @@ impl Protocol {
             Protocol::MeteoraDammV2 => vec![METEORA_DAMM_V2_PROGRAM_ID],
         }
     }
+
+    /// 协议账户数据的前缀格式，由各协议的 `*_DISCRIMINATOR_SCHEME` 常量定义
+    pub fn discriminator_scheme(&self) -> DiscriminatorScheme {
+        match self {
+            Protocol::PumpSwap => PUMPSWAP_DISCRIMINATOR_SCHEME,
+            Protocol::PumpFun => PUMPFUN_DISCRIMINATOR_SCHEME,
+            Protocol::Bonk => BONK_DISCRIMINATOR_SCHEME,
+            Protocol::RaydiumCpmm => RAYDIUM_CPMM_DISCRIMINATOR_SCHEME,
+            Protocol::RaydiumClmm => RAYDIUM_CLMM_DISCRIMINATOR_SCHEME,
+            Protocol::RaydiumAmmV4 => RAYDIUM_AMM_V4_DISCRIMINATOR_SCHEME,
+            Protocol::MeteoraDammV2 => METEORA_DAMM_V2_DISCRIMINATOR_SCHEME,
+        }
+    }
+
+    /// 协议在 [`ProtocolSet`] 等位掩码中对应的位
+    #[inline]
+    pub(crate) fn bit(&self) -> u32 {
+        let index = match self {
+            Protocol::PumpSwap => 0,
+            Protocol::PumpFun => 1,
+            Protocol::Bonk => 2,
+            Protocol::RaydiumCpmm => 3,
+            Protocol::RaydiumClmm => 4,
+            Protocol::RaydiumAmmV4 => 5,
+            Protocol::MeteoraDammV2 => 6,
+        };
+        1 << index
+    }
+
+    /// 由事件元数据中的协议类型获取协议，`Common` 没有对应协议
+    pub fn from_protocol_type(protocol_type: &ProtocolType) -> Option<Protocol> {
+        match protocol_type {
+            ProtocolType::PumpSwap => Some(Protocol::PumpSwap),
+            ProtocolType::PumpFun => Some(Protocol::PumpFun),
+            ProtocolType::Bonk => Some(Protocol::Bonk),
+            ProtocolType::RaydiumCpmm => Some(Protocol::RaydiumCpmm),
+            ProtocolType::RaydiumClmm => Some(Protocol::RaydiumClmm),
+            ProtocolType::RaydiumAmmV4 => Some(Protocol::RaydiumAmmV4),
+            ProtocolType::MeteoraDammV2 => Some(Protocol::MeteoraDammV2),
+            ProtocolType::Common => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Protocol {
@@ -63,3 +120,87 @@ impl std::str::FromStr for Protocol {
         }
     }
 }
+
+/// 协议集合，按位存储，`contains` 为常数时间
+///
+/// ```ignore
+/// let bonk_only = ProtocolSet::only(Protocol::Bonk);
+/// let event = AccountEventParser::parse_account_event_in(bonk_only, account, None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ProtocolSet(u32);
+
+impl ProtocolSet {
+    /// 空集合
+    pub const EMPTY: ProtocolSet = ProtocolSet(0);
+
+    /// 包含所有支持的协议
+    pub fn all() -> Self {
+        Protocol::ALL.into_iter().collect()
+    }
+
+    /// 只包含 `protocol`
+    pub fn only(protocol: Protocol) -> Self {
+        ProtocolSet(protocol.bit())
+    }
+
+    /// 加入 `protocol`
+    pub fn with(mut self, protocol: Protocol) -> Self {
+        self.insert(protocol);
+        self
+    }
+
+    pub fn insert(&mut self, protocol: Protocol) {
+        self.0 |= protocol.bit();
+    }
+
+    pub fn remove(&mut self, protocol: &Protocol) {
+        self.0 &= !protocol.bit();
+    }
+
+    #[inline]
+    pub fn contains(&self, protocol: &Protocol) -> bool {
+        self.0 & protocol.bit() != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// 集合中的协议，按 [`Protocol::ALL`] 的顺序
+    pub fn iter(&self) -> impl Iterator<Item = Protocol> + '_ {
+        Protocol::ALL.into_iter().filter(|protocol| self.contains(protocol))
+    }
+}
+
+impl FromIterator<Protocol> for ProtocolSet {
+    fn from_iter<I: IntoIterator<Item = Protocol>>(iter: I) -> Self {
+        let mut set = ProtocolSet::EMPTY;
+        for protocol in iter {
+            set.insert(protocol);
+        }
+        set
+    }
+}
+
+impl From<&[Protocol]> for ProtocolSet {
+    fn from(protocols: &[Protocol]) -> Self {
+        protocols.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_raydium_amm_v4_accounts_have_no_discriminator() {
+        for protocol in Protocol::ALL {
+            let expected = match protocol {
+                Protocol::RaydiumAmmV4 => DiscriminatorScheme::None,
+                _ => DiscriminatorScheme::Anchor8,
+            };
+            assert_eq!(protocol.discriminator_scheme(), expected, "{protocol:?}");
+        }
+    }
+}
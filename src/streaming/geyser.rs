@@ -0,0 +1,189 @@
+//! Geyser 插件账户更新适配（需要启用 `geyser` feature）
+//!
+//! 在 Geyser 插件中运行时，账户更新以 `ReplicaAccountInfoVersions` 的形式交给插件，而不是
+//! gRPC 的 `SubscribeUpdateAccount`。[`account_from_geyser`] 将其转换为 `AccountPretty`，
+//! 之后与 gRPC 使用相同的解析流程：
+//!
+//! ```ignore
+//! fn update_account(
+//!     &self,
+//!     account: ReplicaAccountInfoVersions,
+//!     slot: Slot,
+//!     _is_startup: bool,
+//! ) -> geyser_plugin_interface::Result<()> {
+//!     let Some(account) = account_from_geyser(&account, slot) else {
+//!         return Ok(());
+//!     };
+//!     if let Some(event) = AccountEventParser::parse_account_event(&protocols, account, None) {
+//!         // ...
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use agave_geyser_plugin_interface::geyser_plugin_interface::{
+    ReplicaAccountInfo, ReplicaAccountInfoV2, ReplicaAccountInfoV3, ReplicaAccountInfoVersions,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::grpc::AccountPretty;
+
+/// 各版本 `ReplicaAccountInfo` 共有的字段
+struct GeyserAccount<'a> {
+    pubkey: &'a [u8],
+    owner: &'a [u8],
+    lamports: u64,
+    executable: bool,
+    rent_epoch: u64,
+    data: &'a [u8],
+    write_version: u64,
+    signature: Option<Signature>,
+}
+
+impl GeyserAccount<'_> {
+    /// pubkey 或 owner 不是 32 字节时返回 `None`
+    fn into_account_pretty(self, slot: u64) -> Option<AccountPretty> {
+        Some(AccountPretty {
+            slot,
+            signature: self.signature.unwrap_or_default(),
+            pubkey: Pubkey::try_from(self.pubkey).ok()?,
+            executable: self.executable,
+            lamports: self.lamports,
+            owner: Pubkey::try_from(self.owner).ok()?,
+            rent_epoch: self.rent_epoch,
+            data: self.data.to_vec(),
+            recv_us: get_high_perf_clock(),
+            write_version: self.write_version,
+        })
+    }
+}
+
+/// 将 Geyser 插件收到的账户更新转换为 `AccountPretty`
+///
+/// `slot` 为 `update_account` 回调的 slot 参数。V2、V3 版本带有触发更新的交易时填充
+/// `signature`，否则为默认签名；pubkey 或 owner 长度不是 32 字节时返回 `None`
+pub fn account_from_geyser(
+    account: &ReplicaAccountInfoVersions,
+    slot: u64,
+) -> Option<AccountPretty> {
+    match account {
+        ReplicaAccountInfoVersions::V0_0_1(info) => account_from_geyser_v1(info, slot),
+        ReplicaAccountInfoVersions::V0_0_2(info) => account_from_geyser_v2(info, slot),
+        ReplicaAccountInfoVersions::V0_0_3(info) => account_from_geyser_v3(info, slot),
+    }
+}
+
+/// 与 [`account_from_geyser`] 相同，用于 `ReplicaAccountInfo`（V1）
+pub fn account_from_geyser_v1(info: &ReplicaAccountInfo, slot: u64) -> Option<AccountPretty> {
+    GeyserAccount {
+        pubkey: info.pubkey,
+        owner: info.owner,
+        lamports: info.lamports,
+        executable: info.executable,
+        rent_epoch: info.rent_epoch,
+        data: info.data,
+        write_version: info.write_version,
+        signature: None,
+    }
+    .into_account_pretty(slot)
+}
+
+/// 与 [`account_from_geyser`] 相同，用于 `ReplicaAccountInfoV2`
+pub fn account_from_geyser_v2(info: &ReplicaAccountInfoV2, slot: u64) -> Option<AccountPretty> {
+    GeyserAccount {
+        pubkey: info.pubkey,
+        owner: info.owner,
+        lamports: info.lamports,
+        executable: info.executable,
+        rent_epoch: info.rent_epoch,
+        data: info.data,
+        write_version: info.write_version,
+        signature: info.txn_signature.copied(),
+    }
+    .into_account_pretty(slot)
+}
+
+/// 与 [`account_from_geyser`] 相同，用于 `ReplicaAccountInfoV3`
+pub fn account_from_geyser_v3(info: &ReplicaAccountInfoV3, slot: u64) -> Option<AccountPretty> {
+    GeyserAccount {
+        pubkey: info.pubkey,
+        owner: info.owner,
+        lamports: info.lamports,
+        executable: info.executable,
+        rent_epoch: info.rent_epoch,
+        data: info.data,
+        write_version: info.write_version,
+        signature: info.txn.map(|txn| *txn.signature()),
+    }
+    .into_account_pretty(slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+    use crate::streaming::event_parser::core::traits::AccountEvent;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::event_parser::{DexEvent, Protocol};
+
+    fn pool_state_data() -> Vec<u8> {
+        let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+        data.extend(
+            borsh::to_vec(&PoolState { real_quote: 10_000, ..Default::default() }).unwrap(),
+        );
+        data
+    }
+
+    #[test]
+    fn synthetic_replica_account_parses_into_a_pool_state_event() {
+        let pubkey = Pubkey::new_unique();
+        let data = pool_state_data();
+        let signature = Signature::from([9; 64]);
+        let info = ReplicaAccountInfoV2 {
+            pubkey: pubkey.as_ref(),
+            lamports: 2_039_280,
+            owner: BONK_PROGRAM_ID.as_ref(),
+            executable: false,
+            rent_epoch: u64::MAX,
+            data: &data,
+            write_version: 77,
+            txn_signature: Some(&signature),
+        };
+
+        let account = account_from_geyser(&ReplicaAccountInfoVersions::V0_0_2(&info), 300).unwrap();
+        assert_eq!((account.slot, account.write_version), (300, 77));
+        assert_eq!(account.signature, signature);
+        let event = AccountEventParser::parse_account_event(&[Protocol::Bonk], account, None);
+        let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+            panic!("expected a pool state event: {event:?}");
+        };
+        assert_eq!(event.pubkey(), pubkey);
+        assert_eq!(event.metadata.slot, 300);
+        assert_eq!(event.pool_state.real_quote, 10_000);
+        assert_eq!((event.base.lamports, event.base.rent_epoch), (2_039_280, u64::MAX));
+    }
+
+    #[test]
+    fn v1_account_has_no_signature_and_bad_keys_are_rejected() {
+        let pubkey = Pubkey::new_unique();
+        let data = pool_state_data();
+        let info = ReplicaAccountInfo {
+            pubkey: pubkey.as_ref(),
+            lamports: 1,
+            owner: BONK_PROGRAM_ID.as_ref(),
+            executable: false,
+            rent_epoch: 0,
+            data: &data,
+            write_version: 5,
+        };
+        let account = account_from_geyser(&ReplicaAccountInfoVersions::V0_0_1(&info), 9).unwrap();
+        assert_eq!((account.pubkey, account.signature), (pubkey, Signature::default()));
+
+        let truncated = ReplicaAccountInfo { pubkey: &[1; 31], ..info };
+        assert!(account_from_geyser_v1(&truncated, 9).is_none());
+    }
+}
@@ -0,0 +1,244 @@
+//! 先快照、后实时的启动流程
+//!
+//! 先拉取 `getProgramAccounts` 快照再建立订阅时，两者之间的更新会丢失。
+//! [`snapshot_then_subscribe`] 反过来先建立订阅并在后台缓冲实时事件，再拉取快照：
+//! 依次产出快照事件和缓冲的实时事件，丢弃 slot 早于快照的实时事件，之后实时事件直接产出。
+//!
+//! 快照的 slot 取自 `getProgramAccounts` 响应的 context slot。缓冲的实时事件按账户去重：
+//! slot 不晚于快照的更新已包含在快照中，同一账户 `(slot, write_version)` 不大于上一次产出的
+//! 更新视为重复，均被丢弃。
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use futures::channel::mpsc;
+use futures::{future, stream, Stream, StreamExt};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{OptionalContext, Response, RpcKeyedAccount};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_client::{GeyserGrpcClient, Interceptor};
+use yellowstone_grpc_proto::geyser::{
+    subscribe_request_filter_accounts_filter::Filter,
+    subscribe_request_filter_accounts_filter_memcmp::Data, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterAccounts, SubscribeRequestFilterAccountsFilter,
+};
+
+use super::error::StreamError;
+use super::event_stream::{open_account_events, SharedDedup};
+use super::types::AccountPretty;
+use crate::common::SolanaRpcClient;
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+use crate::streaming::event_parser::{DexEvent, Protocol};
+
+/// 订阅 `program` 下符合 `filters` 的账户，先产出快照再产出实时更新
+///
+/// `filters` 同时用于 gRPC 订阅和 `getProgramAccounts`，不支持 `Lamports` 过滤条件。
+/// 拉取快照期间的实时事件缓冲在内存中（不限数量）
+///
+/// ```ignore
+/// let client = SubscriptionManager::new(endpoint, x_token, config).connect().await?;
+/// let filters = pool_state_filter_by_creator(&creator).filters;
+/// let events = snapshot_then_subscribe(client, &rpc, BONK_PROGRAM_ID, filters).await?;
+/// futures::pin_mut!(events);
+/// while let Some(event) = events.next().await {
+///     cache.apply(event?);
+/// }
+/// ```
+pub async fn snapshot_then_subscribe<I>(
+    client: GeyserGrpcClient<I>,
+    rpc: &SolanaRpcClient,
+    program: Pubkey,
+    filters: Vec<SubscribeRequestFilterAccountsFilter>,
+) -> Result<impl Stream<Item = Result<DexEvent, StreamError>>, StreamError>
+where
+    I: Interceptor + Send + 'static,
+{
+    let rpc_filters = filters.iter().map(to_rpc_filter).collect::<Result<Vec<_>, _>>()?;
+    let request = SubscribeRequest {
+        accounts: HashMap::from([(
+            "program".to_owned(),
+            SubscribeRequestFilterAccounts {
+                account: vec![],
+                owner: vec![program.to_string()],
+                filters,
+                nonempty_txn_signature: None,
+            },
+        )]),
+        commitment: Some(CommitmentLevel::Processed.into()),
+        ..Default::default()
+    };
+    let mut live =
+        open_account_events(client, request, Protocol::ALL.to_vec(), None, SharedDedup::default())
+            .await?;
+
+    // 拉取快照期间持续读取订阅，避免服务端因客户端读取过慢而断开
+    let (sender, buffered) = mpsc::unbounded();
+    tokio::spawn(async move {
+        while let Some(item) = live.next().await {
+            if sender.unbounded_send(item).is_err() {
+                break;
+            }
+        }
+    });
+
+    let rpc_error = |e: ClientError| StreamError::Connect(e.to_string());
+    let config = RpcProgramAccountsConfig {
+        filters: Some(rpc_filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::processed()),
+            ..Default::default()
+        },
+        with_context: Some(true),
+        ..Default::default()
+    };
+    // `get_program_ui_accounts_with_config` 会丢弃响应的 context，这里直接发送请求以取得快照的 slot
+    let response = rpc
+        .send::<OptionalContext<Vec<RpcKeyedAccount>>>(
+            RpcRequest::GetProgramAccounts,
+            serde_json::json!([program.to_string(), config]),
+        )
+        .await
+        .map_err(rpc_error)?;
+    let OptionalContext::Context(Response { context, value: accounts }) = response else {
+        return Err(StreamError::Connect("getProgramAccounts response has no context".to_owned()));
+    };
+    let snapshot_slot = context.slot;
+
+    let protocols = Protocol::ALL;
+    let snapshot: Vec<_> = accounts
+        .into_iter()
+        .filter_map(|keyed| {
+            let pubkey = Pubkey::from_str(&keyed.pubkey).ok()?;
+            let account = keyed.account.decode::<Account>()?;
+            let account = AccountPretty {
+                slot: snapshot_slot,
+                pubkey,
+                executable: account.executable,
+                lamports: account.lamports,
+                owner: account.owner,
+                rent_epoch: account.rent_epoch,
+                data: account.data,
+                recv_us: get_high_perf_clock(),
+                ..Default::default()
+            };
+            AccountEventParser::parse_account_event(&protocols, account, None)
+        })
+        .collect();
+
+    Ok(replay_after_snapshot(snapshot, snapshot_slot, buffered))
+}
+
+/// 依次产出快照事件和 `live` 中快照之后的事件
+///
+/// slot 不晚于 `snapshot_slot` 的实时事件已包含在快照中；同一账户的事件按
+/// `(slot, write_version)` 去重，不大于该账户上一次产出的事件被丢弃
+fn replay_after_snapshot<S>(
+    snapshot: Vec<DexEvent>,
+    snapshot_slot: u64,
+    live: S,
+) -> impl Stream<Item = Result<DexEvent, StreamError>>
+where
+    S: Stream<Item = Result<DexEvent, StreamError>>,
+{
+    let mut last_emitted: HashMap<Pubkey, (u64, Option<u64>)> = HashMap::new();
+    let live = live.filter(move |item| {
+        let Ok(event) = item else {
+            return future::ready(true);
+        };
+        let position = (event.slot(), event.metadata().write_version);
+        let keep = position.0 > snapshot_slot
+            && match event.account_pubkey() {
+                Some(pubkey) => match last_emitted.get(&pubkey) {
+                    Some(last) if position <= *last => false,
+                    _ => {
+                        last_emitted.insert(pubkey, position);
+                        true
+                    }
+                },
+                None => true,
+            };
+        future::ready(keep)
+    });
+    stream::iter(snapshot.into_iter().map(Ok)).chain(live)
+}
+
+/// 将 gRPC 账户过滤条件转换为 `getProgramAccounts` 的过滤条件
+fn to_rpc_filter(
+    filter: &SubscribeRequestFilterAccountsFilter,
+) -> Result<RpcFilterType, StreamError> {
+    let unsupported = || StreamError::Connect(format!("unsupported snapshot filter: {filter:?}"));
+    match filter.filter.as_ref().ok_or_else(unsupported)? {
+        Filter::Memcmp(memcmp) => {
+            let bytes = match memcmp.data.as_ref().ok_or_else(unsupported)? {
+                Data::Bytes(bytes) => MemcmpEncodedBytes::Bytes(bytes.clone()),
+                Data::Base58(data) => MemcmpEncodedBytes::Base58(data.clone()),
+                Data::Base64(data) => MemcmpEncodedBytes::Base64(data.clone()),
+            };
+            Ok(RpcFilterType::Memcmp(Memcmp::new(memcmp.offset as usize, bytes)))
+        }
+        Filter::Datasize(size) => Ok(RpcFilterType::DataSize(*size)),
+        Filter::TokenAccountState(_) => Ok(RpcFilterType::TokenAccountState),
+        Filter::Lamports(_) => Err(unsupported()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn account_event(pubkey: Pubkey, slot: u64, write_version: u64) -> DexEvent {
+        // `write_version` 为 0 时解析结果中为 `None`，与快照事件一致
+        let account =
+            AccountPrettyBuilder::new().pubkey(pubkey).slot(slot).write_version(write_version);
+        AccountEventParser::parse_account_event(&[], account.build(), None).unwrap()
+    }
+
+    fn position(item: &Result<DexEvent, StreamError>) -> Option<(Pubkey, u64, Option<u64>)> {
+        let event = item.as_ref().ok()?;
+        Some((event.account_pubkey()?, event.slot(), event.metadata().write_version))
+    }
+
+    #[tokio::test]
+    async fn boundary_updates_are_neither_missed_nor_applied_twice() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let snapshot = vec![account_event(a, 100, 0), account_event(b, 100, 0)];
+        let live = vec![
+            Ok(account_event(a, 99, 5)),
+            Ok(account_event(b, 100, 7)),
+            Ok(account_event(a, 101, 9)),
+            Err(StreamError::Closed),
+            Ok(account_event(a, 101, 9)),
+            Ok(account_event(b, 101, 8)),
+            Ok(account_event(a, 101, 8)),
+            Ok(account_event(c, 102, 1)),
+            Ok(account_event(a, 102, 2)),
+        ];
+
+        let output: Vec<_> =
+            replay_after_snapshot(snapshot, 100, stream::iter(live)).collect().await;
+
+        let positions: Vec<_> = output.iter().map(position).collect();
+        assert_eq!(
+            positions,
+            vec![
+                Some((a, 100, None)),
+                Some((b, 100, None)),
+                Some((a, 101, Some(9))),
+                None,
+                Some((b, 101, Some(8))),
+                Some((c, 102, Some(1))),
+                Some((a, 102, Some(2))),
+            ]
+        );
+        assert!(matches!(output[3], Err(StreamError::Closed)));
+    }
+}
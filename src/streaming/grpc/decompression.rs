@@ -0,0 +1,271 @@
+//! gzip 解压比例保护
+//!
+//! [`SubscriptionManager::open`](super::SubscriptionManager::open) 建立的订阅接受服务端 gzip
+//! 压缩的消息。tonic 只保证解压后不超过 `max_decoding_message_size`，异常或恶意的服务端仍可以
+//! 用很小的压缩帧让每条消息都解压出接近上限的数据。[`DecompressionGuard`] 包在 gRPC 服务外层，
+//! 在 tonic 解码之前逐帧检查解压后与压缩前的大小之比，超过 `max_decompression_ratio` 时以
+//! `OutOfRange` 状态结束订阅，与消息过大的处理相同（见 [`StreamError::is_message_too_large`]）
+//!
+//! [`StreamError::is_message_too_large`]: super::StreamError::is_message_too_large
+
+use std::io::{self, Read};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use flate2::read::GzDecoder;
+use http_body::{Body, Frame, SizeHint};
+use tonic::codegen::{http, BoxFuture, Bytes, Service, StdError};
+use tonic::Status;
+
+/// gRPC 帧头长度：1 字节压缩标志 + 4 字节大端消息长度
+const FRAME_HEADER_LEN: usize = 5;
+
+/// 检查 gzip 压缩帧解压比例的 gRPC 服务包装
+#[derive(Debug, Clone)]
+pub struct DecompressionGuard<S> {
+    inner: S,
+    max_ratio: u32,
+    max_message_size: usize,
+}
+
+impl<S> DecompressionGuard<S> {
+    /// `max_message_size` 以上的帧由 tonic 直接拒绝，不再检查
+    pub fn new(inner: S, max_ratio: u32, max_message_size: usize) -> Self {
+        Self { inner, max_ratio, max_message_size }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for DecompressionGuard<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    ResBody: Send + 'static,
+{
+    type Response = http::Response<GuardedBody<ResBody>>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let response = self.inner.call(request);
+        let (max_ratio, max_message_size) = (self.max_ratio, self.max_message_size);
+        Box::pin(async move {
+            let response = response.await?;
+            // 只有服务端声明了 gzip 编码时帧才可能被压缩
+            let gzip = response.headers().get("grpc-encoding").is_some_and(|e| e == "gzip");
+            let scanner = gzip.then(|| FrameScanner::new(max_ratio, max_message_size));
+            Ok(response.map(|inner| GuardedBody { inner, scanner, error: None }))
+        })
+    }
+}
+
+/// [`DecompressionGuard`] 的响应体，原样转发数据并检查其中的压缩帧
+///
+/// 超限帧之前的数据照常转发，使之前的消息仍能被解码，之后再产出错误
+pub struct GuardedBody<B> {
+    inner: B,
+    scanner: Option<FrameScanner>,
+    error: Option<Status>,
+}
+
+impl<B> Body for GuardedBody<B>
+where
+    B: Body<Data = Bytes> + Unpin,
+    B::Error: Into<StdError>,
+{
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Status>>> {
+        let this = &mut *self;
+        if let Some(status) = this.error.take() {
+            return Poll::Ready(Some(Err(status)));
+        }
+        let frame = match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+            Some(Ok(frame)) => frame,
+            Some(Err(error)) => return Poll::Ready(Some(Err(Status::from_error(error.into())))),
+            None => return Poll::Ready(None),
+        };
+        let Some(scanner) = this.scanner.as_mut() else {
+            return Poll::Ready(Some(Ok(frame)));
+        };
+        let data = match frame.into_data() {
+            Ok(data) => data,
+            Err(trailers) => return Poll::Ready(Some(Ok(trailers))),
+        };
+        match scanner.scan(&data) {
+            Ok(()) => Poll::Ready(Some(Ok(Frame::data(data)))),
+            Err((0, status)) => Poll::Ready(Some(Err(status))),
+            Err((valid, status)) => {
+                this.error = Some(status);
+                Poll::Ready(Some(Ok(Frame::data(data.slice(..valid)))))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// 按 gRPC 帧格式切分响应体，收齐一个压缩帧后检查其解压比例
+///
+/// 未压缩的帧和超过 `max_message_size` 的帧直接跳过，不缓存其内容
+#[derive(Debug)]
+struct FrameScanner {
+    max_ratio: u32,
+    max_message_size: usize,
+    header: Vec<u8>,
+    message: Vec<u8>,
+    message_len: usize,
+    skip: usize,
+}
+
+impl FrameScanner {
+    fn new(max_ratio: u32, max_message_size: usize) -> Self {
+        Self {
+            max_ratio,
+            max_message_size,
+            header: Vec::with_capacity(FRAME_HEADER_LEN),
+            message: Vec::new(),
+            message_len: 0,
+            skip: 0,
+        }
+    }
+
+    /// 检查 `data` 中的帧，出错时同时返回超限帧在 `data` 中的起始位置（始于之前的数据时为 0）
+    fn scan(&mut self, mut data: &[u8]) -> Result<(), (usize, Status)> {
+        let total = data.len();
+        let mut frame_start = 0;
+        while !data.is_empty() {
+            if self.skip > 0 {
+                let n = self.skip.min(data.len());
+                self.skip -= n;
+                data = &data[n..];
+                continue;
+            }
+            if self.header.len() < FRAME_HEADER_LEN {
+                if self.header.is_empty() {
+                    frame_start = total - data.len();
+                }
+                let n = (FRAME_HEADER_LEN - self.header.len()).min(data.len());
+                self.header.extend_from_slice(&data[..n]);
+                data = &data[n..];
+                if self.header.len() < FRAME_HEADER_LEN {
+                    break;
+                }
+                let len = u32::from_be_bytes([
+                    self.header[1],
+                    self.header[2],
+                    self.header[3],
+                    self.header[4],
+                ]) as usize;
+                if self.header[0] == 0 || len > self.max_message_size {
+                    self.skip = len;
+                    self.header.clear();
+                    continue;
+                }
+                self.message_len = len;
+            }
+            let n = (self.message_len - self.message.len()).min(data.len());
+            self.message.extend_from_slice(&data[..n]);
+            data = &data[n..];
+            if self.message.len() == self.message_len {
+                check_ratio(&self.message, self.max_ratio).map_err(|e| (frame_start, e))?;
+                self.message.clear();
+                self.header.clear();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 解压 `message`，解压后超过压缩大小的 `max_ratio` 倍时返回 `OutOfRange`
+///
+/// 最多解压到超出限制的第一个字节为止；数据损坏时不在这里报错，交给 tonic 解码时处理
+fn check_ratio(message: &[u8], max_ratio: u32) -> Result<(), Status> {
+    let allowed = (message.len() as u64).saturating_mul(max_ratio as u64);
+    let mut decoder = GzDecoder::new(message).take(allowed.saturating_add(1));
+    match io::copy(&mut decoder, &mut io::sink()) {
+        Ok(inflated) if inflated > allowed => Err(Status::out_of_range(format!(
+            "Error decompressing: a {} byte message expands beyond the {max_ratio}x \
+             decompression ratio limit",
+            message.len()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use tonic::Code;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn frame(compressed: bool, message: &[u8]) -> Vec<u8> {
+        let mut frame = vec![compressed as u8];
+        frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        frame.extend_from_slice(message);
+        frame
+    }
+
+    #[test]
+    fn compressed_frame_within_the_ratio_passes() {
+        let message = gzip(&(0..=255u8).cycle().take(4096).collect::<Vec<_>>());
+        let mut scanner = FrameScanner::new(100, usize::MAX);
+        assert!(scanner.scan(&frame(true, &message)).is_ok());
+    }
+
+    #[test]
+    fn compressed_frame_beyond_the_ratio_split_across_chunks_is_rejected() {
+        let message = gzip(&vec![0u8; 1024 * 1024]);
+        let bytes = frame(true, &message);
+        let mut scanner = FrameScanner::new(100, usize::MAX);
+        let (head, tail) = bytes.split_at(3);
+        assert!(scanner.scan(head).is_ok());
+        let (start, status) = tail.chunks(64).find_map(|chunk| scanner.scan(chunk).err()).unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(status.code(), Code::OutOfRange);
+        assert!(status.message().contains("100x"));
+    }
+
+    #[test]
+    fn rejected_frame_reports_where_it_starts() {
+        let plain = frame(false, b"account");
+        let mut bytes = plain.clone();
+        bytes.extend(frame(true, &gzip(&vec![0u8; 64 * 1024])));
+        let mut scanner = FrameScanner::new(100, usize::MAX);
+        let (start, _) = scanner.scan(&bytes).unwrap_err();
+        assert_eq!(start, plain.len());
+    }
+
+    #[test]
+    fn uncompressed_and_oversized_frames_are_skipped() {
+        let zeros = gzip(&vec![0u8; 1024 * 1024]);
+        let mut bytes = frame(false, &vec![0u8; 4096]);
+        // 超过 max_message_size 的压缩帧由 tonic 拒绝
+        bytes.extend(frame(true, &zeros));
+        let mut scanner = FrameScanner::new(100, zeros.len() - 1);
+        assert!(scanner.scan(&bytes).is_ok());
+        assert!(scanner.message.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+//! 账户更新去重
+//!
+//! gRPC 账户订阅经常在同一 slot 重复投递同一账户。[`DedupLayer`] 按账户地址记录最新的
+//! `(slot, write_version)`，丢弃不比它新的更新，在解析之前过滤掉重复和乱序的旧数据。
+//! 记录的账户数量有上限，超出时淘汰最久未更新的账户，内存占用保持稳定。
+
+use std::collections::{HashMap, VecDeque};
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::types::AccountPretty;
+
+struct Seen {
+    version: (u64, u64),
+    /// 最近一次更新时的序号，用于识别淘汰队列中已过期的记录
+    touched: u64,
+}
+
+/// 按 `(pubkey, slot, write_version)` 去重，最多记录 `capacity` 个账户（LRU）
+pub struct DedupLayer {
+    capacity: usize,
+    seen: HashMap<Pubkey, Seen>,
+    /// 按更新顺序排列的 `(pubkey, touched)`，同一账户可能有多条记录，只有最新的一条有效
+    order: VecDeque<(Pubkey, u64)>,
+    tick: u64,
+    dropped: u64,
+}
+
+impl DedupLayer {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            seen: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            tick: 0,
+            dropped: 0,
+        }
+    }
+
+    /// 检查账户更新是否比已记录的更新更新，是则记录并返回 `true`，重复或过期的更新返回 `false`
+    pub fn accept(&mut self, account: &AccountPretty) -> bool {
+        let version = (account.slot, account.write_version);
+        if let Some(seen) = self.seen.get(&account.pubkey) {
+            if version <= seen.version {
+                self.dropped += 1;
+                return false;
+            }
+        }
+
+        self.tick += 1;
+        self.seen.insert(account.pubkey, Seen { version, touched: self.tick });
+        self.order.push_back((account.pubkey, self.tick));
+        self.evict();
+        true
+    }
+
+    fn evict(&mut self) {
+        while self.seen.len() > self.capacity {
+            let Some((pubkey, touched)) = self.order.pop_front() else {
+                break;
+            };
+            if self.seen.get(&pubkey).is_some_and(|seen| seen.touched == touched) {
+                self.seen.remove(&pubkey);
+            }
+        }
+        // 频繁更新的账户会在队列中留下大量过期记录，超过一定长度时压缩
+        if self.order.len() > self.capacity * 2 {
+            let seen = &self.seen;
+            self.order.retain(|(pubkey, touched)| {
+                seen.get(pubkey).is_some_and(|entry| entry.touched == *touched)
+            });
+        }
+    }
+
+    /// 被丢弃的重复或过期更新数量
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped
+    }
+
+    /// 当前记录的账户数量
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.seen.clear();
+        self.order.clear();
+    }
+}
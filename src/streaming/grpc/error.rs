@@ -0,0 +1,112 @@
+use std::fmt;
+use std::time::Duration;
+
+use tonic::{Code, Status};
+use yellowstone_grpc_client::GeyserGrpcClientError;
+
+/// gRPC 订阅过程中的错误
+#[derive(Debug)]
+pub enum StreamError {
+    /// 建立连接或发送订阅请求失败
+    Connect(String),
+    /// 服务端拒绝了认证（x-token 无效或没有权限）
+    Auth(Status),
+    /// 订阅建立后传输层返回错误
+    Transport(Status),
+    /// 收到无法解析的更新（缺少字段或字段长度不对）
+    Decode(String),
+    /// 服务端关闭了订阅流
+    Closed,
+    /// 超过停滞超时没有收到任何帧（含 ping），连接可能已失效，见 `StallDetector`
+    Stalled { idle: Duration },
+}
+
+impl StreamError {
+    /// 根据状态码区分认证错误和其他传输错误
+    pub fn from_status(status: Status) -> Self {
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => StreamError::Auth(status),
+            _ => StreamError::Transport(status),
+        }
+    }
+
+    /// 发送订阅请求失败时的错误，服务端返回的状态按 [`StreamError::from_status`] 分类
+    pub fn from_client_error(error: GeyserGrpcClientError) -> Self {
+        match error {
+            GeyserGrpcClientError::TonicStatus(status) => StreamError::from_status(status),
+            other => StreamError::Connect(other.to_string()),
+        }
+    }
+
+    /// 是否因为消息超过 `max_decoding_message_size` 而失败
+    pub fn is_message_too_large(&self) -> bool {
+        matches!(self, StreamError::Transport(status) if status.code() == Code::OutOfRange)
+    }
+
+    /// 重新连接是否有可能恢复
+    ///
+    /// 认证错误重连后仍会失败，解析错误与连接无关，这两类不应重试。消息过大
+    /// （[`StreamError::is_message_too_large`]）时重连后会再次收到同一帧，同样不应重试，
+    /// 需要调大 `max_decoding_message_size`
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            StreamError::Transport(_) => !self.is_message_too_large(),
+            StreamError::Connect(_) | StreamError::Closed | StreamError::Stalled { .. } => true,
+            StreamError::Auth(_) | StreamError::Decode(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Connect(e) => write!(f, "failed to open subscription: {e}"),
+            StreamError::Auth(status) => write!(f, "subscription rejected: {status}"),
+            StreamError::Transport(status) => write!(f, "subscription transport error: {status}"),
+            StreamError::Decode(e) => write!(f, "malformed subscription update: {e}"),
+            StreamError::Closed => write!(f, "subscription closed by server"),
+            StreamError::Stalled { idle } => {
+                write!(f, "subscription stalled: no updates or pings for {idle:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StreamError::Auth(status) | StreamError::Transport(status) => Some(status),
+            StreamError::Connect(_)
+            | StreamError::Decode(_)
+            | StreamError::Closed
+            | StreamError::Stalled { .. } => None,
+        }
+    }
+}
+
+impl From<Status> for StreamError {
+    fn from(status: Status) -> Self {
+        StreamError::from_status(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_too_large_is_not_retryable() {
+        let error = StreamError::from_status(Status::out_of_range("message length too large"));
+        assert!(error.is_message_too_large());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn retryable_classification() {
+        assert!(StreamError::from_status(Status::unavailable("reset")).is_retryable());
+        assert!(StreamError::Closed.is_retryable());
+        assert!(StreamError::Stalled { idle: Duration::from_secs(30) }.is_retryable());
+        assert!(!StreamError::from_status(Status::unauthenticated("bad token")).is_retryable());
+        assert!(!StreamError::Decode("missing account".to_owned()).is_retryable());
+    }
+}
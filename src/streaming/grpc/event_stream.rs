@@ -0,0 +1,730 @@
+//! 将 gRPC 账户订阅转换为 `DexEvent` 流
+//!
+//! 与 `YellowstoneGrpc::subscribe_events_immediate` 的回调方式不同，这里直接返回
+//! `Stream<Item = Result<DexEvent, StreamError>>`，可以与 `DexEventStreamExt` 等流适配器组合。
+//! 只处理账户更新：服务端的 ping 通过订阅请求的发送端回复，pong、slot 等其他更新被跳过，
+//! 无法解析的账户被过滤掉。
+//! 缺少字段的账户更新产出 `Decode` 错误后继续；底层流出错或结束时产出一次错误
+//! （`Auth`/`Transport`/`Closed`）后结束。设置停滞超时后，超时没有收到任何帧（含 ping）时
+//! 产出 `Stalled` 错误后结束；合并了多个订阅的流中任何一个订阅停滞，整个流都会结束。
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::{sink, stream, Sink, SinkExt, Stream, StreamExt};
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+use tonic::Status;
+use yellowstone_grpc_client::{GeyserGrpcClient, Interceptor};
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterAccounts, SubscribeRequestPing, SubscribeUpdate, SubscribeUpdateAccount,
+};
+
+use super::dedup::DedupLayer;
+use super::error::StreamError;
+use super::pool::factory;
+use super::stall::StallDetector;
+use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::high_performance_clock::Clock;
+use crate::streaming::event_parser::common::Commitment;
+use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+use crate::streaming::event_parser::core::parser_registry::ParserRegistry;
+use crate::streaming::event_parser::core::AccountDecodeCache;
+use crate::streaming::event_parser::{DexEvent, Protocol};
+
+/// 多个事件流之间共享的去重状态（例如重连前后的订阅）
+pub(crate) type SharedDedup = Arc<Mutex<Option<DedupLayer>>>;
+
+/// 事件流的解码缓存，合并的多个订阅共享同一个缓存
+type SharedDecodeCache = Arc<Mutex<Option<AccountDecodeCache>>>;
+
+/// 事件流的停滞超时，合并的多个订阅共享同一个设置，但各自独立计时
+type SharedStallTimeout = Arc<Mutex<Option<Duration>>>;
+
+/// 事件流的时钟，未设置时使用高性能时钟
+type SharedClock = Arc<Mutex<Option<Arc<dyn Clock>>>>;
+
+/// 事件流的解析器注册表，未设置时使用全局注册表
+type SharedParserRegistry = Arc<Mutex<Option<Arc<ParserRegistry>>>>;
+
+/// 事件流的附加处理：去重、解码缓存、是否保留原始账户数据、停滞超时、时钟和解析器注册表，
+/// 合并的多个订阅共享同一份设置
+#[derive(Clone, Default)]
+struct Layers {
+    dedup: SharedDedup,
+    decode_cache: SharedDecodeCache,
+    keep_raw_data: Arc<AtomicBool>,
+    stall_timeout: SharedStallTimeout,
+    clock: SharedClock,
+    parser_registry: SharedParserRegistry,
+}
+
+struct AccountEventState<S, K> {
+    updates: Pin<Box<S>>,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+    /// 订阅的确认级别，写入每个事件的元数据
+    commitment: Option<Commitment>,
+    layers: Layers,
+    /// 本订阅的停滞检测，设置停滞超时后在第一次读取时创建
+    stall: Option<StallDetector>,
+    /// 是否已收到第一条更新
+    #[cfg(feature = "tracing")]
+    received: bool,
+    /// 订阅请求的发送端，用于回复 ping；同时保持存活以免服务端关闭订阅
+    sink: Pin<Box<K>>,
+}
+
+/// 账户事件流，由 [`subscribe_events`] 和 [`account_events`] 返回
+pub struct AccountEventStream {
+    inner: Pin<Box<dyn Stream<Item = Result<DexEvent, StreamError>> + Send>>,
+    layers: Layers,
+}
+
+impl AccountEventStream {
+    /// 在解析之前按 `(pubkey, slot, write_version)` 丢弃重复或过期的账户更新，
+    /// 最多记录 `capacity` 个账户
+    pub fn with_dedup(self, capacity: usize) -> Self {
+        *self.layers.dedup.lock() = Some(DedupLayer::new(capacity));
+        self
+    }
+
+    /// 去重丢弃的更新数量，未开启去重时为 0
+    pub fn dedup_dropped_count(&self) -> u64 {
+        self.layers.dedup.lock().as_ref().map_or(0, DedupLayer::dropped_count)
+    }
+
+    /// 账户数据未变化时复用上一次的解码结果，跳过 borsh 解码，最多缓存 `capacity` 个账户（LRU）
+    pub fn with_decode_cache(self, capacity: usize) -> Self {
+        *self.layers.decode_cache.lock() = Some(AccountDecodeCache::with_capacity(capacity));
+        self
+    }
+
+    /// 解码缓存的命中率（0.0 ~ 1.0），未开启解码缓存时为 0
+    pub fn decode_cache_hit_rate(&self) -> f64 {
+        self.layers.decode_cache.lock().as_ref().map_or(0.0, AccountDecodeCache::hit_rate)
+    }
+
+    /// 开启后在事件的 `metadata.raw_data` 中保留产生该事件的原始账户数据，
+    /// 便于之后用新的布局重新解码；每个事件会额外复制一份账户数据，默认关闭
+    pub fn with_raw_data(self, enabled: bool) -> Self {
+        self.layers.keep_raw_data.store(enabled, Ordering::Relaxed);
+        self
+    }
+
+    /// 超过 `timeout` 没有收到任何帧（账户更新、ping 等）时产出 `StreamError::Stalled` 并结束，
+    /// 见 [`StallDetector`]；`timeout` 应大于服务端的 ping 间隔，默认不检测
+    ///
+    /// [`subscribe_accounts`] 合并的每个订阅各自计时，任何一个停滞时整个流结束
+    pub fn with_stall_timeout(self, timeout: Duration) -> Self {
+        *self.layers.stall_timeout.lock() = Some(timeout);
+        self
+    }
+
+    /// 使用 `clock` 记录事件的接收时间（`recv_us`）和处理耗时（`handle_us`），
+    /// 用于测试中获得确定的时间戳，默认使用高性能时钟
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        *self.layers.clock.lock() = Some(clock);
+        self
+    }
+
+    /// 使用 `registry` 代替全局 [`ParserRegistry`]：在其上启用/禁用协议、注册账户解析器
+    /// 只影响共享该实例的事件流，默认使用全局注册表
+    pub fn with_parser_registry(self, registry: Arc<ParserRegistry>) -> Self {
+        *self.layers.parser_registry.lock() = Some(registry);
+        self
+    }
+}
+
+impl Stream for AccountEventStream {
+    type Item = Result<DexEvent, StreamError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// 使用 `client` 发送订阅请求，返回解析后的事件流
+///
+/// ```ignore
+/// let client = SubscriptionManager::new(endpoint, x_token, config).connect().await?;
+/// let mut events =
+///     subscribe_events(client, request, vec![Protocol::Bonk], None).await?.with_dedup(100_000);
+/// while let Some(event) = events.next().await {
+///     match event {
+///         Ok(event) => handle(event),
+///         Err(e) => break,
+///     }
+/// }
+/// ```
+pub async fn subscribe_events<I>(
+    client: GeyserGrpcClient<I>,
+    request: SubscribeRequest,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+) -> Result<AccountEventStream, StreamError>
+where
+    I: Interceptor + Send + 'static,
+{
+    let dedup = SharedDedup::default();
+    open_account_events(client, request, protocols, event_type_filter, dedup).await
+}
+
+/// 与 [`subscribe_events`] 相同，使用给定的共享去重状态
+pub(crate) async fn open_account_events<I>(
+    mut client: GeyserGrpcClient<I>,
+    request: SubscribeRequest,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+    dedup: SharedDedup,
+) -> Result<AccountEventStream, StreamError>
+where
+    I: Interceptor + Send + 'static,
+{
+    let commitment = Commitment::from_request(request.commitment);
+    let (sink, updates) = match client.subscribe_with_request(Some(request)).await {
+        Ok(subscription) => subscription,
+        Err(error) => {
+            let error = StreamError::from_client_error(error);
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%error, "failed to open subscription");
+            return Err(error);
+        }
+    };
+    Ok(subscription_events(updates, sink, protocols, event_type_filter, commitment, dedup))
+}
+
+/// 解析已建立的订阅 `updates` 中的账户事件，使用给定的共享去重状态
+pub(crate) fn subscription_events<S, K>(
+    updates: S,
+    sink: K,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+    commitment: Option<Commitment>,
+    dedup: SharedDedup,
+) -> AccountEventStream
+where
+    S: Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static,
+    K: Sink<SubscribeRequest> + Send + 'static,
+{
+    #[cfg(feature = "tracing")]
+    tracing::info!("subscription opened");
+    let layers = Layers { dedup, ..Default::default() };
+    parse_updates(updates, sink, protocols, event_type_filter, commitment, layers)
+}
+
+/// 单个订阅请求中最多包含的账户地址数量，超出时拆分为多个订阅请求
+pub const MAX_ACCOUNTS_PER_SUBSCRIPTION: usize = 1_000;
+
+/// 订阅指定账户地址的更新，按所有支持的协议解析
+///
+/// 地址超过 [`MAX_ACCOUNTS_PER_SUBSCRIPTION`] 时拆分为多个订阅请求，在同一连接上接收并合并为
+/// 一个事件流。某个订阅出错或结束时产出一次错误，其余订阅继续；全部结束后流结束。
+/// 某个订阅停滞（`Stalled`）时产出该错误后整个流结束，以便重新订阅全部账户
+///
+/// ```ignore
+/// let client = SubscriptionManager::new(endpoint, x_token, config).connect().await?;
+/// let mut events = subscribe_accounts(client, pool_pubkeys).await?;
+/// while let Some(event) = events.next().await {
+///     // ...
+/// }
+/// ```
+pub async fn subscribe_accounts<I>(
+    mut client: GeyserGrpcClient<I>,
+    pubkeys: Vec<Pubkey>,
+) -> Result<AccountEventStream, StreamError>
+where
+    I: Interceptor + Send + 'static,
+{
+    let mut subscriptions = Vec::new();
+    for chunk in pubkeys.chunks(MAX_ACCOUNTS_PER_SUBSCRIPTION) {
+        let request = accounts_request(chunk);
+        let commitment = Commitment::from_request(request.commitment);
+        let (sink, updates) = client
+            .subscribe_with_request(Some(request))
+            .await
+            .map_err(StreamError::from_client_error)?;
+        subscriptions.push((updates, sink, commitment));
+    }
+    Ok(merge_account_subscriptions(subscriptions))
+}
+
+/// 解析并合并 [`subscribe_accounts`] 拆分出的各个订阅，共享同一份去重等状态
+fn merge_account_subscriptions<S, K>(
+    subscriptions: Vec<(S, K, Option<Commitment>)>,
+) -> AccountEventStream
+where
+    S: Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static,
+    K: Sink<SubscribeRequest> + Send + 'static,
+{
+    let layers = Layers::default();
+    let streams = subscriptions.into_iter().map(|(updates, sink, commitment)| {
+        let protocols = Protocol::ALL.to_vec();
+        parse_updates(updates, sink, protocols, None, commitment, layers.clone())
+    });
+    let inner = Box::pin(end_on_stall(stream::select_all(streams)));
+    AccountEventStream { inner, layers }
+}
+
+/// 产出第一个 `Stalled` 错误后结束整个流
+///
+/// `select_all` 中停滞的订阅单独结束后，其账户不会再有更新，而合并流仍在继续，消费者无从察觉
+fn end_on_stall<S>(events: S) -> impl Stream<Item = Result<DexEvent, StreamError>>
+where
+    S: Stream<Item = Result<DexEvent, StreamError>>,
+{
+    // 停滞后立即丢弃合并流（关闭所有订阅），不再等待其他订阅的下一个事件
+    stream::unfold(Some(Box::pin(events)), |events| async move {
+        let mut events = events?;
+        let event = events.next().await?;
+        let stalled = matches!(event, Err(StreamError::Stalled { .. }));
+        Some((event, (!stalled).then_some(events)))
+    })
+}
+
+/// 只订阅 `pubkeys` 这些账户的请求
+fn accounts_request(pubkeys: &[Pubkey]) -> SubscribeRequest {
+    let filter = SubscribeRequestFilterAccounts {
+        account: pubkeys.iter().map(Pubkey::to_string).collect(),
+        owner: vec![],
+        filters: vec![],
+        nonempty_txn_signature: None,
+    };
+    SubscribeRequest {
+        accounts: HashMap::from([("accounts".to_owned(), filter)]),
+        commitment: Some(CommitmentLevel::Processed.into()),
+        ..Default::default()
+    }
+}
+
+/// 将任意订阅更新流转换为事件流，便于接入其他来源（如回放或测试数据）的更新
+pub fn account_events<S>(
+    updates: S,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+) -> AccountEventStream
+where
+    S: Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static,
+{
+    let sink = sink::drain();
+    parse_updates(updates, sink, protocols, event_type_filter, None, Default::default())
+}
+
+fn parse_updates<S, K>(
+    updates: S,
+    sink: K,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+    commitment: Option<Commitment>,
+    layers: Layers,
+) -> AccountEventStream
+where
+    S: Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static,
+    K: Sink<SubscribeRequest> + Send + 'static,
+{
+    let state = AccountEventState {
+        updates: Box::pin(updates),
+        protocols,
+        event_type_filter,
+        commitment,
+        layers: layers.clone(),
+        stall: None,
+        #[cfg(feature = "tracing")]
+        received: false,
+        sink: Box::pin(sink),
+    };
+    let inner = stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        loop {
+            let update = match next_update(&mut state).await {
+                Ok(update) => update,
+                Err(error) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(%error, "subscription stalled");
+                    return Some((Err(error), None));
+                }
+            };
+            match update {
+                Some(Ok(update)) => {
+                    #[cfg(feature = "tracing")]
+                    if !state.received {
+                        state.received = true;
+                        tracing::info!("first subscription update received");
+                    }
+                    let account = match update.update_oneof {
+                        Some(UpdateOneof::Account(account)) => account,
+                        Some(UpdateOneof::Ping(_)) => {
+                            // 部分服务端要求回复 ping，否则会断开连接；发送失败时由之后的读取报错
+                            let _ = state.sink.send(ping_request()).await;
+                            continue;
+                        }
+                        _ => continue,
+                    };
+                    if let Err(error) = validate_account_update(&account) {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(%error, "malformed account update");
+                        return Some((Err(error), Some(state)));
+                    }
+                    let mut account = factory::create_account_pretty_pooled(account);
+                    let clock = state.layers.clock.lock().clone();
+                    if let Some(clock) = &clock {
+                        account.recv_us = clock.now_micros();
+                    }
+                    if let Some(dedup) = state.layers.dedup.lock().as_mut() {
+                        if !dedup.accept(&account) {
+                            continue;
+                        }
+                    }
+                    let raw_data = state
+                        .layers
+                        .keep_raw_data
+                        .load(Ordering::Relaxed)
+                        .then(|| Arc::<[u8]>::from(account.data.as_slice()));
+                    let protocols = &state.protocols;
+                    let filter = state.event_type_filter.as_ref();
+                    let registry = state.layers.parser_registry.lock().clone();
+                    let registry = registry.as_deref().unwrap_or(ParserRegistry::global());
+                    let event = {
+                        let cache = state.layers.decode_cache.lock();
+                        let cache = cache.as_ref().unwrap_or(AccountDecodeCache::global());
+                        AccountEventParser::parse_account_event_with_registry(
+                            registry, protocols, account, filter, cache,
+                        )
+                    };
+                    if let Some(mut event) = event {
+                        let metadata = event.metadata_mut();
+                        metadata.commitment = state.commitment;
+                        metadata.raw_data = raw_data;
+                        if let Some(clock) = &clock {
+                            metadata.handle_us = clock.now_micros() - metadata.recv_us;
+                        }
+                        return Some((Ok(event), Some(state)));
+                    }
+                }
+                Some(Err(status)) => {
+                    let error = StreamError::from_status(status);
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(%error, "subscription stream failed");
+                    return Some((Err(error), None));
+                }
+                None => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("subscription closed by server");
+                    return Some((Err(StreamError::Closed), None));
+                }
+            }
+        }
+    });
+    AccountEventStream { inner: Box::pin(inner), layers }
+}
+
+/// 回复服务端 ping 的订阅请求
+fn ping_request() -> SubscribeRequest {
+    SubscribeRequest { ping: Some(SubscribeRequestPing { id: 1 }), ..Default::default() }
+}
+
+/// 读取下一帧（含 ping），设置了停滞检测且超时没有收到任何帧时返回 `Stalled`
+async fn next_update<S, K>(
+    state: &mut AccountEventState<S, K>,
+) -> Result<Option<Result<SubscribeUpdate, Status>>, StreamError>
+where
+    S: Stream<Item = Result<SubscribeUpdate, Status>>,
+{
+    let Some(timeout) = *state.layers.stall_timeout.lock() else {
+        return Ok(state.updates.next().await);
+    };
+    let stall = match &mut state.stall {
+        Some(stall) if stall.timeout() == timeout => stall,
+        stall => stall.insert(StallDetector::new(timeout)),
+    };
+    match tokio::time::timeout_at(stall.deadline(), state.updates.next()).await {
+        Ok(update) => {
+            stall.record_frame();
+            Ok(update)
+        }
+        Err(_) => Err(StreamError::Stalled { idle: stall.idle() }),
+    }
+}
+
+/// 检查账户更新的字段是否完整，避免在转换为 `AccountPretty` 时 panic
+fn validate_account_update(update: &SubscribeUpdateAccount) -> Result<(), StreamError> {
+    let Some(account) = update.account.as_ref() else {
+        let slot = update.slot;
+        return Err(StreamError::Decode(format!("account update at slot {slot} has no account")));
+    };
+    if account.pubkey.len() != 32 || account.owner.len() != 32 {
+        return Err(StreamError::Decode(format!(
+            "invalid pubkey/owner length {}/{} at slot {}",
+            account.pubkey.len(),
+            account.owner.len(),
+            update.slot
+        )));
+    }
+    if account.txn_signature.as_ref().is_some_and(|signature| signature.len() != 64) {
+        return Err(StreamError::Decode(format!("invalid txn_signature at slot {}", update.slot)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use futures::channel::mpsc;
+    use yellowstone_grpc_proto::geyser::{
+        SubscribeUpdateAccountInfo, SubscribeUpdatePing, SubscribeUpdateSlot,
+    };
+
+    use super::*;
+    use crate::streaming::event_parser::common::high_performance_clock::MockClock;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+
+    fn ping() -> Result<SubscribeUpdate, Status> {
+        Ok(SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Ping(SubscribeUpdatePing {})),
+            ..Default::default()
+        })
+    }
+
+    fn pool_state_update(slot: u64) -> Result<SubscribeUpdate, Status> {
+        pool_state_update_of(Pubkey::new_unique(), slot)
+    }
+
+    fn pool_state_update_of(pubkey: Pubkey, slot: u64) -> Result<SubscribeUpdate, Status> {
+        let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&PoolState::default()).unwrap());
+        let account = SubscribeUpdateAccountInfo {
+            pubkey: pubkey.to_bytes().to_vec(),
+            lamports: 1_000_000,
+            owner: BONK_PROGRAM_ID.to_bytes().to_vec(),
+            data,
+            write_version: slot,
+            ..Default::default()
+        };
+        Ok(SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Account(SubscribeUpdateAccount {
+                account: Some(account),
+                slot,
+                is_startup: false,
+            })),
+            ..Default::default()
+        })
+    }
+
+    /// 每隔 `interval` 推送一次 ping，共 `count` 次，之后推送 `last`（若有）并保持连接但不再推送
+    fn pinged_source(
+        interval: Duration,
+        count: usize,
+        last: Option<Result<SubscribeUpdate, Status>>,
+    ) -> impl Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static {
+        let pings = stream::unfold(0, move |sent| async move {
+            if sent == count {
+                return None;
+            }
+            tokio::time::sleep(interval).await;
+            Some((ping(), sent + 1))
+        });
+        pings.chain(stream::iter(last)).chain(stream::pending())
+    }
+
+    fn events_with_stall_timeout<S>(updates: S, timeout: Duration) -> AccountEventStream
+    where
+        S: Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static,
+    {
+        account_events(updates, vec![Protocol::Bonk], None).with_stall_timeout(timeout)
+    }
+
+    #[tokio::test]
+    async fn quiet_source_stalls() {
+        let timeout = Duration::from_millis(50);
+        let mut events = events_with_stall_timeout(stream::pending(), timeout);
+
+        let Some(Err(StreamError::Stalled { idle })) = events.next().await else {
+            panic!("expected a stall");
+        };
+        assert!(idle >= timeout);
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn pinged_source_does_not_stall() {
+        // 10 次 ping 共约 200ms，远超停滞超时，但每次间隔都短于超时
+        let source = pinged_source(Duration::from_millis(20), 10, Some(pool_state_update(7)));
+        let mut events = events_with_stall_timeout(source, Duration::from_millis(100));
+
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event, DexEvent::BonkPoolStateAccountEvent(_)), "{event:?}");
+        assert!(matches!(events.next().await, Some(Err(StreamError::Stalled { .. }))));
+    }
+
+    #[tokio::test]
+    async fn one_stalled_subscription_ends_the_merged_stream() {
+        let timeout = Duration::from_millis(100);
+        let pinged = pinged_source(Duration::from_millis(20), usize::MAX, None);
+        let streams = vec![
+            events_with_stall_timeout(pinged, timeout),
+            events_with_stall_timeout(stream::pending(), timeout),
+        ];
+        let mut merged = Box::pin(end_on_stall(stream::select_all(streams)));
+
+        assert!(matches!(merged.next().await, Some(Err(StreamError::Stalled { .. }))));
+        assert!(merged.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn mocked_subscription_yields_account_events_in_order() {
+        let slot = SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Slot(SubscribeUpdateSlot {
+                slot: 6,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let missing_account = SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Account(SubscribeUpdateAccount {
+                account: None,
+                slot: 6,
+                is_startup: false,
+            })),
+            ..Default::default()
+        };
+        let updates = stream::iter(vec![
+            ping(),
+            pool_state_update(5),
+            Ok(slot),
+            Ok(missing_account),
+            pool_state_update(7),
+        ]);
+        let events: Vec<_> = account_events(updates, vec![Protocol::Bonk], None).collect().await;
+
+        // ping 与 slot 更新被跳过，缺少 account 字段的更新产出错误但不中断流
+        let slot_of = |result: &Result<DexEvent, StreamError>| match result {
+            Ok(DexEvent::BonkPoolStateAccountEvent(e)) => Some(e.metadata.slot),
+            _ => None,
+        };
+        assert_eq!(events.len(), 4, "{events:?}");
+        assert_eq!(slot_of(&events[0]), Some(5));
+        assert!(matches!(events[1], Err(StreamError::Decode(_))), "{:?}", events[1]);
+        assert_eq!(slot_of(&events[2]), Some(7));
+        assert!(matches!(events[3], Err(StreamError::Closed)), "{:?}", events[3]);
+    }
+
+    /// 模拟服务端：只推送 `request` 中列出的账户的更新
+    fn serve_account_filter(
+        request: &SubscribeRequest,
+        updates: &[(Pubkey, u64)],
+    ) -> impl Stream<Item = Result<SubscribeUpdate, Status>> + Send + 'static {
+        let watched: Vec<String> =
+            request.accounts.values().flat_map(|filter| filter.account.clone()).collect();
+        let matching: Vec<_> = updates
+            .iter()
+            .filter(|(pubkey, _)| watched.contains(&pubkey.to_string()))
+            .map(|&(pubkey, slot)| pool_state_update_of(pubkey, slot))
+            .collect();
+        stream::iter(matching)
+    }
+
+    #[tokio::test]
+    async fn only_watched_accounts_are_delivered() {
+        // 超过单个请求的上限，拆分为两个订阅
+        let watched: Vec<_> =
+            (0..=MAX_ACCOUNTS_PER_SUBSCRIPTION).map(|_| Pubkey::new_unique()).collect();
+        let other = Pubkey::new_unique();
+        let updates = [(watched[0], 5), (other, 6), (watched[MAX_ACCOUNTS_PER_SUBSCRIPTION], 7)];
+
+        let requests: Vec<_> =
+            watched.chunks(MAX_ACCOUNTS_PER_SUBSCRIPTION).map(accounts_request).collect();
+        assert_eq!(requests.len(), 2);
+        let subscriptions = requests
+            .iter()
+            .map(|request| {
+                let commitment = Commitment::from_request(request.commitment);
+                (serve_account_filter(request, &updates), sink::drain(), commitment)
+            })
+            .collect();
+        let events: Vec<_> = merge_account_subscriptions(subscriptions).collect().await;
+
+        let mut delivered: Vec<_> =
+            events.iter().filter_map(|event| event.as_ref().ok()?.account_pubkey()).collect();
+        delivered.sort();
+        let mut expected = vec![watched[0], watched[MAX_ACCOUNTS_PER_SUBSCRIPTION]];
+        expected.sort();
+        assert_eq!(delivered, expected);
+        // 每个订阅结束时各产出一次 Closed
+        let closed = events.iter().filter(|e| matches!(e, Err(StreamError::Closed))).count();
+        assert_eq!(closed, 2);
+    }
+
+    #[tokio::test]
+    async fn pings_are_answered_through_the_sink() {
+        let (sink, mut requests) = mpsc::unbounded::<SubscribeRequest>();
+        let updates = stream::iter(vec![ping(), pool_state_update(5)]);
+        let mut events =
+            parse_updates(updates, sink, vec![Protocol::Bonk], None, None, Default::default());
+
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event, DexEvent::BonkPoolStateAccountEvent(_)), "{event:?}");
+        assert_eq!(requests.try_recv().unwrap(), ping_request());
+        assert!(matches!(events.next().await, Some(Err(StreamError::Closed))));
+    }
+
+    #[tokio::test]
+    async fn raw_account_bytes_are_kept_only_when_enabled() {
+        let update = pool_state_update(5);
+        let Some(UpdateOneof::Account(account)) = update.as_ref().unwrap().update_oneof.clone()
+        else {
+            unreachable!()
+        };
+        let data = account.account.unwrap().data;
+
+        let updates = stream::iter(vec![update.clone()]);
+        let mut events = account_events(updates, vec![Protocol::Bonk], None).with_raw_data(true);
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.metadata().raw_data.as_deref(), Some(data.as_slice()));
+
+        let updates = stream::iter(vec![update]);
+        let mut events = account_events(updates, vec![Protocol::Bonk], None);
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.metadata().raw_data, None);
+    }
+
+    #[tokio::test]
+    async fn timestamps_come_from_the_injected_clock() {
+        let received_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let clock = Arc::new(MockClock::new(received_at));
+        let updates = stream::iter(vec![pool_state_update(5), pool_state_update(6)]);
+        let mut events =
+            account_events(updates, vec![Protocol::Bonk], None).with_clock(clock.clone());
+
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.metadata().recv_us, 1_700_000_000_000_000);
+        assert_eq!(event.metadata().handle_us, 0);
+
+        clock.advance(Duration::from_millis(3));
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.metadata().recv_us, 1_700_000_000_003_000);
+    }
+
+    #[tokio::test]
+    async fn disabling_a_protocol_only_affects_streams_sharing_the_registry() {
+        let registry = Arc::new(ParserRegistry::new());
+        registry.set_enabled(Protocol::Bonk, false);
+        let updates = stream::iter(vec![pool_state_update(5)]);
+        let mut events =
+            account_events(updates, vec![Protocol::Bonk], None).with_parser_registry(registry);
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event, DexEvent::UnknownAccountEvent(_)), "{event:?}");
+
+        let updates = stream::iter(vec![pool_state_update(5)]);
+        let mut events = account_events(updates, vec![Protocol::Bonk], None);
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event, DexEvent::BonkPoolStateAccountEvent(_)), "{event:?}");
+    }
+}
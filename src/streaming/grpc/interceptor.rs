@@ -0,0 +1,37 @@
+use tonic::metadata::{AsciiMetadataKey, AsciiMetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+use crate::common::AnyResult;
+
+/// 为每个 gRPC 请求附加 metadata（`x-token` 以及自定义 header）
+#[derive(Debug, Clone, Default)]
+pub struct HeaderInterceptor {
+    headers: Vec<(AsciiMetadataKey, AsciiMetadataValue)>,
+}
+
+impl HeaderInterceptor {
+    /// 由 `x-token` 和自定义 header 构建，header 名称或值不合法时返回错误
+    ///
+    /// 自定义 header 在 `x-token` 之后写入，同名时覆盖前者
+    pub fn new(x_token: Option<&str>, headers: &[(String, String)]) -> AnyResult<Self> {
+        let mut parsed = Vec::with_capacity(headers.len() + 1);
+        if let Some(x_token) = x_token {
+            parsed.push((AsciiMetadataKey::from_static("x-token"), x_token.parse()?));
+        }
+        for (name, value) in headers {
+            parsed.push((AsciiMetadataKey::from_bytes(name.as_bytes())?, value.parse()?));
+        }
+        Ok(Self { headers: parsed })
+    }
+}
+
+impl Interceptor for HeaderInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let metadata = request.metadata_mut();
+        for (name, value) in &self.headers {
+            metadata.insert(name.clone(), value.clone());
+        }
+        Ok(request)
+    }
+}
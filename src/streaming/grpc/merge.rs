@@ -0,0 +1,179 @@
+//! 合并多个冗余订阅
+//!
+//! 同时连接多个 gRPC 服务商时，同一事件会从每个来源各收到一次。[`merge_subscriptions`] 交错
+//! 读取所有来源，按 [`DexEvent::content_hash`] 去重，只产出最先到达的一份；某个来源中断时
+//! 其他来源继续产出，合并后的流不受影响。
+
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::stream::BoxStream;
+use futures::{future, stream, Stream, StreamExt};
+use parking_lot::Mutex;
+
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::event_parser::DexEvent;
+
+/// 去重时记录的最近事件哈希数量
+pub const MERGE_DEDUP_CAPACITY: usize = 100_000;
+
+/// 单个来源的状态
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceStatus {
+    /// 收到的事件数量（含重复）
+    pub received: u64,
+    /// 最先到达、被产出的事件数量
+    pub first_deliveries: u64,
+    /// 最近一次收到事件的时间（微秒），尚未收到事件时为 `None`
+    pub last_event_us: Option<i64>,
+    /// 来源的流是否已经结束
+    pub finished: bool,
+}
+
+struct MergeState {
+    sources: Vec<SourceStatus>,
+    /// 最近一次最先送达事件的来源
+    leading: Option<usize>,
+    seen: HashSet<[u8; 32]>,
+    /// 按到达顺序排列的哈希，超出容量时淘汰最早的
+    order: VecDeque<[u8; 32]>,
+}
+
+impl MergeState {
+    /// 记录来源 `source` 收到的事件，事件首次出现时返回 `true`
+    fn observe(&mut self, source: usize, event: &DexEvent) -> bool {
+        let status = &mut self.sources[source];
+        status.received += 1;
+        status.last_event_us = Some(get_high_perf_clock());
+
+        let hash = event.content_hash();
+        if !self.seen.insert(hash) {
+            return false;
+        }
+        self.order.push_back(hash);
+        if self.order.len() > MERGE_DEDUP_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.sources[source].first_deliveries += 1;
+        self.leading = Some(source);
+        true
+    }
+}
+
+/// 合并后的事件流，由 [`merge_subscriptions`] 返回
+pub struct MergedStream {
+    inner: Pin<Box<dyn Stream<Item = DexEvent> + Send>>,
+    state: Arc<Mutex<MergeState>>,
+}
+
+impl MergedStream {
+    /// 最近一次最先送达事件的来源下标，尚未产出事件时为 `None`
+    pub fn leading_source(&self) -> Option<usize> {
+        self.state.lock().leading
+    }
+
+    /// 各来源的状态，顺序与传入 [`merge_subscriptions`] 的顺序一致
+    pub fn source_status(&self) -> Vec<SourceStatus> {
+        self.state.lock().sources.clone()
+    }
+}
+
+impl Stream for MergedStream {
+    type Item = DexEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// 交错读取 `streams` 中的所有来源，按内容哈希去重后合并为一个事件流
+///
+/// 最近 [`MERGE_DEDUP_CAPACITY`] 个事件参与去重，同一事件只产出最先到达的一份。
+/// 所有来源都结束后合并的流结束
+///
+/// ```ignore
+/// let primary = subscribe_events(primary_client, request.clone(), protocols.clone(), None);
+/// let backup = subscribe_events(backup_client, request, protocols, None);
+/// let (primary, backup) = (primary.await?, backup.await?);
+/// let mut events = merge_subscriptions(vec![
+///     primary.filter_map(|e| future::ready(e.ok())).boxed(),
+///     backup.filter_map(|e| future::ready(e.ok())).boxed(),
+/// ]);
+/// while let Some(event) = events.next().await {
+///     // ...
+/// }
+/// ```
+pub fn merge_subscriptions(streams: Vec<BoxStream<'static, DexEvent>>) -> MergedStream {
+    let state = Arc::new(Mutex::new(MergeState {
+        sources: vec![SourceStatus::default(); streams.len()],
+        leading: None,
+        seen: HashSet::new(),
+        order: VecDeque::new(),
+    }));
+
+    // 每个来源结束时追加一个 `None`，用于标记来源已结束
+    let tagged = streams.into_iter().enumerate().map(|(source, events)| {
+        let end = stream::once(future::ready((source, None)));
+        events.map(move |event| (source, Some(event))).chain(end)
+    });
+    let shared = state.clone();
+    let inner = stream::select_all(tagged).filter_map(move |(source, event)| {
+        let mut state = shared.lock();
+        let event = match event {
+            Some(event) => state.observe(source, &event).then_some(event),
+            None => {
+                state.sources[source].finished = true;
+                None
+            }
+        };
+        future::ready(event)
+    });
+    MergedStream { inner: Box::pin(inner), state }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::streaming::event_parser::common::EventMetadata;
+    use crate::streaming::event_parser::protocols::bonk::BonkPoolStateAccountEvent;
+
+    fn pool_state_update(slot: u64) -> DexEvent {
+        DexEvent::BonkPoolStateAccountEvent(BonkPoolStateAccountEvent {
+            metadata: EventMetadata { slot, ..Default::default() },
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn lagging_source_fills_gaps_without_duplicates() {
+        // 领先的来源漏掉了 slot 4，落后的来源每个事件都晚到 5ms
+        let leading = stream::iter([1, 2, 3, 5, 6].map(pool_state_update)).boxed();
+        let lagging = stream::iter((1..=6).map(pool_state_update))
+            .then(|event| async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                event
+            })
+            .boxed();
+
+        let mut merged = merge_subscriptions(vec![leading, lagging]);
+        let mut slots = Vec::new();
+        while let Some(event) = merged.next().await {
+            slots.push(event.metadata().slot);
+        }
+        slots.sort_unstable();
+        assert_eq!(slots, [1, 2, 3, 4, 5, 6]);
+
+        let status = merged.source_status();
+        assert_eq!((status[0].received, status[0].first_deliveries), (5, 5));
+        assert_eq!((status[1].received, status[1].first_deliveries), (6, 1));
+        assert!(status.iter().all(|source| source.finished && source.last_event_us.is_some()));
+        // slot 4 只有落后的来源送达，且是最后一个首次送达的事件
+        assert_eq!(merged.leading_source(), Some(1));
+    }
+}
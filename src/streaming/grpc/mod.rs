@@ -1,13 +1,38 @@
 // gRPC 相关模块
+pub mod bootstrap;
 pub mod connection;
+pub mod decompression;
+pub mod dedup;
+pub mod error;
+pub mod event_stream;
+pub mod interceptor;
+pub mod merge;
 pub mod pool;
+pub mod reconnect;
+pub mod stall;
 pub mod subscription;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 pub mod types;
 
 // 重新导出主要类型
+pub use bootstrap::snapshot_then_subscribe;
 pub use connection::*;
+pub use decompression::DecompressionGuard;
+pub use dedup::DedupLayer;
+pub use error::StreamError;
+pub use event_stream::{
+    account_events, subscribe_accounts, subscribe_events, AccountEventStream,
+    MAX_ACCOUNTS_PER_SUBSCRIPTION,
+};
+pub use interceptor::*;
+pub use merge::{merge_subscriptions, MergedStream, SourceStatus, MERGE_DEDUP_CAPACITY};
 pub use pool::*;
+pub use reconnect::{ReconnectConfig, ReconnectEvent, ReconnectingSubscription};
+pub use stall::StallDetector;
 pub use subscription::*;
+#[cfg(any(test, feature = "test-util"))]
+pub use test_util::{run_account_fixture, AccountPrettyBuilder, DEFAULT_TEST_LAMPORTS};
 pub use types::*;
 
 // 从公用模块重新导出
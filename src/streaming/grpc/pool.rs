@@ -108,6 +108,7 @@ impl PooledAccountPretty {
         self.account.lamports = account_info.lamports;
         self.account.owner = Pubkey::try_from(account_info.owner.as_slice()).expect("valid pubkey");
         self.account.rent_epoch = account_info.rent_epoch;
+        self.account.write_version = account_info.write_version;
 
         // 优化数据字段的重用
         let new_data = account_info.data;
@@ -131,6 +132,7 @@ impl Drop for PooledAccountPretty {
             self.account.signature = Signature::default();
             self.account.pubkey = Pubkey::default();
             self.account.owner = Pubkey::default();
+            self.account.write_version = 0;
             pool.push_back(std::mem::take(&mut self.account));
         }
     }
@@ -0,0 +1,563 @@
+//! 断线自动重连的事件流
+//!
+//! [`ReconnectingSubscription`] 在 [`subscribe_events`](super::subscribe_events) 的基础上，
+//! 在传输错误或服务端关闭订阅后按指数退避重新建立订阅。开启 `resume_from_slot` 时重连请求带上 `from_slot`（最后收到事件的 slot），
+//! 由服务端补发断线期间的更新；该 slot 内已收到的事件可能被重复投递，需要时用
+//! [`ReconnectingSubscription::with_dedup`] 去重。
+//!
+//! 是否重连由 [`StreamError::is_retryable`] 决定：认证被拒绝或消息超过大小限制时不再重连，直接产出错误并结束；
+//! 单条更新的 `Decode` 错误原样产出，不影响当前连接。设置
+//! [`ReconnectingSubscription::with_stall_timeout`] 后，服务端停止推送但连接未断开时也会重连。
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::future::BoxFuture;
+use futures::{stream, Stream, StreamExt};
+use yellowstone_grpc_proto::geyser::{CommitmentLevel, SubscribeRequest};
+
+use super::dedup::DedupLayer;
+use super::error::StreamError;
+use super::event_stream::{subscription_events, AccountEventStream, SharedDedup};
+use super::subscription::SubscriptionManager;
+use crate::streaming::common::MetricsManager;
+use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::high_performance_clock::Clock;
+use crate::streaming::event_parser::common::{Checkpoint, Commitment};
+use crate::streaming::event_parser::core::parser_registry::ParserRegistry;
+use crate::streaming::event_parser::{DexEvent, Protocol};
+
+/// 重连退避配置
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectConfig {
+    /// 第一次重连前的等待时间（默认 500ms），之后每次翻倍
+    pub base_delay: Duration,
+    /// 等待时间上限（默认 30s）
+    pub max_delay: Duration,
+    /// 随机抖动比例，取值 0.0..=1.0，实际等待时间在 `delay * (1 - jitter)..=delay` 之间（默认 0.2）
+    pub jitter: f64,
+    /// 连续重连失败的最大次数，`None` 表示不限（默认）
+    pub max_attempts: Option<u32>,
+    /// 重连时是否从最后收到事件的 slot 继续订阅（默认开启）
+    pub resume_from_slot: bool,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            max_attempts: None,
+            resume_from_slot: true,
+        }
+    }
+}
+
+impl ReconnectConfig {
+    /// 第 `attempt` 次（从 1 开始）重连前的等待时间
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self.base_delay.saturating_mul(1u32 << exponent).min(self.max_delay);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+        delay.mul_f64(1.0 - jitter * random_unit())
+    }
+}
+
+/// 0.0..1.0 之间的伪随机数，仅用于错开重连时间
+fn random_unit() -> f64 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+    // 对纳秒数做一次整数哈希，避免低位规律
+    let mixed = (nanos as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 11;
+    mixed as f64 / (1u64 << 53) as f64
+}
+
+/// 重连过程中的状态变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconnectEvent {
+    /// 订阅断开，`error` 为断开原因
+    Disconnected { error: String },
+    /// 即将进行第 `attempt` 次重连，等待 `delay` 后开始
+    Reconnecting { attempt: u32, delay: Duration },
+    /// 第 `attempt` 次重连成功，`from_slot` 为请求补发的起始 slot
+    Reconnected { attempt: u32, from_slot: Option<u64> },
+}
+
+type ReconnectCallback = Arc<dyn Fn(&ReconnectEvent) + Send + Sync>;
+
+/// 按请求建立一次订阅，替代默认的 gRPC 连接（用于测试）
+type Transport = Arc<
+    dyn Fn(SubscribeRequest) -> BoxFuture<'static, Result<AccountEventStream, StreamError>>
+        + Send
+        + Sync,
+>;
+
+/// 断线自动重连的订阅
+///
+/// ```ignore
+/// let events = ReconnectingSubscription::new(manager, request, vec![Protocol::Bonk])
+///     .with_reconnect_config(ReconnectConfig { max_attempts: Some(10), ..Default::default() })
+///     .on_reconnect_event(|event| log::warn!("{event:?}"))
+///     .into_stream();
+/// futures::pin_mut!(events);
+/// while let Some(event) = events.next().await {
+///     // 超过 max_attempts 或认证被拒绝时收到最后一个 Err，之后流结束；Decode 错误之后流继续
+/// }
+/// ```
+pub struct ReconnectingSubscription {
+    manager: SubscriptionManager,
+    request: SubscribeRequest,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+    config: ReconnectConfig,
+    on_event: Option<ReconnectCallback>,
+    dedup: SharedDedup,
+    resume: Option<Checkpoint>,
+    resume_by_write_version: bool,
+    stall_timeout: Option<Duration>,
+    keep_raw_data: bool,
+    clock: Option<Arc<dyn Clock>>,
+    parser_registry: Option<Arc<ParserRegistry>>,
+    transport: Option<Transport>,
+}
+
+impl ReconnectingSubscription {
+    pub fn new(
+        manager: SubscriptionManager,
+        request: SubscribeRequest,
+        protocols: Vec<Protocol>,
+    ) -> Self {
+        Self {
+            manager,
+            request,
+            protocols,
+            event_type_filter: None,
+            config: ReconnectConfig::default(),
+            on_event: None,
+            dedup: SharedDedup::default(),
+            resume: None,
+            resume_by_write_version: false,
+            stall_timeout: None,
+            keep_raw_data: false,
+            clock: None,
+            parser_registry: None,
+            transport: None,
+        }
+    }
+
+    /// 设置事件类型过滤
+    pub fn with_event_type_filter(mut self, event_type_filter: EventTypeFilter) -> Self {
+        self.event_type_filter = Some(event_type_filter);
+        self
+    }
+
+    /// 设置订阅的确认级别，覆盖请求中原有的 `commitment`
+    pub fn with_commitment(mut self, level: Commitment) -> Self {
+        self.request.commitment = Some(CommitmentLevel::from(level).into());
+        self
+    }
+
+    /// 从持久化的处理进度继续订阅
+    ///
+    /// 请求的 `from_slot` 设为 `checkpoint.slot`，由服务端补发该 slot 之后的更新；
+    /// 不在 `checkpoint` 之后的事件（见 [`Checkpoint::is_after`]）被丢弃，不会产出
+    ///
+    /// ```ignore
+    /// let events = ReconnectingSubscription::new(manager, request, vec![Protocol::Bonk])
+    ///     .resume_from(store.load_checkpoint()?)
+    ///     .into_stream();
+    /// futures::pin_mut!(events);
+    /// while let Some(event) = events.next().await {
+    ///     let event = event?;
+    ///     handle(&event);
+    ///     store.save_checkpoint(event.checkpoint())?;
+    /// }
+    /// ```
+    pub fn resume_from(mut self, checkpoint: Checkpoint) -> Self {
+        self.request.from_slot = Some(checkpoint.slot);
+        self.resume = Some(checkpoint);
+        self
+    }
+
+    /// 开启后 `resume_from` 在 checkpoint 所在的 slot 内按写入版本号丢弃已处理的事件，
+    /// 见 [`Checkpoint::is_after_with_write_version`]；只在恢复时连接同一个节点时开启，默认关闭
+    pub fn with_write_version_resume(mut self, enabled: bool) -> Self {
+        self.resume_by_write_version = enabled;
+        self
+    }
+
+    /// 设置重连退避配置
+    pub fn with_reconnect_config(mut self, config: ReconnectConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 在解析之前丢弃重复或过期的账户更新，去重状态在重连前后保持，
+    /// 重连补发的已收到更新也会被丢弃
+    pub fn with_dedup(self, capacity: usize) -> Self {
+        *self.dedup.lock() = Some(DedupLayer::new(capacity));
+        self
+    }
+
+    /// 超过 `timeout` 没有收到任何帧（账户更新、ping 等）时视为连接失效并重连，
+    /// 见 [`AccountEventStream::with_stall_timeout`]
+    pub fn with_stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+        self
+    }
+
+    /// 在事件的 `metadata.raw_data` 中保留原始账户数据，重连后的连接同样生效，
+    /// 见 [`AccountEventStream::with_raw_data`]
+    pub fn with_raw_data(mut self, enabled: bool) -> Self {
+        self.keep_raw_data = enabled;
+        self
+    }
+
+    /// 使用 `clock` 记录事件的时间戳，重连后的连接同样生效，见 [`AccountEventStream::with_clock`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// 使用 `registry` 代替全局解析器注册表，重连后的连接同样生效，
+    /// 见 [`AccountEventStream::with_parser_registry`]
+    pub fn with_parser_registry(mut self, registry: Arc<ParserRegistry>) -> Self {
+        self.parser_registry = Some(registry);
+        self
+    }
+
+    /// 设置重连状态回调，用于记录断开、重连等事件
+    pub fn on_reconnect_event<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ReconnectEvent) + Send + Sync + 'static,
+    {
+        self.on_event = Some(Arc::new(callback));
+        self
+    }
+
+    fn notify(&self, event: ReconnectEvent) {
+        #[cfg(feature = "tracing")]
+        match &event {
+            ReconnectEvent::Disconnected { error } => {
+                tracing::warn!(%error, "subscription disconnected")
+            }
+            ReconnectEvent::Reconnecting { attempt, delay } => {
+                tracing::info!(attempt, ?delay, "reconnecting subscription")
+            }
+            ReconnectEvent::Reconnected { attempt, from_slot } => {
+                tracing::info!(attempt, ?from_slot, "subscription reconnected")
+            }
+        }
+        if let Some(callback) = &self.on_event {
+            callback(&event);
+        }
+    }
+
+    /// 使用 `transport` 代替 gRPC 连接建立订阅，每次（重新）连接调用一次
+    #[cfg(test)]
+    fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    async fn open(&self, request: SubscribeRequest) -> Result<AccountEventStream, StreamError> {
+        if let Some(transport) = &self.transport {
+            return transport(request).await;
+        }
+        let commitment = Commitment::from_request(request.commitment);
+        let (sink, updates) = self.manager.open(request).await?;
+        Ok(subscription_events(
+            updates,
+            sink,
+            self.protocols.clone(),
+            self.event_type_filter.clone(),
+            commitment,
+            self.dedup.clone(),
+        ))
+    }
+
+    async fn connect(&self, from_slot: Option<u64>) -> Result<AccountEventStream, StreamError> {
+        let mut request = self.request.clone();
+        if from_slot.is_some() {
+            request.from_slot = from_slot;
+        }
+        let events = self.open(request).await?.with_raw_data(self.keep_raw_data);
+        let events = match &self.clock {
+            Some(clock) => events.with_clock(clock.clone()),
+            None => events,
+        };
+        let events = match &self.parser_registry {
+            Some(registry) => events.with_parser_registry(registry.clone()),
+            None => events,
+        };
+        Ok(match self.stall_timeout {
+            Some(timeout) => events.with_stall_timeout(timeout),
+            None => events,
+        })
+    }
+
+    /// 转换为事件流
+    ///
+    /// 断线后自动重连，连续重连失败次数超过 `max_attempts` 或遇到不可重试的错误时
+    /// 才产出最后一次的错误并结束
+    pub fn into_stream(self) -> impl Stream<Item = Result<DexEvent, StreamError>> {
+        let state = ReconnectState {
+            subscription: self,
+            current: None,
+            attempt: 0,
+            started: false,
+            connected_once: false,
+            last_slot: None,
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            if state.done {
+                return None;
+            }
+            loop {
+                if let Some(current) = state.current.as_mut() {
+                    let error = match current.next().await {
+                        Some(Ok(event)) => {
+                            if let Some(resume) = &state.subscription.resume {
+                                let checkpoint = event.checkpoint();
+                                let unprocessed = if state.subscription.resume_by_write_version {
+                                    checkpoint.is_after_with_write_version(resume)
+                                } else {
+                                    checkpoint.is_after(resume)
+                                };
+                                if !unprocessed {
+                                    continue;
+                                }
+                            }
+                            let slot = event.metadata().slot;
+                            state.last_slot = Some(state.last_slot.map_or(slot, |s| s.max(slot)));
+                            return Some((Ok(event), state));
+                        }
+                        // 单条更新无法解析，连接本身没有问题
+                        Some(Err(error @ StreamError::Decode(_))) => {
+                            return Some((Err(error), state));
+                        }
+                        Some(Err(error)) => error,
+                        None => StreamError::Closed,
+                    };
+                    state.current = None;
+                    if !error.is_retryable() {
+                        state.done = true;
+                        return Some((Err(error), state));
+                    }
+                    state
+                        .subscription
+                        .notify(ReconnectEvent::Disconnected { error: error.to_string() });
+                    continue;
+                }
+
+                // 除第一次连接外，每次连接前都按退避时间等待
+                if state.started {
+                    state.attempt += 1;
+                    let delay = state.subscription.config.delay_for(state.attempt);
+                    state
+                        .subscription
+                        .notify(ReconnectEvent::Reconnecting { attempt: state.attempt, delay });
+                    tokio::time::sleep(delay).await;
+                }
+                state.started = true;
+
+                let from_slot =
+                    if state.subscription.config.resume_from_slot { state.last_slot } else { None };
+                match state.subscription.connect(from_slot).await {
+                    Ok(events) => {
+                        if state.connected_once {
+                            MetricsManager::global().increment_reconnects();
+                            state.subscription.notify(ReconnectEvent::Reconnected {
+                                attempt: state.attempt,
+                                from_slot,
+                            });
+                        }
+                        state.connected_once = true;
+                        state.attempt = 0;
+                        state.current = Some(events);
+                    }
+                    Err(error) => {
+                        let exhausted = state
+                            .subscription
+                            .config
+                            .max_attempts
+                            .is_some_and(|max| state.attempt >= max);
+                        if exhausted || !error.is_retryable() {
+                            state.done = true;
+                            return Some((Err(error), state));
+                        }
+                        state
+                            .subscription
+                            .notify(ReconnectEvent::Disconnected { error: error.to_string() });
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct ReconnectState {
+    subscription: ReconnectingSubscription,
+    current: Option<AccountEventStream>,
+    /// 当前连续重连的次数
+    attempt: u32,
+    /// 是否已经尝试过连接
+    started: bool,
+    connected_once: bool,
+    last_slot: Option<u64>,
+    done: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::FutureExt;
+    use parking_lot::Mutex;
+    use solana_sdk::pubkey::Pubkey;
+    use tonic::Status;
+    use yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof;
+    use yellowstone_grpc_proto::geyser::{
+        SubscribeUpdate, SubscribeUpdateAccount, SubscribeUpdateAccountInfo,
+    };
+
+    use super::*;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::grpc::{account_events, ClientConfig};
+
+    fn pool_state_update(slot: u64) -> Result<SubscribeUpdate, Status> {
+        let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&PoolState::default()).unwrap());
+        let account = SubscribeUpdateAccountInfo {
+            pubkey: Pubkey::new_unique().to_bytes().to_vec(),
+            lamports: 1_000_000,
+            owner: BONK_PROGRAM_ID.to_bytes().to_vec(),
+            data,
+            write_version: slot,
+            ..Default::default()
+        };
+        Ok(SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Account(SubscribeUpdateAccount {
+                account: Some(account),
+                slot,
+                is_startup: false,
+            })),
+            ..Default::default()
+        })
+    }
+
+    /// 第一次连接推送 slot 1..=3 后断开，之后两次连接被拒绝，第四次连接从请求的
+    /// `from_slot` 开始补发到 slot 5，并记录每次请求的 `from_slot`
+    fn flaky_transport(requests: Arc<Mutex<Vec<Option<u64>>>>) -> Transport {
+        let connects = Arc::new(AtomicUsize::new(0));
+        Arc::new(move |request: SubscribeRequest| {
+            requests.lock().push(request.from_slot);
+            let result = match connects.fetch_add(1, Ordering::SeqCst) {
+                0 => {
+                    let updates = (1..=3).map(pool_state_update);
+                    let lost = Err(Status::unavailable("connection reset"));
+                    let updates = stream::iter(updates.chain([lost]));
+                    Ok(account_events(updates, vec![Protocol::Bonk], None))
+                }
+                1 | 2 => Err(StreamError::Connect("connection refused".to_owned())),
+                _ => {
+                    let from = request.from_slot.unwrap_or(0);
+                    let updates = stream::iter((from..=5).map(pool_state_update));
+                    let updates = updates.chain(stream::pending());
+                    Ok(account_events(updates, vec![Protocol::Bonk], None))
+                }
+            };
+            async move { result }.boxed()
+        })
+    }
+
+    #[tokio::test]
+    async fn recovers_after_two_failed_reconnects_without_losing_events() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let notifications = Arc::new(Mutex::new(Vec::new()));
+        let config = ReconnectConfig {
+            base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..Default::default()
+        };
+        let manager = SubscriptionManager::new(
+            "http://127.0.0.1:1".to_owned(),
+            None,
+            ClientConfig::default(),
+        );
+        let recorded = notifications.clone();
+        let events =
+            ReconnectingSubscription::new(manager, Default::default(), vec![Protocol::Bonk])
+                .with_reconnect_config(config)
+                .with_transport(flaky_transport(requests.clone()))
+                .on_reconnect_event(move |event| recorded.lock().push(event.clone()))
+                .into_stream();
+
+        let slots: Vec<u64> =
+            events.take(6).map(|event| event.unwrap().metadata().slot).collect().await;
+
+        // 断开前的最后一个 slot 被重新请求，之后的 slot 全部收到
+        assert_eq!(slots, vec![1, 2, 3, 3, 4, 5]);
+        assert_eq!(*requests.lock(), vec![None, Some(3), Some(3), Some(3)]);
+        let notifications = notifications.lock();
+        assert!(matches!(notifications[0], ReconnectEvent::Disconnected { .. }));
+        let attempts: Vec<u32> = notifications
+            .iter()
+            .filter_map(|event| match event {
+                ReconnectEvent::Reconnecting { attempt, .. } => Some(*attempt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(attempts, vec![1, 2, 3]);
+        assert_eq!(
+            notifications.last(),
+            Some(&ReconnectEvent::Reconnected { attempt: 3, from_slot: Some(3) })
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_commitment_is_sent_and_recorded_on_events() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let recorded = sent.clone();
+        let transport: Transport = Arc::new(move |request: SubscribeRequest| {
+            recorded.lock().push(request.commitment);
+            // 与真实连接相同，由请求中的 commitment 决定事件元数据中的确认级别
+            let commitment = Commitment::from_request(request.commitment);
+            let updates = stream::iter(vec![pool_state_update(5)]).chain(stream::pending());
+            let events = subscription_events(
+                updates,
+                futures::sink::drain(),
+                vec![Protocol::Bonk],
+                None,
+                commitment,
+                SharedDedup::default(),
+            );
+            async move { Ok(events) }.boxed()
+        });
+        let manager = SubscriptionManager::new(
+            "http://127.0.0.1:1".to_owned(),
+            None,
+            ClientConfig::default(),
+        );
+        let request = SubscribeRequest {
+            commitment: Some(CommitmentLevel::Processed.into()),
+            ..Default::default()
+        };
+        let mut events = Box::pin(
+            ReconnectingSubscription::new(manager, request, vec![Protocol::Bonk])
+                .with_commitment(Commitment::Finalized)
+                .with_transport(transport)
+                .into_stream(),
+        );
+
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(*sent.lock(), vec![Some(CommitmentLevel::Finalized as i32)]);
+        assert_eq!(event.metadata().commitment, Some(Commitment::Finalized));
+    }
+}
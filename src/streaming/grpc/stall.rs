@@ -0,0 +1,51 @@
+//! 订阅停滞检测
+//!
+//! 服务端不再推送任何更新、但 TCP 连接没有断开时，订阅流既不产出事件也不报错，消费者会
+//! 一直等待。Yellowstone 服务端会定期推送 ping，正常的订阅即使没有账户更新也会持续收到帧；
+//! [`StallDetector`] 记录最近一次收到帧（含 ping）的时间，超过 `timeout` 没有收到任何帧时
+//! 视为停滞，由事件流产出 `StreamError::Stalled` 后结束，交给重连逻辑处理。
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// 记录最近一次收到帧的时间，判断订阅是否停滞
+///
+/// 任何帧（账户更新、ping、slot 等）都应调用 [`StallDetector::record_frame`]。`timeout` 应大于
+/// 服务端的 ping 间隔，避免把没有账户更新的正常订阅误判为停滞
+#[derive(Debug, Clone)]
+pub struct StallDetector {
+    timeout: Duration,
+    last_frame: Instant,
+}
+
+impl StallDetector {
+    /// 从当前时间开始计时
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, last_frame: Instant::now() }
+    }
+
+    /// 收到一帧，重新开始计时
+    pub fn record_frame(&mut self) {
+        self.last_frame = Instant::now();
+    }
+
+    /// 距离最近一次收到帧的时间
+    pub fn idle(&self) -> Duration {
+        self.last_frame.elapsed()
+    }
+
+    /// 是否已经超过 `timeout` 没有收到任何帧
+    pub fn is_stalled(&self) -> bool {
+        self.idle() >= self.timeout
+    }
+
+    /// 没有新的帧时判定为停滞的时间点
+    pub fn deadline(&self) -> Instant {
+        self.last_frame + self.timeout
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
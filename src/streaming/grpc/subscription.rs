@@ -1,13 +1,20 @@
 use futures::{channel::mpsc, sink::Sink, Stream};
 use maplit::hashmap;
 use std::{collections::HashMap, time::Duration};
-use tonic::{transport::channel::ClientTlsConfig, Status};
+use tonic::codec::CompressionEncoding;
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::{channel::ClientTlsConfig, Channel, Endpoint};
+use tonic::Status;
+use tonic_health::pb::health_client::HealthClient;
 use yellowstone_grpc_client::{GeyserGrpcClient, Interceptor};
 use yellowstone_grpc_proto::geyser::{
-    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+    geyser_client::GeyserClient, CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
     SubscribeRequestFilterBlocksMeta, SubscribeRequestFilterTransactions, SubscribeUpdate,
 };
 
+use super::decompression::DecompressionGuard;
+use super::error::StreamError;
+use super::interceptor::HeaderInterceptor;
 use super::types::AccountsFilterMap;
 use super::types::TransactionsFilterMap;
 use crate::common::AnyResult;
@@ -31,14 +38,61 @@ impl SubscriptionManager {
     }
 
     /// Create gRPC connection
+    ///
+    /// `x-token` and the custom headers from `ConnectionConfig::headers` are attached to
+    /// every request through `HeaderInterceptor`
     pub async fn connect(&self) -> AnyResult<GeyserGrpcClient<impl Interceptor>> {
-        let builder = GeyserGrpcClient::build_from_shared(self.endpoint.clone())?
-            .x_token(self.x_token.clone())?
+        let connection = &self.config.connection;
+        let interceptor = HeaderInterceptor::new(self.x_token.as_deref(), &connection.headers)?;
+        let channel = self.channel().await?;
+        let geyser = GeyserClient::with_interceptor(channel.clone(), interceptor.clone())
+            .max_decoding_message_size(connection.max_decoding_message_size);
+        let health = HealthClient::with_interceptor(channel, interceptor);
+        Ok(GeyserGrpcClient::new(health, geyser))
+    }
+
+    async fn channel(&self) -> AnyResult<Channel> {
+        let connection = &self.config.connection;
+        let channel = Endpoint::from_shared(self.endpoint.clone())?
             .tls_config(ClientTlsConfig::new().with_native_roots())?
-            .max_decoding_message_size(self.config.connection.max_decoding_message_size)
-            .connect_timeout(Duration::from_secs(self.config.connection.connect_timeout))
-            .timeout(Duration::from_secs(self.config.connection.request_timeout));
-        Ok(builder.connect().await?)
+            .connect_timeout(Duration::from_secs(connection.connect_timeout))
+            .timeout(Duration::from_secs(connection.request_timeout))
+            .connect()
+            .await?;
+        Ok(channel)
+    }
+
+    /// Open a subscription with `request` and return the request sink and the update stream
+    ///
+    /// Unlike the client returned by `connect`, this subscription accepts gzip-compressed
+    /// messages from the server. Every compressed message is checked against
+    /// `ConnectionConfig::max_decompression_ratio` by `DecompressionGuard`.
+    pub async fn open(
+        &self,
+        request: SubscribeRequest,
+    ) -> Result<
+        (
+            impl Sink<SubscribeRequest, Error = mpsc::SendError>,
+            impl Stream<Item = Result<SubscribeUpdate, Status>>,
+        ),
+        StreamError,
+    > {
+        let connection = &self.config.connection;
+        let interceptor = HeaderInterceptor::new(self.x_token.as_deref(), &connection.headers)
+            .map_err(|e| StreamError::Connect(e.to_string()))?;
+        let channel = self.channel().await.map_err(|e| StreamError::Connect(e.to_string()))?;
+        let service = DecompressionGuard::new(
+            InterceptedService::new(channel, interceptor),
+            connection.max_decompression_ratio,
+            connection.max_decoding_message_size,
+        );
+        let mut geyser = GeyserClient::new(service)
+            .accept_compressed(CompressionEncoding::Gzip)
+            .max_decoding_message_size(connection.max_decoding_message_size);
+        let (sink, requests) = mpsc::unbounded();
+        sink.unbounded_send(request).map_err(|e| StreamError::Connect(e.to_string()))?;
+        let updates = geyser.subscribe(requests).await?.into_inner();
+        Ok((sink, updates))
     }
 
     /// Create subscription request and return stream
@@ -68,12 +122,11 @@ impl SubscriptionManager {
             commitment: if let Some(commitment) = commitment {
                 Some(commitment as i32)
             } else {
-                Some(CommitmentLevel::Processed.into())
+                Some(CommitmentLevel::Confirmed.into())
             },
             ..Default::default()
         };
-        let mut client = self.connect().await?;
-        let (sink, stream) = client.subscribe_with_request(Some(subscribe_request.clone())).await?;
+        let (sink, stream) = self.open(subscribe_request.clone()).await?;
         Ok((sink, stream, subscribe_request))
     }
 
@@ -135,3 +188,194 @@ impl SubscriptionManager {
         &self.config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::{self, BoxStream};
+    use futures::StreamExt;
+    use solana_sdk::pubkey::Pubkey;
+    use tokio::net::TcpListener;
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+    use tonic::{Request, Response, Streaming};
+    use yellowstone_grpc_proto::geyser::geyser_server::{Geyser, GeyserServer};
+    use yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof;
+    use yellowstone_grpc_proto::geyser::{
+        GetBlockHeightRequest, GetBlockHeightResponse, GetLatestBlockhashRequest,
+        GetLatestBlockhashResponse, GetSlotRequest, GetSlotResponse, GetVersionRequest,
+        GetVersionResponse, IsBlockhashValidRequest, IsBlockhashValidResponse, PingRequest,
+        PongResponse, SubscribeReplayInfoRequest, SubscribeReplayInfoResponse,
+        SubscribeUpdateAccount, SubscribeUpdateAccountInfo,
+    };
+
+    use super::*;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::event_parser::Protocol;
+    use crate::streaming::grpc::ReconnectingSubscription;
+
+    /// 推送固定的更新后保持连接的 Geyser 服务
+    struct MockGeyser {
+        updates: Vec<SubscribeUpdate>,
+    }
+
+    #[tonic::async_trait]
+    impl Geyser for MockGeyser {
+        type SubscribeStream = BoxStream<'static, Result<SubscribeUpdate, Status>>;
+
+        async fn subscribe(
+            &self,
+            _request: Request<Streaming<SubscribeRequest>>,
+        ) -> Result<Response<Self::SubscribeStream>, Status> {
+            let updates = stream::iter(self.updates.clone().into_iter().map(Ok));
+            Ok(Response::new(updates.chain(stream::pending()).boxed()))
+        }
+
+        async fn subscribe_replay_info(
+            &self,
+            _request: Request<SubscribeReplayInfoRequest>,
+        ) -> Result<Response<SubscribeReplayInfoResponse>, Status> {
+            Err(Status::unimplemented("subscribe_replay_info"))
+        }
+
+        async fn ping(
+            &self,
+            _request: Request<PingRequest>,
+        ) -> Result<Response<PongResponse>, Status> {
+            Err(Status::unimplemented("ping"))
+        }
+
+        async fn get_latest_blockhash(
+            &self,
+            _request: Request<GetLatestBlockhashRequest>,
+        ) -> Result<Response<GetLatestBlockhashResponse>, Status> {
+            Err(Status::unimplemented("get_latest_blockhash"))
+        }
+
+        async fn get_block_height(
+            &self,
+            _request: Request<GetBlockHeightRequest>,
+        ) -> Result<Response<GetBlockHeightResponse>, Status> {
+            Err(Status::unimplemented("get_block_height"))
+        }
+
+        async fn get_slot(
+            &self,
+            _request: Request<GetSlotRequest>,
+        ) -> Result<Response<GetSlotResponse>, Status> {
+            Err(Status::unimplemented("get_slot"))
+        }
+
+        async fn is_blockhash_valid(
+            &self,
+            _request: Request<IsBlockhashValidRequest>,
+        ) -> Result<Response<IsBlockhashValidResponse>, Status> {
+            Err(Status::unimplemented("is_blockhash_valid"))
+        }
+
+        async fn get_version(
+            &self,
+            _request: Request<GetVersionRequest>,
+        ) -> Result<Response<GetVersionResponse>, Status> {
+            Err(Status::unimplemented("get_version"))
+        }
+    }
+
+    /// 在本地端口启动 `MockGeyser`，`gzip` 为 true 时对客户端接受压缩的响应使用 gzip
+    async fn serve(updates: Vec<SubscribeUpdate>, gzip: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let mut service = GeyserServer::new(MockGeyser { updates });
+        if gzip {
+            service = service.send_compressed(CompressionEncoding::Gzip);
+        }
+        let server = Server::builder().add_service(service);
+        tokio::spawn(server.serve_with_incoming(TcpIncoming::from(listener)));
+        endpoint
+    }
+
+    fn account_update(owner: Pubkey, data: Vec<u8>) -> SubscribeUpdate {
+        let account = SubscribeUpdateAccountInfo {
+            pubkey: Pubkey::new_unique().to_bytes().to_vec(),
+            lamports: 1_000_000,
+            owner: owner.to_bytes().to_vec(),
+            data,
+            ..Default::default()
+        };
+        SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Account(SubscribeUpdateAccount {
+                account: Some(account),
+                slot: 1,
+                is_startup: false,
+            })),
+            ..Default::default()
+        }
+    }
+
+    fn pool_state_update() -> SubscribeUpdate {
+        let mut data = POOL_STATE_DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&PoolState::default()).unwrap());
+        account_update(BONK_PROGRAM_ID, data)
+    }
+
+    /// 先推送一个正常的 PoolState，再推送 `oversized`，返回订阅产出的全部结果
+    async fn subscribe_through(
+        oversized: SubscribeUpdate,
+        gzip: bool,
+        config: ClientConfig,
+    ) -> Vec<Result<crate::streaming::event_parser::DexEvent, StreamError>> {
+        let endpoint = serve(vec![pool_state_update(), oversized], gzip).await;
+        let manager = SubscriptionManager::new(endpoint, None, config);
+        let events =
+            ReconnectingSubscription::new(manager, Default::default(), vec![Protocol::Bonk])
+                .into_stream();
+        tokio::time::timeout(Duration::from_secs(10), events.collect()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn frame_over_the_max_message_size_ends_the_subscription() {
+        let oversized = account_update(Pubkey::new_unique(), vec![7; 64 * 1024]);
+        let config = ClientConfig::default().with_max_message_size(16 * 1024);
+
+        let results = subscribe_through(oversized, false, config).await;
+
+        // 限制以下的帧正常解析，超限的帧产出一次不可重试的错误后流结束
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        let error = results[1].as_ref().unwrap_err();
+        assert!(error.is_message_too_large(), "{error}");
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn gzip_frame_beyond_the_decompression_ratio_ends_the_subscription() {
+        let zeros = account_update(Pubkey::new_unique(), vec![0; 1024 * 1024]);
+        let config = ClientConfig::default().with_max_decompression_ratio(50);
+
+        let results = subscribe_through(zeros, true, config).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        let error = results[1].as_ref().unwrap_err();
+        assert!(error.is_message_too_large(), "{error}");
+        assert!(error.to_string().contains("50x decompression ratio"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn gzip_frame_within_the_decompression_ratio_is_decoded() {
+        let zeros = account_update(Pubkey::new_unique(), vec![0; 1024 * 1024]);
+        let endpoint = serve(vec![pool_state_update(), zeros], true).await;
+        // 全零数据的压缩比约为 1000
+        let config = ClientConfig::default().with_max_decompression_ratio(2_000);
+        let manager = SubscriptionManager::new(endpoint, None, config);
+
+        let (_sink, updates) = manager.open(Default::default()).await.unwrap();
+        let updates: Vec<_> = updates.take(2).collect().await;
+
+        let Some(UpdateOneof::Account(update)) = &updates[1].as_ref().unwrap().update_oneof else {
+            panic!("expected an account update");
+        };
+        assert_eq!(update.account.as_ref().unwrap().data.len(), 1024 * 1024);
+    }
+}
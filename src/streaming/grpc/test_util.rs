@@ -0,0 +1,221 @@
+//! 构造测试用 `AccountPretty` 的辅助工具（`test-util` feature）
+//!
+//! 编写账户解析器的测试时，需要拼接 8 字节 discriminator 和 borsh 编码的账户结构体，
+//! 并填写合理的 pubkey、owner 和 lamports。[`AccountPrettyBuilder`] 自动处理这些细节：
+//!
+//! ```ignore
+//! let account = AccountPrettyBuilder::new()
+//!     .owner(BONK_PROGRAM_ID)
+//!     .discriminator(POOL_STATE_DISCRIMINATOR)
+//!     .borsh_data(&pool_state)
+//!     .build();
+//! let event = AccountEventParser::parse_account_event(&[Protocol::Bonk], account, None);
+//! ```
+//!
+//! 已有真实账户数据（RPC 返回的 base64）时，可用 [`run_account_fixture`] 对照期望的 JSON 测试解析器，
+//! fixture 文件的格式见 `tests/fixtures/README.md`
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use borsh::BorshSerialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use super::types::AccountPretty;
+use crate::streaming::event_parser::core::account_event_parser::decode_account_str;
+
+/// 默认的账户余额，非 0 以免账户被当作已关闭
+pub const DEFAULT_TEST_LAMPORTS: u64 = 1_000_000_000;
+
+/// 逐字段构造 `AccountPretty`
+///
+/// 未设置的 pubkey 和 owner 使用 `Pubkey::new_unique()`，lamports 为
+/// [`DEFAULT_TEST_LAMPORTS`]，slot 为 1，`rent_epoch` 为 `u64::MAX`（免租）。
+/// 设置了 discriminator 时，`build` 会将其放在账户数据之前
+#[derive(Clone)]
+pub struct AccountPrettyBuilder {
+    account: AccountPretty,
+    discriminator: Option<[u8; 8]>,
+}
+
+impl Default for AccountPrettyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountPrettyBuilder {
+    pub fn new() -> Self {
+        Self {
+            account: AccountPretty {
+                slot: 1,
+                pubkey: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                lamports: DEFAULT_TEST_LAMPORTS,
+                rent_epoch: u64::MAX,
+                ..Default::default()
+            },
+            discriminator: None,
+        }
+    }
+
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.account.slot = slot;
+        self
+    }
+
+    pub fn signature(mut self, signature: Signature) -> Self {
+        self.account.signature = signature;
+        self
+    }
+
+    pub fn pubkey(mut self, pubkey: Pubkey) -> Self {
+        self.account.pubkey = pubkey;
+        self
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.account.owner = owner;
+        self
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.account.lamports = lamports;
+        self
+    }
+
+    pub fn executable(mut self, executable: bool) -> Self {
+        self.account.executable = executable;
+        self
+    }
+
+    pub fn rent_epoch(mut self, rent_epoch: u64) -> Self {
+        self.account.rent_epoch = rent_epoch;
+        self
+    }
+
+    pub fn write_version(mut self, write_version: u64) -> Self {
+        self.account.write_version = write_version;
+        self
+    }
+
+    pub fn recv_us(mut self, recv_us: i64) -> Self {
+        self.account.recv_us = recv_us;
+        self
+    }
+
+    /// 账户数据的前 8 字节，`build` 时放在 [`Self::data`] / [`Self::borsh_data`] 之前
+    pub fn discriminator(mut self, discriminator: [u8; 8]) -> Self {
+        self.discriminator = Some(discriminator);
+        self
+    }
+
+    /// discriminator 之后的原始账户数据
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.account.data = data;
+        self
+    }
+
+    /// 以 `value` 的 borsh 编码作为 discriminator 之后的账户数据
+    pub fn borsh_data<T: BorshSerialize>(mut self, value: &T) -> Self {
+        self.account.data = borsh::to_vec(value).expect("borsh serialization into Vec cannot fail");
+        self
+    }
+
+    pub fn build(self) -> AccountPretty {
+        let mut account = self.account;
+        if let Some(discriminator) = self.discriminator {
+            let mut data = Vec::with_capacity(8 + account.data.len());
+            data.extend_from_slice(&discriminator);
+            data.append(&mut account.data);
+            account.data = data;
+        }
+        account
+    }
+}
+
+/// 以 fixture 测试账户解析器，解析失败或与期望不符时 panic
+///
+/// `base64_data` 为 RPC 返回的完整账户数据（含 discriminator），须以 `discriminator` 开头。
+/// 不同程序的账户可能使用相同的 discriminator（如 Anchor 的 `PoolState`），因此需要传入 `owner`
+/// 选择解析器。`expected` 为 JSON 对象，与 [`DexEvent::to_json_value`] 输出的 `data` 比较：
+/// 只比较 `expected` 中出现的字段（嵌套对象同样如此），数组和其他取值须完全相等
+///
+/// ```ignore
+/// run_account_fixture(
+///     &BONK_PROGRAM_ID,
+///     POOL_STATE_DISCRIMINATOR,
+///     include_str!("../tests/fixtures/bonk/pool_state.b64"),
+///     include_str!("../tests/fixtures/bonk/pool_state.json"),
+/// );
+/// ```
+///
+/// [`DexEvent::to_json_value`]: crate::streaming::event_parser::DexEvent::to_json_value
+pub fn run_account_fixture(
+    owner: &Pubkey,
+    discriminator: [u8; 8],
+    base64_data: &str,
+    expected: &str,
+) {
+    let data = STANDARD.decode(base64_data.trim()).expect("fixture data is not valid base64");
+    assert_eq!(data.get(..8), Some(&discriminator[..]), "fixture data has another discriminator");
+    let event = decode_account_str(base64_data, owner, &Pubkey::default())
+        .expect("fixture account was not parsed");
+    let actual = event.to_json_value().expect("DexEvent serialization cannot fail");
+    let expected: serde_json::Value =
+        serde_json::from_str(expected).expect("expected fixture is not valid JSON");
+    assert!(expected.is_object(), "expected fixture must be a JSON object");
+    assert_json_subset(&expected, &actual["data"], "data");
+}
+
+fn assert_json_subset(expected: &serde_json::Value, actual: &serde_json::Value, path: &str) {
+    match (expected, actual) {
+        (serde_json::Value::Object(expected), serde_json::Value::Object(actual)) => {
+            for (key, value) in expected {
+                let path = format!("{path}.{key}");
+                let actual = actual.get(key).unwrap_or_else(|| panic!("missing field `{path}`"));
+                assert_json_subset(value, actual, &path);
+            }
+        }
+        _ => assert_eq!(expected, actual, "field `{path}`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::event_parser::{DexEvent, Protocol};
+
+    #[test]
+    fn build_prefixes_discriminator() {
+        let account = AccountPrettyBuilder::new()
+            .discriminator([1, 2, 3, 4, 5, 6, 7, 8])
+            .data(vec![9, 10])
+            .build();
+        assert_eq!(account.data, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(account.lamports, DEFAULT_TEST_LAMPORTS);
+        assert_ne!(account.pubkey, account.owner);
+    }
+
+    #[test]
+    fn bonk_pool_state_parses() {
+        let pool_state = PoolState { supply: 1_000, ..Default::default() };
+        let pubkey = Pubkey::new_unique();
+        let account = AccountPrettyBuilder::new()
+            .pubkey(pubkey)
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(&pool_state)
+            .build();
+        let event = AccountEventParser::parse_account_event(&[Protocol::Bonk], account, None);
+        let Some(DexEvent::BonkPoolStateAccountEvent(event)) = event else {
+            panic!("expected a pool state event, got {event:?}");
+        };
+        assert_eq!(event.base.pubkey, pubkey);
+        assert_eq!(event.pool_state, pool_state);
+    }
+}
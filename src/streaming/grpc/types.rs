@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use std::{collections::HashMap, fmt};
 use yellowstone_grpc_proto::{
@@ -29,6 +30,62 @@ pub struct AccountPretty {
     pub rent_epoch: u64,
     pub data: Vec<u8>,
     pub recv_us: i64,
+    /// 账户写入版本号，同一节点上单调递增，用于判断同一账户更新的先后（快照等来源为 0）
+    pub write_version: u64,
+}
+
+impl AccountPretty {
+    /// 从 base58 编码的账户数据构建（例如 `solana account` CLI 的输出），解码失败返回 `None`
+    pub fn from_base58_data(
+        pubkey: Pubkey,
+        owner: Pubkey,
+        data_b58: &str,
+        lamports: u64,
+        slot: u64,
+    ) -> Option<AccountPretty> {
+        let data = bs58::decode(data_b58.trim()).into_vec().ok()?;
+        Some(AccountPretty { slot, pubkey, lamports, owner, data, ..Default::default() })
+    }
+
+    /// 从 base64 编码的账户数据构建（例如 RPC `getAccountInfo` 的输出），
+    /// 同时接受标准字母表和 URL 安全字母表，解码失败返回 `None`
+    pub fn from_base64_data(
+        pubkey: Pubkey,
+        owner: Pubkey,
+        data_b64: &str,
+        lamports: u64,
+        slot: u64,
+    ) -> Option<AccountPretty> {
+        use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+        use base64::Engine;
+
+        let data_b64 = data_b64.trim();
+        let data = STANDARD.decode(data_b64).or_else(|_| URL_SAFE.decode(data_b64)).ok()?;
+        Some(AccountPretty { slot, pubkey, lamports, owner, data, ..Default::default() })
+    }
+
+    /// 账户事件共有的账户字段，账户事件通过 `base` 字段嵌入
+    pub fn base(&self) -> AccountEventBase {
+        AccountEventBase {
+            pubkey: self.pubkey,
+            executable: self.executable,
+            lamports: self.lamports,
+            owner: self.owner,
+            rent_epoch: self.rent_epoch,
+        }
+    }
+}
+
+/// 账户事件共有的账户字段，由 [`AccountPretty::base`] 生成
+///
+/// 嵌入账户事件时使用 `#[serde(flatten)]`，JSON 中的字段与事件自身的字段处于同一层
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountEventBase {
+    pub pubkey: Pubkey,
+    pub executable: bool,
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub rent_epoch: u64,
 }
 
 impl fmt::Debug for AccountPretty {
@@ -42,6 +99,7 @@ impl fmt::Debug for AccountPretty {
             .field("owner", &self.owner)
             .field("rent_epoch", &self.rent_epoch)
             .field("data", &self.data)
+            .field("write_version", &self.write_version)
             .finish()
     }
 }
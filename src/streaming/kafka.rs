@@ -0,0 +1,183 @@
+//! Kafka 事件输出（需要启用 `kafka` feature）
+//!
+//! 将 `DexEvent` 序列化后发送到指定 topic。账户事件以账户地址作为消息 key，
+//! 其他事件以交易签名作为 key，保证同一账户/交易的消息落在同一分区。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use log::error;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use rdkafka::ClientConfig as KafkaClientConfig;
+use tokio::sync::mpsc;
+
+use crate::common::{AnyResult, StringNumbers};
+use crate::streaming::event_parser::DexEvent;
+
+/// 消息序列化格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KafkaPayloadFormat {
+    #[default]
+    Json,
+    Bincode,
+}
+
+/// Kafka 输出配置
+#[derive(Debug, Clone)]
+pub struct KafkaSinkConfig {
+    /// Kafka 集群地址（`bootstrap.servers`）
+    pub brokers: String,
+    /// 目标 topic
+    pub topic: String,
+    /// 消息序列化格式
+    pub format: KafkaPayloadFormat,
+    /// 生产者本地队列已满时最长等待时间（毫秒），超时计为投递失败
+    pub queue_timeout_ms: u64,
+    /// 消息投递超时时间（毫秒）
+    pub message_timeout_ms: u64,
+    /// JSON 格式下将 u64/u128 输出为字符串，避免 JavaScript 等消费者丢失精度
+    pub u64_as_string: bool,
+    /// `into_callback` 使用的待发送事件通道容量，通道已满时新事件被丢弃并计数
+    pub channel_capacity: usize,
+    /// `into_callback` 同时等待投递结果的最大消息数量，达到上限后暂停从通道取出新事件
+    pub max_in_flight: usize,
+}
+
+impl Default for KafkaSinkConfig {
+    fn default() -> Self {
+        Self {
+            brokers: "localhost:9092".to_string(),
+            topic: "solana-dex-events".to_string(),
+            format: KafkaPayloadFormat::default(),
+            queue_timeout_ms: 1000,
+            message_timeout_ms: 5000,
+            u64_as_string: false,
+            channel_capacity: 10_000,
+            max_in_flight: 256,
+        }
+    }
+}
+
+/// Kafka 事件输出
+pub struct KafkaSink {
+    producer: FutureProducer,
+    config: KafkaSinkConfig,
+    delivered_count: AtomicU64,
+    delivery_error_count: AtomicU64,
+    dropped_count: AtomicU64,
+}
+
+impl KafkaSink {
+    pub fn new(config: KafkaSinkConfig) -> AnyResult<Self> {
+        let producer: FutureProducer = KafkaClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("message.timeout.ms", config.message_timeout_ms.to_string())
+            .create()?;
+        Ok(Self {
+            producer,
+            config,
+            delivered_count: AtomicU64::new(0),
+            delivery_error_count: AtomicU64::new(0),
+            dropped_count: AtomicU64::new(0),
+        })
+    }
+
+    /// 序列化事件
+    fn encode(&self, event: &DexEvent) -> AnyResult<Vec<u8>> {
+        Ok(match self.config.format {
+            KafkaPayloadFormat::Json if self.config.u64_as_string => {
+                serde_json::to_vec(&StringNumbers(event))?
+            }
+            KafkaPayloadFormat::Json => serde_json::to_vec(event)?,
+            KafkaPayloadFormat::Bincode => bincode::serialize(event)?,
+        })
+    }
+
+    /// 消息 key：账户事件使用账户地址，其他事件使用交易签名
+    fn message_key(event: &DexEvent) -> String {
+        match event.account_pubkey() {
+            Some(pubkey) => pubkey.to_string(),
+            None => event.metadata().signature.to_string(),
+        }
+    }
+
+    /// 发送单个事件并等待投递结果
+    ///
+    /// 生产者队列已满时最多等待 `queue_timeout_ms`，形成对上游的背压
+    pub async fn send(&self, event: &DexEvent) -> AnyResult<()> {
+        let payload = self.encode(event)?;
+        let key = Self::message_key(event);
+        let record = FutureRecord::to(&self.config.topic).key(&key).payload(&payload);
+        match self
+            .producer
+            .send(record, Timeout::After(Duration::from_millis(self.config.queue_timeout_ms)))
+            .await
+        {
+            Ok(_) => {
+                self.delivered_count.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err((e, _)) => {
+                self.delivery_error_count.fetch_add(1, Ordering::Relaxed);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// 生成可直接传给订阅接口的回调，需要在 tokio 运行时内调用
+    ///
+    /// 回调将事件放入容量为 `channel_capacity` 的通道，由后台任务取出发送，最多同时有
+    /// `max_in_flight` 条消息等待投递结果，不必等上一条投递完成再发送下一条。
+    /// 回调使用 `try_send`，不会阻塞调用方；Kafka 跟不上导致通道已满时事件被丢弃并计入
+    /// [`Self::get_dropped_count`]。所有回调副本被释放后，后台任务等待剩余事件投递完成后退出
+    pub fn into_callback(self: Arc<Self>) -> impl Fn(DexEvent) + Send + Sync + 'static {
+        let (tx, mut rx) = mpsc::channel::<DexEvent>(self.config.channel_capacity.max(1));
+        let max_in_flight = self.config.max_in_flight.max(1);
+        let sink = self.clone();
+        tokio::spawn(async move {
+            let deliver = |event: DexEvent| {
+                let sink = sink.clone();
+                async move {
+                    if let Err(e) = sink.send(&event).await {
+                        error!("Failed to deliver event to Kafka: {e:?}");
+                    }
+                }
+            };
+            let mut in_flight = FuturesUnordered::new();
+            loop {
+                tokio::select! {
+                    event = rx.recv(), if in_flight.len() < max_in_flight => match event {
+                        Some(event) => in_flight.push(deliver(event)),
+                        None => break,
+                    },
+                    Some(()) = in_flight.next(), if !in_flight.is_empty() => {}
+                }
+            }
+            while in_flight.next().await.is_some() {}
+        });
+        move |event: DexEvent| {
+            if tx.try_send(event).is_err() {
+                self.dropped_count.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// 已成功投递的消息数量
+    pub fn get_delivered_count(&self) -> u64 {
+        self.delivered_count.load(Ordering::Relaxed)
+    }
+
+    /// 投递失败的消息数量
+    pub fn get_delivery_error_count(&self) -> u64 {
+        self.delivery_error_count.load(Ordering::Relaxed)
+    }
+
+    /// 因 `into_callback` 的通道已满而丢弃的事件数量
+    pub fn get_dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+}
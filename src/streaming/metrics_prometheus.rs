@@ -0,0 +1,259 @@
+//! Prometheus 指标导出（`metrics-prometheus` feature）
+//!
+//! [`register_metrics`] 向 `prometheus::Registry` 注册一个 collector，每次 gather 时从
+//! [`ParserMetrics`] 和 [`MetricsManager`] 读取当前值，核心计数器本身不依赖 prometheus。
+//! 两者默认关闭，需要分别开启后才会有数据：
+//!
+//! ```ignore
+//! ParserMetrics::global().set_enabled(true);
+//! let registry = prometheus::Registry::new();
+//! register_metrics(&registry)?;
+//! let families = registry.gather();
+//! ```
+//!
+//! | 指标 | 类型 | 标签 |
+//! |---|---|---|
+//! | `solana_streamer_parse_attempted_total` | counter | `event_type` |
+//! | `solana_streamer_parse_succeeded_total` | counter | `event_type` |
+//! | `solana_streamer_parse_failed_total` | counter | `event_type` |
+//! | `solana_streamer_parse_too_short_total` | counter | `event_type` |
+//! | `solana_streamer_parse_borsh_failed_total` | counter | `event_type` |
+//! | `solana_streamer_parse_round_trip_mismatch_total` | counter | `event_type` |
+//! | `solana_streamer_reconnects_total` | counter | |
+//! | `solana_streamer_dropped_events_total` | counter | |
+//! | `solana_streamer_processing_latency_us` | gauge | `kind`（tx/account/block_meta）、`stat` |
+//! | `solana_streamer_buffer_high_water_mark` | gauge | |
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{GaugeVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry};
+
+use crate::streaming::common::{MetricsEventType, MetricsManager};
+use crate::streaming::event_parser::core::ParserMetrics;
+
+/// 向 `registry` 注册解析和订阅相关的指标
+pub fn register_metrics(registry: &Registry) -> prometheus::Result<()> {
+    registry.register(Box::new(StreamerCollector::new()?))
+}
+
+struct StreamerCollector {
+    attempted: IntCounterVec,
+    succeeded: IntCounterVec,
+    failed: IntCounterVec,
+    too_short: IntCounterVec,
+    borsh_failed: IntCounterVec,
+    round_trip_mismatch: IntCounterVec,
+    reconnects: IntCounter,
+    dropped_events: IntCounter,
+    processing_latency: GaugeVec,
+    buffer_high_water_mark: IntGauge,
+    /// 各计数器上一次读到的源计数，按（指标名，标签）索引；同时串行化 gather 时的刷新，
+    /// 避免并发 gather 重复累加
+    last_seen: Mutex<HashMap<(&'static str, &'static str), u64>>,
+}
+
+impl StreamerCollector {
+    fn new() -> prometheus::Result<Self> {
+        let parse_counter =
+            |name: &str, help: &str| IntCounterVec::new(Opts::new(name, help), &["event_type"]);
+        Ok(Self {
+            attempted: parse_counter(
+                "solana_streamer_parse_attempted_total",
+                "Account decode attempts by event type",
+            )?,
+            succeeded: parse_counter(
+                "solana_streamer_parse_succeeded_total",
+                "Successful account decodes by event type",
+            )?,
+            failed: parse_counter(
+                "solana_streamer_parse_failed_total",
+                "Failed account decodes by event type",
+            )?,
+            too_short: parse_counter(
+                "solana_streamer_parse_too_short_total",
+                "Account decodes that failed because the data was too short",
+            )?,
+            borsh_failed: parse_counter(
+                "solana_streamer_parse_borsh_failed_total",
+                "Account decodes that failed in borsh deserialization",
+            )?,
+            round_trip_mismatch: parse_counter(
+                "solana_streamer_parse_round_trip_mismatch_total",
+                "Verified account decodes whose re-encoding differed from the account data",
+            )?,
+            reconnects: IntCounter::new(
+                "solana_streamer_reconnects_total",
+                "Successful subscription reconnects",
+            )?,
+            dropped_events: IntCounter::new(
+                "solana_streamer_dropped_events_total",
+                "Events dropped because the parse buffer was full",
+            )?,
+            processing_latency: GaugeVec::new(
+                Opts::new(
+                    "solana_streamer_processing_latency_us",
+                    "Event processing time in microseconds",
+                ),
+                &["kind", "stat"],
+            )?,
+            buffer_high_water_mark: IntGauge::new(
+                "solana_streamer_buffer_high_water_mark",
+                "Highest observed parse buffer depth",
+            )?,
+            last_seen: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 按源计数相对上一次读数的增量推进计数器
+    ///
+    /// 源计数小于上一次读数时视为被重置（如 `ParserMetrics::reset`），从 0 开始计算增量
+    fn advance(counter: &IntCounter, last_seen: &mut u64, value: u64) {
+        let delta = if value >= *last_seen { value - *last_seen } else { value };
+        counter.inc_by(delta);
+        *last_seen = value;
+    }
+
+    fn refresh(&self) {
+        let mut last_seen = self.last_seen.lock();
+        let mut advance = |counter: &IntCounter, key, value| {
+            Self::advance(counter, last_seen.entry(key).or_default(), value)
+        };
+        for (event_type, counts) in ParserMetrics::global().snapshot() {
+            let label = event_type.metric_label();
+            let labels = [label];
+            let parse_counters = [
+                (&self.attempted, "attempted", counts.attempted),
+                (&self.succeeded, "succeeded", counts.succeeded),
+                (&self.failed, "failed", counts.failed()),
+                (&self.too_short, "too_short", counts.too_short),
+                (&self.borsh_failed, "borsh_failed", counts.borsh_failed),
+                (&self.round_trip_mismatch, "round_trip_mismatch", counts.round_trip_mismatch),
+            ];
+            for (counter, name, value) in parse_counters {
+                advance(&counter.with_label_values(&labels), (name, label), value);
+            }
+        }
+
+        let metrics = MetricsManager::global();
+        advance(&self.reconnects, ("reconnects", ""), metrics.get_reconnect_count());
+        advance(&self.dropped_events, ("dropped_events", ""), metrics.get_dropped_events_count());
+        for (kind, event_type) in [
+            ("tx", MetricsEventType::Transaction),
+            ("account", MetricsEventType::Account),
+            ("block_meta", MetricsEventType::BlockMeta),
+        ] {
+            let stats = metrics.get_event_metrics(event_type).processing_stats;
+            self.processing_latency.with_label_values(&[kind, "last"]).set(stats.last_us);
+            self.processing_latency.with_label_values(&[kind, "avg"]).set(stats.avg_us);
+        }
+        self.buffer_high_water_mark.set(metrics.get_buffer_high_water_mark() as i64);
+    }
+}
+
+impl Collector for StreamerCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        let mut descs = Vec::new();
+        descs.extend(self.attempted.desc());
+        descs.extend(self.succeeded.desc());
+        descs.extend(self.failed.desc());
+        descs.extend(self.too_short.desc());
+        descs.extend(self.borsh_failed.desc());
+        descs.extend(self.round_trip_mismatch.desc());
+        descs.extend(self.reconnects.desc());
+        descs.extend(self.dropped_events.desc());
+        descs.extend(self.processing_latency.desc());
+        descs.extend(self.buffer_high_water_mark.desc());
+        descs
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.refresh();
+        let mut families = Vec::new();
+        families.extend(self.attempted.collect());
+        families.extend(self.succeeded.collect());
+        families.extend(self.failed.collect());
+        families.extend(self.too_short.collect());
+        families.extend(self.borsh_failed.collect());
+        families.extend(self.round_trip_mismatch.collect());
+        families.extend(self.reconnects.collect());
+        families.extend(self.dropped_events.collect());
+        families.extend(self.processing_latency.collect());
+        families.extend(self.buffer_high_water_mark.collect());
+        families
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::event_parser::common::EventType;
+    use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+    use crate::streaming::event_parser::protocols::bonk::{
+        GlobalConfig, BONK_PROGRAM_ID, GLOBAL_CONFIG_DISCRIMINATOR,
+    };
+    use crate::streaming::event_parser::Protocol;
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn parse_counter(families: &[MetricFamily], name: &str, event_type: &str) -> f64 {
+        let family = families.iter().find(|family| family.name() == name).unwrap();
+        let metric = family
+            .get_metric()
+            .iter()
+            .find(|metric| metric.get_label().iter().any(|label| label.value() == event_type))
+            .unwrap();
+        metric.get_counter().get_value()
+    }
+
+    #[test]
+    fn registered_counters_report_parse_results() {
+        // GlobalConfig 账户只在这里解析，其他测试对全局 ParserMetrics 的更新不影响这些计数
+        ParserMetrics::global().set_enabled(true);
+        let registry = Registry::new();
+        register_metrics(&registry).unwrap();
+
+        let global_config = || {
+            AccountPrettyBuilder::new()
+                .owner(BONK_PROGRAM_ID)
+                .discriminator(GLOBAL_CONFIG_DISCRIMINATOR)
+        };
+        let accounts = [
+            global_config().borsh_data(&GlobalConfig::default()).build(),
+            global_config().borsh_data(&GlobalConfig::default()).build(),
+            global_config().data(vec![0; 16]).build(),
+        ];
+        for account in accounts {
+            AccountEventParser::parse_account_event(&[Protocol::Bonk], account, None);
+        }
+
+        let families = registry.gather();
+        let label = EventType::AccountBonkGlobalConfig.metric_label();
+        let counter = |name| parse_counter(&families, name, label);
+        assert_eq!(counter("solana_streamer_parse_attempted_total"), 3.0);
+        assert_eq!(counter("solana_streamer_parse_succeeded_total"), 2.0);
+        assert_eq!(counter("solana_streamer_parse_failed_total"), 1.0);
+        assert_eq!(counter("solana_streamer_parse_too_short_total"), 1.0);
+        assert_eq!(counter("solana_streamer_parse_borsh_failed_total"), 0.0);
+        assert!(families.iter().any(|family| family.name() == "solana_streamer_reconnects_total"));
+    }
+
+    #[test]
+    fn advance_adds_deltas_and_survives_source_reset() {
+        let counter = IntCounter::new("test_total", "test").unwrap();
+        let mut last_seen = 0;
+
+        StreamerCollector::advance(&counter, &mut last_seen, 10);
+        StreamerCollector::advance(&counter, &mut last_seen, 15);
+        assert_eq!(counter.get(), 15);
+
+        // 源计数被重置后重新从 0 累加，导出的计数器继续增长
+        StreamerCollector::advance(&counter, &mut last_seen, 3);
+        assert_eq!(counter.get(), 18);
+        StreamerCollector::advance(&counter, &mut last_seen, 7);
+        assert_eq!(counter.get(), 22);
+        StreamerCollector::advance(&counter, &mut last_seen, 7);
+        assert_eq!(counter.get(), 22);
+    }
+}
@@ -1,11 +1,46 @@
+pub mod adapters;
+#[cfg(feature = "arrow")]
+pub mod arrow_export;
+pub mod broadcast;
 pub mod common;
 pub mod event_parser;
+#[cfg(feature = "geyser")]
+pub mod geyser;
 pub mod grpc;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "metrics-prometheus")]
+pub mod metrics_prometheus;
+pub mod replay;
 pub mod shred;
 pub mod shred_stream;
+pub mod snapshot;
+pub mod typed_dispatcher;
 pub mod yellowstone_grpc;
 pub mod yellowstone_sub_system;
 
+pub use adapters::{
+    DexEventStreamExt, GapDetector, GlobalConfigChangeTracker, LateEventPolicy, LateSlotPolicy,
+    MomentumTracker, OverflowPolicy, PoolTradeTracker, PreviousStateTracker, RateTracker,
+    SequenceStamper, Sequenced, SlotAggregate, SlotAggregator, SlotGap, SlotOrderingBuffer,
+    StateChange, TradingLiveTracker,
+};
+#[cfg(feature = "arrow")]
+pub use arrow_export::{pool_state_arrow_schema, pool_states_to_record_batch};
+pub use broadcast::{BroadcastFanout, BroadcastSubscriber};
+#[cfg(feature = "geyser")]
+pub use geyser::{
+    account_from_geyser, account_from_geyser_v1, account_from_geyser_v2, account_from_geyser_v3,
+};
+#[cfg(feature = "kafka")]
+pub use kafka::{KafkaPayloadFormat, KafkaSink, KafkaSinkConfig};
+#[cfg(feature = "metrics-prometheus")]
+pub use metrics_prometheus::register_metrics;
+pub use replay::{record_to_writer, replay_from_reader, AccountLogReader};
 pub use shred::ShredStreamGrpc;
+pub use snapshot::{decode_accounts, replay_snapshot, SnapshotLiveMerger, SnapshotReader};
+#[cfg(feature = "rayon")]
+pub use snapshot::parse_accounts_par;
+pub use typed_dispatcher::TypedDispatcher;
 pub use yellowstone_grpc::YellowstoneGrpc;
 pub use yellowstone_sub_system::{SystemEvent, TransferInfo};
@@ -0,0 +1,214 @@
+//! 账户更新的录制与回放
+//!
+//! 将收到的 `AccountPretty` 按行写入 JSON 日志，之后离线回放给解析器，用于复现线上的解码问题。
+//!
+//! # 文件格式
+//!
+//! 每行一条 JSON 记录（newline-delimited JSON），空行被忽略：
+//!
+//! ```text
+//! {"slot":1,"signature":"..","pubkey":"..","owner":"..","lamports":0,"rent_epoch":0,
+//!  "executable":false,"write_version":0,"data":".."}
+//! ```
+//!
+//! - `signature`、`pubkey`、`owner` 为 base58 字符串
+//! - `data` 为标准字母表 base64 编码的账户原始数据，与 RPC 返回的格式一致
+//! - 任何一行解析失败时，错误信息带有该行的行号
+//! - 回放时 `recv_us` 设为读取时刻，不使用录制时的值
+
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::event_parser::{DexEvent, Protocol};
+use crate::streaming::grpc::AccountPretty;
+use crate::streaming::snapshot::decode_accounts;
+
+/// 日志中的一条账户记录
+#[derive(Serialize, Deserialize)]
+struct AccountRecord {
+    slot: u64,
+    signature: String,
+    pubkey: String,
+    owner: String,
+    lamports: u64,
+    rent_epoch: u64,
+    executable: bool,
+    #[serde(default)]
+    write_version: u64,
+    data: String,
+}
+
+impl AccountRecord {
+    fn from_account(account: &AccountPretty) -> Self {
+        Self {
+            slot: account.slot,
+            signature: account.signature.to_string(),
+            pubkey: account.pubkey.to_string(),
+            owner: account.owner.to_string(),
+            lamports: account.lamports,
+            rent_epoch: account.rent_epoch,
+            executable: account.executable,
+            write_version: account.write_version,
+            data: STANDARD.encode(&account.data),
+        }
+    }
+
+    fn into_account(self) -> AnyResult<AccountPretty> {
+        Ok(AccountPretty {
+            slot: self.slot,
+            signature: Signature::from_str(&self.signature)?,
+            pubkey: Pubkey::from_str(&self.pubkey)?,
+            executable: self.executable,
+            lamports: self.lamports,
+            owner: Pubkey::from_str(&self.owner)?,
+            rent_epoch: self.rent_epoch,
+            data: STANDARD.decode(&self.data)?,
+            recv_us: get_high_perf_clock(),
+            write_version: self.write_version,
+        })
+    }
+}
+
+/// 将一条账户更新写入日志（一行 JSON）
+pub fn record_to_writer(account: &AccountPretty, writer: &mut impl Write) -> AnyResult<()> {
+    serde_json::to_writer(&mut *writer, &AccountRecord::from_account(account))?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// 账户日志读取器，逐行读取账户记录
+pub struct AccountLogReader<R: BufRead> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> AccountLogReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader, line: String::new(), line_number: 0 }
+    }
+
+    /// 读取下一条账户记录，输入结束时返回 `Ok(None)`
+    pub fn next_account(&mut self) -> AnyResult<Option<AccountPretty>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+            let line = self.line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let record: AccountRecord = serde_json::from_str(line).map_err(|e| {
+                anyhow!("Invalid account log record at line {}: {}", self.line_number, e)
+            })?;
+            return record.into_account().map(Some).map_err(|e| {
+                anyhow!("Invalid account log record at line {}: {}", self.line_number, e)
+            });
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for AccountLogReader<R> {
+    type Item = AnyResult<AccountPretty>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_account().transpose()
+    }
+}
+
+/// 回放 [`record_to_writer`] 写入的日志，惰性地产出解析出的事件
+///
+/// 与 [`decode_accounts`] 相同，未被识别的账户直接跳过，读取或格式错误原样产出
+///
+/// ```ignore
+/// let reader = BufReader::new(File::open("accounts.ndjson")?);
+/// for event in replay_from_reader(reader, Protocol::ALL.to_vec(), None) {
+///     println!("{:?}", event?);
+/// }
+/// ```
+pub fn replay_from_reader(
+    reader: impl BufRead,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+) -> impl Iterator<Item = AnyResult<DexEvent>> {
+    decode_accounts(AccountLogReader::new(reader), protocols, event_type_filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+    use crate::streaming::event_parser::protocols::bonk::{
+        PoolState, BONK_PROGRAM_ID, POOL_STATE_DISCRIMINATOR,
+    };
+    use crate::streaming::grpc::AccountPrettyBuilder;
+
+    fn pool_account(slot: u64, supply: u64) -> AccountPretty {
+        AccountPrettyBuilder::new()
+            .slot(slot)
+            .write_version(slot * 10)
+            .owner(BONK_PROGRAM_ID)
+            .discriminator(POOL_STATE_DISCRIMINATOR)
+            .borsh_data(&PoolState { supply, ..Default::default() })
+            .build()
+    }
+
+    #[test]
+    fn replayed_events_match_live_parse() {
+        let accounts: Vec<_> = (1..=3).map(|i| pool_account(i, i * 1_000)).collect();
+        let mut log = Vec::new();
+        for account in &accounts {
+            record_to_writer(account, &mut log).unwrap();
+        }
+        let first_line = std::str::from_utf8(&log).unwrap().lines().next().unwrap();
+        assert!(first_line.contains(&STANDARD.encode(&accounts[0].data)), "{first_line}");
+
+        let replayed: Vec<_> = replay_from_reader(Cursor::new(log), vec![Protocol::Bonk], None)
+            .collect::<AnyResult<_>>()
+            .unwrap();
+        let live: Vec<_> = accounts
+            .into_iter()
+            .filter_map(|a| AccountEventParser::parse_account_event(&[Protocol::Bonk], a, None))
+            .collect();
+        assert_eq!(replayed.len(), 3);
+        assert_eq!(live.len(), 3);
+        for (replayed, live) in replayed.iter().zip(&live) {
+            assert_eq!(replayed.content_hash(), live.content_hash());
+            assert_eq!(replayed.metadata().write_version, live.metadata().write_version);
+        }
+    }
+
+    #[test]
+    fn record_errors_report_line_number() {
+        let mut log = Vec::new();
+        record_to_writer(&pool_account(1, 1), &mut log).unwrap();
+        log.extend_from_slice(b"\n");
+        let mut bad = Vec::new();
+        record_to_writer(&pool_account(2, 2), &mut bad).unwrap();
+        let bad = String::from_utf8(bad).unwrap();
+        let data = STANDARD.encode(pool_account(2, 2).data);
+        log.extend_from_slice(bad.replace(&data, "not base64!").as_bytes());
+        log.extend_from_slice(b"{\"slot\":\n");
+
+        let mut reader = AccountLogReader::new(Cursor::new(log));
+        assert!(reader.next_account().unwrap().is_some());
+        let error = reader.next_account().unwrap_err();
+        assert!(error.to_string().contains("at line 3"), "{error}");
+        let error = reader.next_account().unwrap_err();
+        assert!(error.to_string().contains("at line 4"), "{error}");
+    }
+}
@@ -25,7 +25,7 @@ impl ShredStreamGrpc {
     /// 创建客户端，使用自定义配置
     pub async fn new_with_config(endpoint: String, config: StreamClientConfig) -> AnyResult<Self> {
         let shredstream_client = ShredstreamProxyClient::connect(endpoint.clone()).await?;
-        MetricsManager::init(config.enable_metrics);
+        MetricsManager::init_on(config.enable_metrics, config.runtime.as_ref());
         Ok(Self {
             shredstream_client: Arc::new(shredstream_client),
             config,
@@ -60,7 +60,7 @@ impl ShredStreamGrpc {
 
     /// 启动自动性能监控任务
     pub async fn start_auto_metrics_monitoring(&self) {
-        MetricsManager::global().start_auto_monitoring().await;
+        MetricsManager::global().start_auto_monitoring_on(self.config.runtime.as_ref()).await;
     }
 
     /// 停止当前订阅
@@ -34,7 +34,8 @@ impl ShredStreamGrpc {
         let mut metrics_handle = None;
         // 启动自动性能监控（如果启用）
         if self.config.enable_metrics {
-            metrics_handle = MetricsManager::global().start_auto_monitoring().await;
+            metrics_handle =
+                MetricsManager::global().start_auto_monitoring_on(self.config.runtime.as_ref()).await;
         }
 
         // 启动流处理
@@ -45,7 +46,7 @@ impl ShredStreamGrpc {
         // Wrap callback once before the async block
         let callback = Arc::new(callback);
 
-        let stream_task = tokio::spawn(async move {
+        let stream_task = self.config.spawn(async move {
             while let Some(message) = stream.next().await {
                 match message {
                     Ok(msg) => {
@@ -0,0 +1,439 @@
+//! Geyser 快照文件读取
+//!
+//! 部分数据提供商会定期分发某个程序全部账户的快照文件，用于回填状态。
+//! 读取快照并将每个账户作为 `AccountPretty` 交给 `AccountEventParser` 解析，
+//! 无需大量调用 RPC 即可重建状态。
+//!
+//! # 文件格式
+//!
+//! 文件由若干条连续的长度前缀记录组成，没有文件头：
+//!
+//! ```text
+//! record  := len:u32 payload[len]
+//! payload := pubkey[32] owner[32] lamports:u64 rent_epoch:u64 executable:u8 slot:u64 data[..]
+//! ```
+//!
+//! - 所有整数均为小端序
+//! - `executable` 为 0 或 1
+//! - `data` 为 payload 剩余的全部字节（账户原始数据）
+//! - 文件在记录边界处结束；在记录中间结束视为文件损坏
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::AnyResult;
+use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::high_performance_clock::get_high_perf_clock;
+use crate::streaming::event_parser::core::account_event_parser::AccountEventParser;
+use crate::streaming::event_parser::{DexEvent, Protocol};
+use crate::streaming::grpc::AccountPretty;
+
+/// 记录 payload 中固定头部的长度（不含账户数据）
+pub const SNAPSHOT_RECORD_HEADER_LEN: usize = 32 + 32 + 8 + 8 + 1 + 8;
+
+/// 单条记录 payload 的最大长度：固定头部加上 Solana 账户数据的上限（10 MiB）
+///
+/// 长度前缀超过此值的记录视为文件损坏，在分配缓冲区之前拒绝
+pub const SNAPSHOT_MAX_RECORD_LEN: usize = SNAPSHOT_RECORD_HEADER_LEN + 10 * 1024 * 1024;
+
+/// 快照文件读取器，逐条读取账户记录
+pub struct SnapshotReader<R: Read> {
+    reader: R,
+}
+
+impl SnapshotReader<BufReader<File>> {
+    /// 打开快照文件
+    pub fn open(path: impl AsRef<Path>) -> AnyResult<Self> {
+        let file = File::open(path)?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R: Read> SnapshotReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// 读取下一条账户记录，文件在记录边界处结束时返回 `Ok(None)`
+    ///
+    /// 长度前缀只读到 1~3 字节即结束的文件视为被截断，返回错误
+    pub fn next_account(&mut self) -> AnyResult<Option<AccountPretty>> {
+        let mut len_buf = [0u8; 4];
+        let mut read = 0;
+        while read < len_buf.len() {
+            match self.reader.read(&mut len_buf[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        match read {
+            0 => return Ok(None),
+            4 => {}
+            _ => return Err(anyhow!("Truncated snapshot record length: {} of 4 bytes", read)),
+        }
+        let len = u32::from_le_bytes(len_buf) as usize;
+        if len < SNAPSHOT_RECORD_HEADER_LEN {
+            return Err(anyhow!(
+                "Snapshot record too short: {} bytes, expected at least {}",
+                len,
+                SNAPSHOT_RECORD_HEADER_LEN
+            ));
+        }
+        if len > SNAPSHOT_MAX_RECORD_LEN {
+            return Err(anyhow!(
+                "Snapshot record too long: {} bytes, expected at most {}",
+                len,
+                SNAPSHOT_MAX_RECORD_LEN
+            ));
+        }
+
+        let mut payload = vec![0u8; len];
+        self.reader
+            .read_exact(&mut payload)
+            .map_err(|e| anyhow!("Truncated snapshot record ({} bytes): {}", len, e))?;
+
+        Ok(Some(Self::decode_record(payload)?))
+    }
+
+    fn decode_record(mut payload: Vec<u8>) -> AnyResult<AccountPretty> {
+        let pubkey = Pubkey::new_from_array(payload[0..32].try_into()?);
+        let owner = Pubkey::new_from_array(payload[32..64].try_into()?);
+        let lamports = u64::from_le_bytes(payload[64..72].try_into()?);
+        let rent_epoch = u64::from_le_bytes(payload[72..80].try_into()?);
+        let executable = match payload[80] {
+            0 => false,
+            1 => true,
+            other => return Err(anyhow!("Invalid executable flag in snapshot record: {}", other)),
+        };
+        let slot = u64::from_le_bytes(payload[81..89].try_into()?);
+        // 复用 payload 缓冲区作为账户数据
+        let data = payload.split_off(SNAPSHOT_RECORD_HEADER_LEN);
+
+        Ok(AccountPretty {
+            slot,
+            pubkey,
+            executable,
+            lamports,
+            owner,
+            rent_epoch,
+            data,
+            recv_us: get_high_perf_clock(),
+            ..Default::default()
+        })
+    }
+}
+
+impl<R: Read> Iterator for SnapshotReader<R> {
+    type Item = AnyResult<AccountPretty>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_account().transpose()
+    }
+}
+
+/// 逐条解码账户记录，惰性地产出解析出的事件
+///
+/// 每次迭代只读取并解析一条记录，不会缓冲整个输入，内存占用与输入大小无关，
+/// 适合处理数 GB 的快照文件。未被任何解析器识别（或被事件类型过滤掉）的账户直接跳过，
+/// 读取错误原样产出。已在内存中的 `AccountPretty` 可通过 `.map(Ok)` 传入
+///
+/// ```ignore
+/// let reader = SnapshotReader::open("pools.snap")?;
+/// for event in decode_accounts(reader, vec![Protocol::Bonk], None) {
+///     handle(event?);
+/// }
+/// ```
+pub fn decode_accounts<I>(
+    accounts: I,
+    protocols: Vec<Protocol>,
+    event_type_filter: Option<EventTypeFilter>,
+) -> impl Iterator<Item = AnyResult<DexEvent>>
+where
+    I: IntoIterator<Item = AnyResult<AccountPretty>>,
+{
+    accounts.into_iter().filter_map(move |account| match account {
+        Ok(account) => {
+            AccountEventParser::parse_account_event(&protocols, account, event_type_filter.as_ref())
+                .map(Ok)
+        }
+        Err(e) => Some(Err(e)),
+    })
+}
+
+/// 在 rayon 线程池上并行解析一批账户，结果与输入一一对应、顺序一致
+///
+/// 适合回填大量快照账户；每个账户的解析相互独立，结果与逐个调用
+/// `AccountEventParser::parse_account_event` 相同
+#[cfg(feature = "rayon")]
+pub fn parse_accounts_par(
+    accounts: &[AccountPretty],
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+) -> Vec<Option<DexEvent>> {
+    use rayon::prelude::*;
+
+    accounts
+        .par_iter()
+        .map(|account| {
+            AccountEventParser::parse_account_event(protocols, account.clone(), event_type_filter)
+        })
+        .collect()
+}
+
+/// 读取快照文件并将每个账户交给解析器，解析出的事件传给回调
+///
+/// 返回读取的账户记录数量
+pub fn replay_snapshot<F>(
+    path: impl AsRef<Path>,
+    protocols: &[Protocol],
+    event_type_filter: Option<&EventTypeFilter>,
+    callback: F,
+) -> AnyResult<usize>
+where
+    F: Fn(DexEvent),
+{
+    let mut reader = SnapshotReader::open(path)?;
+    let mut count = 0;
+    while let Some(account) = reader.next_account()? {
+        count += 1;
+        if let Some(event) =
+            AccountEventParser::parse_account_event(protocols, account, event_type_filter)
+        {
+            callback(event);
+        }
+    }
+    Ok(count)
+}
+
+/// 快照与实时事件的合并阶段
+enum MergePhase {
+    /// 快照加载中，实时事件暂存在缓冲区
+    Buffering(VecDeque<DexEvent>),
+    /// 快照已加载完成，实时事件直接交给回调
+    Live,
+}
+
+/// 合并快照与实时事件，保证下游缓存先应用完整快照再应用实时更新
+///
+/// 快照加载期间实时事件按到达顺序暂存（最多 `capacity` 条），快照事件直接交给回调。
+/// 调用 [`SnapshotLiveMerger::finish_snapshot`] 时按顺序回放缓冲的实时事件，之后实时事件
+/// 直接交给回调。缓冲区已满时丢弃最早的实时事件并计数，账户更新由之后的更新覆盖
+///
+/// ```ignore
+/// let merger = SnapshotLiveMerger::new(100_000, |event| cache.apply(event));
+/// grpc.subscribe_events_immediate(..., merger.live_callback()).await?;
+/// merger.replay_snapshot("pools.snap", &[Protocol::Bonk], None)?;
+/// ```
+pub struct SnapshotLiveMerger<F> {
+    callback: F,
+    capacity: usize,
+    phase: Mutex<MergePhase>,
+    dropped_count: AtomicU64,
+}
+
+impl<F> SnapshotLiveMerger<F>
+where
+    F: Fn(DexEvent) + Send + Sync + 'static,
+{
+    /// 创建合并器，`capacity` 为快照加载期间最多缓冲的实时事件数量
+    pub fn new(capacity: usize, callback: F) -> Arc<Self> {
+        Arc::new(Self {
+            callback,
+            capacity: capacity.max(1),
+            phase: Mutex::new(MergePhase::Buffering(VecDeque::new())),
+            dropped_count: AtomicU64::new(0),
+        })
+    }
+
+    /// 生成传给实时订阅接口的回调
+    pub fn live_callback(self: &Arc<Self>) -> impl Fn(DexEvent) + Send + Sync + 'static {
+        let merger = Arc::clone(self);
+        move |event: DexEvent| merger.on_live_event(event)
+    }
+
+    fn on_live_event(&self, event: DexEvent) {
+        let mut phase = self.phase.lock();
+        if let MergePhase::Buffering(buffer) = &mut *phase {
+            if buffer.len() >= self.capacity {
+                buffer.pop_front();
+                self.dropped_count.fetch_add(1, Ordering::Relaxed);
+            }
+            buffer.push_back(event);
+            return;
+        }
+        // 直通模式下不持有锁调用回调
+        drop(phase);
+        (self.callback)(event);
+    }
+
+    /// 应用一个快照事件
+    pub fn apply_snapshot_event(&self, event: DexEvent) {
+        (self.callback)(event);
+    }
+
+    /// 快照加载完成：按顺序回放缓冲的实时事件并切换到直通模式，返回回放的事件数量
+    ///
+    /// 回放期间持有锁，新到达的实时事件会等待回放结束，保证顺序。重复调用返回 0
+    pub fn finish_snapshot(&self) -> usize {
+        let mut phase = self.phase.lock();
+        let MergePhase::Buffering(buffer) = std::mem::replace(&mut *phase, MergePhase::Live) else {
+            return 0;
+        };
+        let count = buffer.len();
+        for event in buffer {
+            (self.callback)(event);
+        }
+        count
+    }
+
+    /// 读取快照文件并应用其中的事件，完成后调用 [`SnapshotLiveMerger::finish_snapshot`]
+    ///
+    /// 返回读取的账户记录数量。读取失败时不会切换到直通模式
+    pub fn replay_snapshot(
+        &self,
+        path: impl AsRef<Path>,
+        protocols: &[Protocol],
+        event_type_filter: Option<&EventTypeFilter>,
+    ) -> AnyResult<usize> {
+        let count = replay_snapshot(path, protocols, event_type_filter, |event| {
+            self.apply_snapshot_event(event)
+        })?;
+        self.finish_snapshot();
+        Ok(count)
+    }
+
+    /// 快照是否已加载完成
+    pub fn is_live(&self) -> bool {
+        matches!(*self.phase.lock(), MergePhase::Live)
+    }
+
+    /// 当前缓冲的实时事件数量
+    pub fn buffered_len(&self) -> usize {
+        match &*self.phase.lock() {
+            MergePhase::Buffering(buffer) => buffer.len(),
+            MergePhase::Live => 0,
+        }
+    }
+
+    /// 因缓冲区已满而丢弃的实时事件数量
+    pub fn get_dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn record(pubkey: Pubkey, owner: Pubkey, slot: u64, data: &[u8]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(pubkey.as_ref());
+        payload.extend_from_slice(owner.as_ref());
+        payload.extend_from_slice(&5_000u64.to_le_bytes());
+        payload.extend_from_slice(&u64::MAX.to_le_bytes());
+        payload.push(0);
+        payload.extend_from_slice(&slot.to_le_bytes());
+        payload.extend_from_slice(data);
+        let mut record = (payload.len() as u32).to_le_bytes().to_vec();
+        record.extend(payload);
+        record
+    }
+
+    #[test]
+    fn reads_records_until_eof() {
+        let (pubkey, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut file = record(pubkey, owner, 7, &[1, 2, 3]);
+        file.extend(record(owner, pubkey, 8, &[]));
+
+        let accounts: Vec<_> =
+            SnapshotReader::new(Cursor::new(file)).collect::<AnyResult<_>>().unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].pubkey, pubkey);
+        assert_eq!(accounts[0].owner, owner);
+        assert_eq!(accounts[0].slot, 7);
+        assert_eq!(accounts[0].lamports, 5_000);
+        assert_eq!(accounts[0].data, vec![1, 2, 3]);
+        assert!(accounts[1].data.is_empty());
+    }
+
+    #[test]
+    fn rejects_oversized_length_prefix() {
+        let file = u32::MAX.to_le_bytes().to_vec();
+        let error = SnapshotReader::new(Cursor::new(file)).next_account().unwrap_err();
+        assert!(error.to_string().contains("too long"), "{error}");
+    }
+
+    #[test]
+    fn partial_length_prefix_is_a_truncation_error() {
+        let mut file = record(Pubkey::new_unique(), Pubkey::new_unique(), 1, &[1, 2, 3]);
+        let record_len = file.len();
+        for partial in 1..4 {
+            file.truncate(record_len);
+            file.extend_from_slice(&[0xFF; 3][..partial]);
+            let mut reader = SnapshotReader::new(Cursor::new(file.clone()));
+            assert!(reader.next_account().unwrap().is_some());
+            let error = reader.next_account().unwrap_err();
+            assert!(error.to_string().contains("Truncated snapshot record length"), "{error}");
+        }
+
+        let mut reader = SnapshotReader::new(Cursor::new(Vec::new()));
+        assert!(reader.next_account().unwrap().is_none());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_parsing_matches_serial_parsing() {
+        use crate::streaming::event_parser::protocols::bonk::{
+            GlobalConfig, PoolState, BONK_PROGRAM_ID, GLOBAL_CONFIG_DISCRIMINATOR,
+            POOL_STATE_DISCRIMINATOR,
+        };
+        use crate::streaming::grpc::AccountPrettyBuilder;
+
+        // 池子、全局配置和无法解析的账户交错排列
+        let accounts: Vec<_> = (0..2_000u64)
+            .map(|i| {
+                let builder = AccountPrettyBuilder::new().owner(BONK_PROGRAM_ID).slot(i);
+                match i % 3 {
+                    0 => builder
+                        .discriminator(POOL_STATE_DISCRIMINATOR)
+                        .borsh_data(&PoolState { real_base: i, ..Default::default() }),
+                    1 => builder
+                        .discriminator(GLOBAL_CONFIG_DISCRIMINATOR)
+                        .borsh_data(&GlobalConfig { index: i as u16, ..Default::default() }),
+                    _ => builder.data(vec![i as u8; 16]),
+                }
+                .build()
+            })
+            .collect();
+        let protocols = [Protocol::Bonk];
+
+        // 解析时写入的接收时间不同，按 content_hash 比较
+        let hash = |event: &Option<DexEvent>| event.as_ref().map(DexEvent::content_hash);
+        let parallel = parse_accounts_par(&accounts, &protocols, None);
+        assert_eq!(parallel.len(), accounts.len());
+        for (account, parallel) in accounts.iter().zip(&parallel) {
+            let serial = AccountEventParser::parse_account_event(&protocols, account.clone(), None);
+            assert_eq!(hash(parallel), hash(&serial), "slot {}", account.slot);
+        }
+        assert!(parallel.iter().any(Option::is_some));
+    }
+
+    #[test]
+    fn rejects_truncated_record() {
+        let mut file = record(Pubkey::new_unique(), Pubkey::new_unique(), 1, &[1, 2, 3]);
+        file.truncate(file.len() - 1);
+        assert!(SnapshotReader::new(Cursor::new(file)).next_account().is_err());
+    }
+}
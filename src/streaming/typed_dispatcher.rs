@@ -0,0 +1,83 @@
+//! 按事件类型分发到类型化通道
+//!
+//! 消费者为关心的事件类型注册各自的 `Sender`，直接收到具体的事件结构体而无需再匹配
+//! `DexEvent`。没有注册的类型发送到默认通道（未设置则丢弃）。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use crate::streaming::event_parser::core::TypedEvent;
+use crate::streaming::event_parser::DexEvent;
+
+/// 尝试投递事件：类型匹配时返回 `Ok(是否投递成功)`，不匹配时原样返回事件
+type Route = Box<dyn Fn(DexEvent) -> Result<bool, Box<DexEvent>> + Send + Sync>;
+
+/// 类型化事件分发器
+///
+/// ```ignore
+/// let (pool_tx, pool_rx) = mpsc::channel::<BonkPoolStateAccountEvent>(1024);
+/// let (other_tx, other_rx) = mpsc::channel::<DexEvent>(1024);
+/// let dispatcher = Arc::new(TypedDispatcher::new().on(pool_tx).default_channel(other_tx));
+/// grpc.subscribe_events_immediate(..., dispatcher.into_callback()).await?;
+/// ```
+#[derive(Default)]
+pub struct TypedDispatcher {
+    routes: Vec<Route>,
+    default_tx: Option<mpsc::Sender<DexEvent>>,
+    dropped_count: AtomicU64,
+}
+
+impl TypedDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 将类型为 `T` 的事件发送到 `tx`，同一类型重复注册时只有第一个生效
+    pub fn on<T: TypedEvent>(mut self, tx: mpsc::Sender<T>) -> Self {
+        let route = move |event: DexEvent| T::from_dex_event(event).map(|e| tx.try_send(e).is_ok());
+        self.routes.push(Box::new(route));
+        self
+    }
+
+    /// 未注册类型的事件发送到 `tx`
+    pub fn default_channel(mut self, tx: mpsc::Sender<DexEvent>) -> Self {
+        self.default_tx = Some(tx);
+        self
+    }
+
+    /// 分发单个事件
+    ///
+    /// 使用 `try_send`，不会阻塞调用方；通道已满或已关闭时事件被丢弃并计数
+    pub fn dispatch(&self, mut event: DexEvent) {
+        for route in &self.routes {
+            match route(event) {
+                Ok(delivered) => {
+                    if !delivered {
+                        self.dropped_count.fetch_add(1, Ordering::Relaxed);
+                    }
+                    return;
+                }
+                Err(unmatched) => event = *unmatched,
+            }
+        }
+        let delivered = match &self.default_tx {
+            Some(tx) => tx.try_send(event).is_ok(),
+            None => false,
+        };
+        if !delivered {
+            self.dropped_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 生成可直接传给订阅接口的回调
+    pub fn into_callback(self: Arc<Self>) -> impl Fn(DexEvent) + Send + Sync + 'static {
+        move |event: DexEvent| self.dispatch(event)
+    }
+
+    /// 因通道已满、已关闭或没有默认通道而丢弃的事件数量
+    pub fn get_dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+}
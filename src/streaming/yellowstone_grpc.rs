@@ -1,9 +1,10 @@
 use crate::common::AnyResult;
 use crate::streaming::common::{
-    process_grpc_transaction, MetricsManager, PerformanceMetrics, StreamClientConfig,
-    SubscriptionHandle,
+    process_grpc_transaction, BackpressureStrategy, MetricsManager, PerformanceMetrics,
+    PubkeyWaiters, StreamClientConfig, SubscriptionHandle,
 };
 use crate::streaming::event_parser::common::filter::EventTypeFilter;
+use crate::streaming::event_parser::common::Commitment;
 use crate::streaming::event_parser::{Protocol, DexEvent};
 use crate::streaming::grpc::pool::factory;
 use crate::streaming::grpc::{EventPretty, SubscriptionManager};
@@ -15,7 +16,10 @@ use log::error;
 use solana_sdk::pubkey::Pubkey;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::Mutex;
+use tokio::time::error::Elapsed;
 use yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof;
 use yellowstone_grpc_proto::geyser::{
     CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccountsFilter, SubscribeRequestPing,
@@ -49,6 +53,8 @@ pub struct YellowstoneGrpc {
     pub current_request: Arc<tokio::sync::RwLock<Option<SubscribeRequest>>>,
 
     pub event_type_filter: Arc<tokio::sync::RwLock<Option<EventTypeFilter>>>,
+    /// 等待指定账户下一次更新的调用方
+    pub waiters: PubkeyWaiters,
 }
 
 impl YellowstoneGrpc {
@@ -66,7 +72,7 @@ impl YellowstoneGrpc {
         let _ = rustls::crypto::ring::default_provider().install_default().ok();
         let subscription_manager =
             SubscriptionManager::new(endpoint.clone(), x_token.clone(), config.clone());
-        MetricsManager::init(config.enable_metrics);
+        MetricsManager::init_on(config.enable_metrics, config.runtime.as_ref());
 
         Ok(Self {
             endpoint,
@@ -78,6 +84,7 @@ impl YellowstoneGrpc {
             control_tx: Arc::new(tokio::sync::Mutex::new(None)),
             current_request: Arc::new(tokio::sync::RwLock::new(None)),
             event_type_filter: Arc::new(tokio::sync::RwLock::new(None)),
+            waiters: PubkeyWaiters::new(),
         })
     }
 
@@ -117,6 +124,22 @@ impl YellowstoneGrpc {
         self.active_subscription.store(false, Ordering::Release);
     }
 
+    /// 等待指定账户的下一次更新事件
+    ///
+    /// 需要先通过 `subscribe_events_immediate` 订阅包含该账户的数据，
+    /// 在 `timeout` 内没有收到该账户的事件时返回 `Elapsed`
+    pub async fn next_for(&self, pubkey: Pubkey, timeout: Duration) -> Result<DexEvent, Elapsed> {
+        let rx = self.waiters.register(pubkey);
+        tokio::time::timeout(timeout, async move {
+            match rx.await {
+                Ok(event) => event,
+                // 登记表中的发送端不会被提前丢弃，这里仅作兜底，等待超时
+                Err(_) => std::future::pending().await,
+            }
+        })
+        .await
+    }
+
     /// Simplified immediate event subscription (recommended for simple scenarios)
     ///
     /// # Parameters
@@ -155,7 +178,8 @@ impl YellowstoneGrpc {
         let mut metrics_handle = None;
         // 启动自动性能监控（如果启用）
         if self.config.enable_metrics {
-            metrics_handle = MetricsManager::global().start_auto_monitoring().await;
+            metrics_handle =
+                MetricsManager::global().start_auto_monitoring_on(self.config.runtime.as_ref()).await;
         }
 
         let transactions = self
@@ -166,10 +190,22 @@ impl YellowstoneGrpc {
             .subscribe_with_account_request(account_filter, event_type_filter.as_ref());
 
         // 订阅事件
+        // 在配置的 runtime 上建立连接，使底层连接任务也运行在该 runtime 上
+        let subscription_manager = self.subscription_manager.clone();
+        let connect_filter = event_type_filter.clone();
         let (subscribe_tx, mut stream, subscribe_request) = self
-            .subscription_manager
-            .subscribe_with_request(transactions, accounts, commitment, event_type_filter.as_ref())
-            .await?;
+            .config
+            .run(async move {
+                subscription_manager
+                    .subscribe_with_request(
+                        transactions,
+                        accounts,
+                        commitment,
+                        connect_filter.as_ref(),
+                    )
+                    .await
+            })
+            .await??;
 
         // 用 Arc<Mutex<>> 包装 subscribe_tx 以支持多线程共享
         let subscribe_tx = Arc::new(Mutex::new(subscribe_tx));
@@ -178,45 +214,65 @@ impl YellowstoneGrpc {
         *self.control_tx.lock().await = Some(control_tx);
 
         // Wrap callback once before the async block
-        let callback = Arc::new(callback);
+        // 每个事件先交给 next_for 的等待者，再交给用户回调
+        // 与 SubscriptionManager::subscribe_with_request 的默认值一致
+        let commitment = Commitment::from(commitment.unwrap_or(CommitmentLevel::Confirmed));
+        let waiters = self.waiters.clone();
+        let callback = Arc::new(move |mut event: DexEvent| {
+            event.metadata_mut().commitment = Some(commitment);
+            waiters.notify(&event);
+            callback(event);
+        });
+
+        // 网络读取与解析之间的有界缓冲区
+        let backpressure_strategy = self.config.backpressure.strategy;
+        let excluded_owners = self.config.excluded_owners();
+        let min_lamports = self.config.min_lamports;
+        let (buffer_tx, mut buffer_rx) =
+            tokio::sync::mpsc::channel::<EventPretty>(self.config.backpressure.permits.max(1));
+
+        // 解析任务：从缓冲区消费事件并解析
+        let event_handle = self.config.spawn(async move {
+            while let Some(event_pretty) = buffer_rx.recv().await {
+                if let Err(e) = process_grpc_transaction(
+                    event_pretty,
+                    &protocols,
+                    event_type_filter.as_ref(),
+                    callback.clone(),
+                    bot_wallet,
+                )
+                .await
+                {
+                    error!("Error processing event: {e:?}");
+                }
+            }
+        });
 
-        let stream_handle = tokio::spawn(async move {
+        // 读取任务：从 gRPC 流读取消息并推入缓冲区
+        let stream_handle = self.config.spawn(async move {
             loop {
                 tokio::select! {
                     message = stream.next() => {
                         match message {
                             Some(Ok(msg)) => {
                                 let created_at = msg.created_at;
-                                match msg.update_oneof {
+                                let event_pretty = match msg.update_oneof {
                                     Some(UpdateOneof::Account(account)) => {
                                         let account_pretty = factory::create_account_pretty_pooled(account);
-                                        log::debug!("Received account: {:?}", account_pretty);
-                                        if let Err(e) = process_grpc_transaction(
-                                            EventPretty::Account(account_pretty),
-                                            &protocols,
-                                            event_type_filter.as_ref(),
-                                            callback.clone(),
-                                            bot_wallet,
-                                        )
-                                        .await
-                                        {
-                                            error!("Error processing account event: {e:?}");
+                                        if excluded_owners.contains(&account_pretty.owner) {
+                                            continue;
                                         }
+                                        if account_pretty.lamports < min_lamports {
+                                            MetricsManager::global().increment_low_lamports_filtered();
+                                            continue;
+                                        }
+                                        log::debug!("Received account: {:?}", account_pretty);
+                                        EventPretty::Account(account_pretty)
                                     }
                                     Some(UpdateOneof::BlockMeta(sut)) => {
                                         let block_meta_pretty = factory::create_block_meta_pretty_pooled(sut, created_at);
                                         log::debug!("Received block meta: {:?}", block_meta_pretty);
-                                        if let Err(e) = process_grpc_transaction(
-                                            EventPretty::BlockMeta(block_meta_pretty),
-                                            &protocols,
-                                            event_type_filter.as_ref(),
-                                            callback.clone(),
-                                            bot_wallet,
-                                        )
-                                        .await
-                                        {
-                                            error!("Error processing block meta event: {e:?}");
-                                        }
+                                        EventPretty::BlockMeta(block_meta_pretty)
                                     }
                                     Some(UpdateOneof::Transaction(sut)) => {
                                         let transaction_pretty = factory::create_transaction_pretty_pooled(sut, created_at);
@@ -225,17 +281,7 @@ impl YellowstoneGrpc {
                                             transaction_pretty.signature,
                                             transaction_pretty.slot
                                         );
-                                        if let Err(e) = process_grpc_transaction(
-                                            EventPretty::Transaction(transaction_pretty),
-                                            &protocols,
-                                            event_type_filter.as_ref(),
-                                            callback.clone(),
-                                            bot_wallet,
-                                        )
-                                        .await
-                                        {
-                                            error!("Error processing transaction event: {e:?}");
-                                        }
+                                        EventPretty::Transaction(transaction_pretty)
                                     }
                                     Some(UpdateOneof::Ping(_)) => {
                                         // 只在需要时获取锁，并立即释放
@@ -248,13 +294,20 @@ impl YellowstoneGrpc {
                                                 .await;
                                         }
                                         log::debug!("service is ping: {}", Local::now());
+                                        continue;
                                     }
                                     Some(UpdateOneof::Pong(_)) => {
                                         log::debug!("service is pong: {}", Local::now());
+                                        continue;
                                     }
                                     _ => {
                                         log::debug!("Received other message type");
+                                        continue;
                                     }
+                                };
+                                if !Self::push_to_buffer(&buffer_tx, event_pretty, backpressure_strategy).await {
+                                    error!("Event buffer closed, stopping stream");
+                                    break;
                                 }
                             }
                             Some(Err(error)) => {
@@ -275,13 +328,42 @@ impl YellowstoneGrpc {
         });
 
         // 保存订阅句柄
-        let subscription_handle = SubscriptionHandle::new(stream_handle, None, metrics_handle);
+        let subscription_handle =
+            SubscriptionHandle::new(stream_handle, Some(event_handle), metrics_handle);
         let mut handle_guard = self.subscription_handle.lock().await;
         *handle_guard = Some(subscription_handle);
 
         Ok(())
     }
 
+    /// 将事件推入解析缓冲区，缓冲区已满时按照背压策略处理
+    ///
+    /// 返回 `false` 表示解析任务已退出，读取任务应当停止
+    async fn push_to_buffer(
+        buffer_tx: &tokio::sync::mpsc::Sender<EventPretty>,
+        event_pretty: EventPretty,
+        strategy: BackpressureStrategy,
+    ) -> bool {
+        let metrics = MetricsManager::global();
+        match buffer_tx.try_send(event_pretty) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event_pretty)) => {
+                metrics.increment_buffer_overflow();
+                match strategy {
+                    BackpressureStrategy::Block => {
+                        if buffer_tx.send(event_pretty).await.is_err() {
+                            return false;
+                        }
+                    }
+                    BackpressureStrategy::Drop => metrics.increment_dropped_events(),
+                }
+            }
+            Err(TrySendError::Closed(_)) => return false,
+        }
+        metrics.record_buffer_depth(buffer_tx.max_capacity() - buffer_tx.capacity());
+        true
+    }
+
     /// Update subscription filters at runtime without reconnection
     ///
     /// # Parameters
@@ -356,6 +438,7 @@ impl Clone for YellowstoneGrpc {
             control_tx: self.control_tx.clone(),
             event_type_filter: self.event_type_filter.clone(),
             current_request: self.current_request.clone(),
+            waiters: self.waiters.clone(),
         }
     }
 }
@@ -52,7 +52,7 @@ impl YellowstoneGrpc {
 
         let callback = Box::new(callback);
 
-        tokio::spawn(async move {
+        self.config.spawn(async move {
             while let Some(message) = stream.next().await {
                 match message {
                     Ok(msg) => {
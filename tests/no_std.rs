@@ -0,0 +1,34 @@
+//! 关闭默认 feature、只启用 `no-std` 时 crate 仍能编译，且不依赖流式订阅相关的 crate
+//!
+//! cdylib 需要全局分配器和 panic handler，由最终链接的程序提供，这里只编译 rlib
+
+use std::process::Command;
+
+fn cargo(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO"))
+        .args(args)
+        .args(["--no-default-features", "--features", "no-std"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("CARGO_TARGET_DIR", concat!(env!("CARGO_TARGET_TMPDIR"), "/no-std"))
+        .output()
+        .expect("failed to run cargo");
+    assert!(
+        output.status.success(),
+        "cargo {args:?} failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn decode_module_builds_without_std() {
+    cargo(&["rustc", "--lib", "--crate-type", "rlib"]);
+}
+
+#[test]
+fn no_std_build_leaves_out_streaming_dependencies() {
+    let tree = cargo(&["tree", "--edges", "normal", "--prefix", "none"]);
+    for dependency in ["tokio ", "tonic ", "yellowstone-grpc-client ", "solana-sdk "] {
+        assert!(!tree.contains(dependency), "{dependency}found in:\n{tree}");
+    }
+}